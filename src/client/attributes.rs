@@ -0,0 +1,221 @@
+use crate::uri::url_encode;
+
+use aws_sdk::types::{RequestPayer, StorageClass};
+use std::collections::{BTreeMap, HashMap};
+
+/// Attributes of an uploaded object.
+///
+/// These are set when the upload of the object is created.  The expected
+/// bucket owner and whether the requester pays are about the bucket rather
+/// than the object, so they are sent with every request of the upload, see
+/// [`BucketAccess`].
+///
+/// Attributes can be attached to each object URI from an [`ObjectUriIter`],
+/// and the ones that an object does not have are taken from the defaults of
+/// the upload.  The content type falls back to the one of the [`PartEncoder`]
+/// if it is set nowhere else.
+///
+/// [`ObjectUriIter`]: crate::uri::ObjectUriIter
+/// [`PartEncoder`]: crate::codec::PartEncoder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectAttributes {
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+    cache_control: Option<String>,
+    metadata: BTreeMap<String, String>,
+    tags: BTreeMap<String, String>,
+    storage_class: Option<StorageClass>,
+    access: BucketAccess,
+}
+
+impl ObjectAttributes {
+    /// Create a new, empty `ObjectAttributes`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `Content-Type` of the object.
+    pub fn with_content_type<T: Into<String>>(self, content_type: T) -> Self {
+        Self {
+            content_type: Some(content_type.into()),
+            ..self
+        }
+    }
+
+    /// Set the `Content-Encoding` of the object.
+    pub fn with_content_encoding<T: Into<String>>(self, encoding: T) -> Self {
+        Self {
+            content_encoding: Some(encoding.into()),
+            ..self
+        }
+    }
+
+    /// Set the `Cache-Control` of the object.
+    pub fn with_cache_control<T: Into<String>>(self, cache_control: T) -> Self {
+        Self {
+            cache_control: Some(cache_control.into()),
+            ..self
+        }
+    }
+
+    /// Add the pair `key` and `value` to the user-defined metadata.
+    pub fn with_metadata<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add the tag `key` with `value` to the object.
+    pub fn with_tag<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the storage class of the object.
+    pub fn with_storage_class(self, storage_class: StorageClass) -> Self {
+        Self {
+            storage_class: Some(storage_class),
+            ..self
+        }
+    }
+
+    /// Set the account ID that the bucket is expected to be owned by.
+    pub fn with_expected_bucket_owner<T: Into<String>>(mut self, owner: T) -> Self {
+        self.access.expected_owner = Some(owner.into());
+        self
+    }
+
+    /// Set whether the requester agrees to pay for the requests, which is
+    /// required if the bucket is configured so.
+    pub fn with_requester_pays(mut self, requester_pays: bool) -> Self {
+        self.access.requester_pays = requester_pays;
+        self
+    }
+
+    /// Fill in what is not set here from `defaults`.
+    ///
+    /// Metadata and tags are combined, with the values here taking precedence.
+    pub fn or(self, defaults: &ObjectAttributes) -> Self {
+        let mut metadata = defaults.metadata.clone();
+        metadata.extend(self.metadata);
+        let mut tags = defaults.tags.clone();
+        tags.extend(self.tags);
+        let access = BucketAccess {
+            expected_owner: self
+                .access
+                .expected_owner
+                .or_else(|| defaults.access.expected_owner.clone()),
+            requester_pays: self.access.requester_pays || defaults.access.requester_pays,
+        };
+        Self {
+            content_type: self.content_type.or_else(|| defaults.content_type.clone()),
+            content_encoding: self
+                .content_encoding
+                .or_else(|| defaults.content_encoding.clone()),
+            cache_control: self
+                .cache_control
+                .or_else(|| defaults.cache_control.clone()),
+            metadata,
+            tags,
+            storage_class: self
+                .storage_class
+                .or_else(|| defaults.storage_class.clone()),
+            access,
+        }
+    }
+
+    /// Returns the `Content-Type` of the object if it was set.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Returns the `Content-Encoding` of the object if it was set.
+    pub fn content_encoding(&self) -> Option<&str> {
+        self.content_encoding.as_deref()
+    }
+
+    /// Returns the `Cache-Control` of the object if it was set.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Returns the user-defined metadata of the object.
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the tags of the object.
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    /// Returns the storage class of the object if it was set.
+    pub fn storage_class(&self) -> Option<&StorageClass> {
+        self.storage_class.as_ref()
+    }
+
+    /// Returns the expected owner of the bucket and whether the requester
+    /// pays.
+    pub fn bucket_access(&self) -> &BucketAccess {
+        &self.access
+    }
+
+    pub(crate) fn sdk_metadata(&self) -> Option<HashMap<String, String>> {
+        if self.metadata.is_empty() {
+            return None;
+        }
+        Some(self.metadata.clone().into_iter().collect())
+    }
+
+    /// The tags as the URL query parameters of the `x-amz-tagging` header.
+    pub(crate) fn tagging(&self) -> Option<String> {
+        if self.tags.is_empty() {
+            return None;
+        }
+        let pairs = self
+            .tags
+            .iter()
+            .map(|(k, v)| format!("{}={}", url_encode(k), url_encode(v)));
+        Some(pairs.collect::<Vec<_>>().join("&"))
+    }
+}
+
+/// The expected owner of the bucket of an upload and whether the requester
+/// pays for its requests.
+///
+/// Unlike the other [`ObjectAttributes`], these have to be sent with every
+/// request of the upload and not only the one to create it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketAccess {
+    expected_owner: Option<String>,
+    requester_pays: bool,
+}
+
+impl BucketAccess {
+    /// Returns the account ID that the bucket is expected to be owned by.
+    pub fn expected_owner(&self) -> Option<&str> {
+        self.expected_owner.as_deref()
+    }
+
+    /// Returns whether the requester pays for the requests.
+    pub fn requester_pays(&self) -> bool {
+        self.requester_pays
+    }
+
+    /// The value of the `x-amz-expected-bucket-owner` header.
+    pub(crate) fn sdk_expected_owner(&self) -> Option<String> {
+        self.expected_owner.clone()
+    }
+
+    /// The value of the `x-amz-request-payer` header.
+    pub(crate) fn sdk_request_payer(&self) -> Option<RequestPayer> {
+        self.requester_pays.then_some(RequestPayer::Requester)
+    }
+}
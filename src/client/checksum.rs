@@ -0,0 +1,132 @@
+use crate::complete_upload::CompleteMultipartUploadOutput as CompleteResponse;
+
+use aws_sdk::types::{ChecksumAlgorithm as SdkChecksumAlgorithm, ChecksumType, CopyPartResult};
+use aws_smithy_checksums::ChecksumAlgorithm as SmithyChecksumAlgorithm;
+use std::fmt::{self, Display, Formatter};
+
+/// Algorithm for the checksums that S3 uses to verify the integrity of an
+/// upload.
+///
+/// When an upload is configured with one, the checksum of every part is sent
+/// in the part upload request and again in the request to complete the upload,
+/// where S3 uses them to verify the object.
+///
+/// The object checksum for `Crc32c` and `Sha256` is a composite checksum, i.e.,
+/// the checksum of the concatenated part checksums, followed by the number of
+/// parts.  `Crc64Nvme` only supports a checksum of the full object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChecksumAlgorithm {
+    /// The CRC32C checksum.
+    Crc32c,
+    /// The CRC64NVME checksum.
+    Crc64Nvme,
+    /// The SHA-256 checksum.
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// Compute the checksum of `bytes` with this algorithm.
+    pub fn checksum(self, bytes: &[u8]) -> Checksum {
+        let mut hasher = self.to_smithy().into_impl();
+        hasher.update(bytes);
+        let digest = hasher.finalize();
+        Checksum::new(self, aws_smithy_types::base64::encode(digest))
+    }
+
+    /// The kind of object checksum that uploads using this algorithm have.
+    pub(crate) fn checksum_type(self) -> ChecksumType {
+        match self {
+            Self::Crc32c | Self::Sha256 => ChecksumType::Composite,
+            Self::Crc64Nvme => ChecksumType::FullObject,
+        }
+    }
+
+    fn to_smithy(self) -> SmithyChecksumAlgorithm {
+        match self {
+            Self::Crc32c => SmithyChecksumAlgorithm::Crc32c,
+            Self::Crc64Nvme => SmithyChecksumAlgorithm::Crc64Nvme,
+            Self::Sha256 => SmithyChecksumAlgorithm::Sha256,
+        }
+    }
+}
+
+impl From<ChecksumAlgorithm> for SdkChecksumAlgorithm {
+    fn from(value: ChecksumAlgorithm) -> Self {
+        match value {
+            ChecksumAlgorithm::Crc32c => Self::Crc32C,
+            ChecksumAlgorithm::Crc64Nvme => Self::Crc64Nvme,
+            ChecksumAlgorithm::Sha256 => Self::Sha256,
+        }
+    }
+}
+
+impl Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_smithy().as_str().fmt(f)
+    }
+}
+
+/// The checksum of a part or of an uploaded object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Checksum {
+    /// The algorithm that computed the checksum.
+    pub algorithm: ChecksumAlgorithm,
+    /// The base64-encoded value of the checksum.
+    ///
+    /// For a composite object checksum this has the suffix `-N`, where `N` is
+    /// the number of parts.
+    pub value: String,
+}
+
+impl Checksum {
+    /// Create a new `Checksum` from algorithm and base64-encoded value.
+    pub fn new<T: Into<String>>(algorithm: ChecksumAlgorithm, value: T) -> Self {
+        Self {
+            algorithm,
+            value: value.into(),
+        }
+    }
+
+    /// Returns the checksum of the first algorithm with a value.
+    pub(crate) fn from_values(
+        crc32c: Option<&str>,
+        crc64: Option<&str>,
+        sha256: Option<&str>,
+    ) -> Option<Self> {
+        let (algorithm, value) = match (crc32c, crc64, sha256) {
+            (Some(v), _, _) => (ChecksumAlgorithm::Crc32c, v),
+            (_, Some(v), _) => (ChecksumAlgorithm::Crc64Nvme, v),
+            (_, _, Some(v)) => (ChecksumAlgorithm::Sha256, v),
+            _ => return None,
+        };
+        Some(Self::new(algorithm, value))
+    }
+
+    pub(crate) fn from_complete_resp(value: &CompleteResponse) -> Option<Self> {
+        Self::from_values(
+            value.checksum_crc32_c(),
+            value.checksum_crc64_nvme(),
+            value.checksum_sha256(),
+        )
+    }
+
+    pub(crate) fn from_copy_result(value: &CopyPartResult) -> Option<Self> {
+        Self::from_values(
+            value.checksum_crc32_c(),
+            value.checksum_crc64_nvme(),
+            value.checksum_sha256(),
+        )
+    }
+
+    /// Returns the value if this was computed with `algorithm`.
+    pub(crate) fn value_for(&self, algorithm: ChecksumAlgorithm) -> Option<String> {
+        (self.algorithm == algorithm).then(|| self.value.clone())
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.value)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::client::SendRequest;
+use crate::client::request::{AbortRequest, IncompleteUpload, ListUploadsRequest};
+use crate::error::{Error, Result};
+
+use std::time::Duration;
+
+/// Abort the multipart uploads in a bucket that were created longer ago than
+/// a given age and have not been completed or aborted.
+///
+/// An upload that is never completed or aborted, e.g., because the process
+/// writing it crashed, does not appear in object listings, but the parts that
+/// were uploaded to it are stored until it is aborted.  This lists the uploads
+/// in a bucket, or under a key prefix of a bucket, and sends a request to abort
+/// each one older than the age.
+///
+/// In a dry run the uploads that would be aborted are listed but no request is
+/// sent to abort them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::{AbortIncomplete, SdkClient};
+/// use aws_multipart_upload::request::ListUploadsRequest;
+/// use std::time::Duration;
+///
+/// # async fn example() -> aws_multipart_upload::error::Result<()> {
+/// let client = SdkClient::defaults().await;
+/// let req = ListUploadsRequest::new("my-bucket").with_prefix("my/prefix");
+/// let summary = AbortIncomplete::new(req, Duration::from_secs(24 * 60 * 60))
+///     .dry_run(true)
+///     .send(&client)
+///     .await?;
+///
+/// for upload in &summary.aborted {
+///     println!("would abort {} started {:?} ago", upload.data.uri, upload.age());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AbortIncomplete {
+    req: ListUploadsRequest,
+    older_than: Duration,
+    dry_run: bool,
+}
+
+impl AbortIncomplete {
+    /// Create a new `AbortIncomplete` for the uploads listed by `req` that are
+    /// older than `older_than`.
+    pub fn new(req: ListUploadsRequest, older_than: Duration) -> Self {
+        Self {
+            req,
+            older_than,
+            dry_run: false,
+        }
+    }
+
+    /// Set whether this is a dry run, which only lists the uploads to abort.
+    pub fn dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    /// Send the requests to list and abort the uploads using `client`.
+    ///
+    /// This fails if the uploads cannot be listed.  An upload that fails to be
+    /// aborted does not stop the others from being aborted, and is reported in
+    /// the summary along with the error.
+    pub async fn send<C: SendRequest>(&self, client: &C) -> Result<AbortSummary> {
+        let uploads = SendRequest::send_list_uploads_request(client, self.req.clone()).await?;
+        let mut summary = AbortSummary {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+
+        for upload in uploads {
+            if upload.age() < self.older_than {
+                summary.skipped += 1;
+                continue;
+            }
+            if self.dry_run {
+                summary.aborted.push(upload);
+                continue;
+            }
+
+            let (id, uri) = (upload.data.get_id(), upload.data.get_uri());
+            trace!(%id, ?uri, age = ?upload.age(), "aborting incomplete upload");
+            let req = AbortRequest::new(id, uri);
+            match SendRequest::send_abort_upload_request(client, req).await {
+                Ok(()) => summary.aborted.push(upload),
+                Err(e) => summary.failed.push((upload, e)),
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+/// The uploads that [`AbortIncomplete`] aborted.
+#[derive(Debug, Default)]
+pub struct AbortSummary {
+    /// Whether this was a dry run, in which case nothing was aborted.
+    pub dry_run: bool,
+    /// The uploads that were aborted, or would have been if this is a dry run.
+    pub aborted: Vec<IncompleteUpload>,
+    /// The uploads that could not be aborted and the error from the request.
+    pub failed: Vec<(IncompleteUpload, Error)>,
+    /// The number of uploads that were listed but are not old enough to abort.
+    pub skipped: usize,
+}
+
+impl AbortSummary {
+    /// Returns the number of uploads that were listed.
+    pub fn listed(&self) -> usize {
+        self.aborted.len() + self.failed.len() + self.skipped
+    }
+
+    /// Returns whether every upload old enough to abort was aborted.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
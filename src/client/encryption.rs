@@ -0,0 +1,166 @@
+use crate::error::{ErrorRepr, Result};
+
+use aws_sdk::types::ServerSideEncryption;
+use aws_smithy_types::base64;
+use md5::{Digest as _, Md5};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// Server-side encryption of the objects being uploaded.
+///
+/// The encryption is requested when the upload is created.  With a key that
+/// the customer provides (SSE-C), S3 does not store the key, so it is sent with
+/// every part and with the request to complete the upload as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encryption {
+    /// Encrypt with a key managed by S3 (SSE-S3).
+    S3,
+    /// Encrypt with a KMS key (SSE-KMS).
+    Kms {
+        /// The ID of the KMS key, or the AWS managed key if there is none.
+        key_id: Option<String>,
+        /// The encryption context, which is additional authenticated data for
+        /// the key.
+        context: BTreeMap<String, String>,
+    },
+    /// Encrypt with a key provided by the customer (SSE-C).
+    Customer(CustomerKey),
+}
+
+impl Encryption {
+    /// Encrypt with the KMS key having the ID `key_id`.
+    pub fn kms<T: Into<String>>(key_id: T) -> Self {
+        Self::Kms {
+            key_id: Some(key_id.into()),
+            context: BTreeMap::new(),
+        }
+    }
+
+    /// Encrypt with the AWS managed KMS key.
+    pub fn kms_default() -> Self {
+        Self::Kms {
+            key_id: None,
+            context: BTreeMap::new(),
+        }
+    }
+
+    /// Encrypt with the 256-bit key `key`.
+    pub fn customer(key: CustomerKey) -> Self {
+        Self::Customer(key)
+    }
+
+    /// Add the pair `key` and `value` to the encryption context.
+    ///
+    /// This does nothing unless the encryption is SSE-KMS.
+    pub fn with_context<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        if let Self::Kms { context, .. } = &mut self {
+            context.insert(key.into(), value.into());
+        }
+        self
+    }
+
+    /// Returns the key provided by the customer, if the encryption is SSE-C.
+    pub fn customer_key(&self) -> Option<&CustomerKey> {
+        match self {
+            Self::Customer(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// The value of the `x-amz-server-side-encryption` header.
+    pub(crate) fn algorithm(&self) -> Option<ServerSideEncryption> {
+        match self {
+            Self::S3 => Some(ServerSideEncryption::Aes256),
+            Self::Kms { .. } => Some(ServerSideEncryption::AwsKms),
+            Self::Customer(_) => None,
+        }
+    }
+
+    pub(crate) fn kms_key_id(&self) -> Option<String> {
+        match self {
+            Self::Kms { key_id, .. } => key_id.clone(),
+            _ => None,
+        }
+    }
+
+    /// The encryption context as base64-encoded JSON.
+    pub(crate) fn kms_context(&self) -> Option<String> {
+        match self {
+            Self::Kms { context, .. } if !context.is_empty() => {
+                let json = serde_json::to_string(context).ok()?;
+                Some(base64::encode(json))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A 256-bit key for server-side encryption with a key provided by the
+/// customer (SSE-C).
+///
+/// The key is never printed: the `Debug` output only has the MD5 digest of the
+/// key, which is what S3 returns to identify it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CustomerKey {
+    key: Arc<str>,
+    md5: Arc<str>,
+}
+
+impl CustomerKey {
+    /// The algorithm of the key, which is the only one S3 supports.
+    const ALGORITHM: &'static str = "AES256";
+
+    /// Create a new `CustomerKey` from the bytes of the key.
+    pub fn new(key: [u8; 32]) -> Self {
+        let md5 = Md5::digest(key);
+        Self {
+            key: base64::encode(key).into(),
+            md5: base64::encode(md5).into(),
+        }
+    }
+
+    /// Create a new `CustomerKey` from the base64-encoded key.
+    ///
+    /// This fails if the value is not base64 or the key is not 256 bits.
+    pub fn from_base64(value: &str) -> Result<Self> {
+        let bytes = base64::decode(value.trim())
+            .map_err(|_| ErrorRepr::Invalid("customer key is not base64".into()))?;
+        let key = <[u8; 32]>::try_from(bytes).map_err(|bytes| {
+            let msg = format!("customer key is {} bits, not 256", bytes.len() * 8);
+            ErrorRepr::Invalid(msg)
+        })?;
+        Ok(Self::new(key))
+    }
+
+    /// Returns the base64-encoded MD5 digest of the key.
+    pub fn key_md5(&self) -> &str {
+        &self.md5
+    }
+
+    /// The values of the algorithm, key, and key MD5 headers for `key`.
+    pub(crate) fn headers(key: Option<&Self>) -> (Option<String>, Option<String>, Option<String>) {
+        match key {
+            Some(key) => (
+                Some(Self::ALGORITHM.to_string()),
+                Some(key.key.to_string()),
+                Some(key.md5.to_string()),
+            ),
+            None => (None, None, None),
+        }
+    }
+}
+
+impl Debug for CustomerKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomerKey")
+            .field("key", &"<redacted>")
+            .field("md5", &self.md5)
+            .finish()
+    }
+}
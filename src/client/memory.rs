@@ -0,0 +1,407 @@
+use crate::client::part::{CompletedPart, CompletedParts, EntityTag, PartNumber};
+use crate::client::request::*;
+use crate::client::{
+    Checksum, ChecksumAlgorithm, CustomerKey, Encryption, SendRequest, UploadData, UploadId,
+};
+use crate::error::{Result, UploadContext as _};
+use crate::uri::ObjectUri;
+use crate::{AWS_MAX_PART_COUNT, AWS_MAX_PART_SIZE, AWS_MIN_PART_SIZE};
+
+use aws_sdk::types::ChecksumType;
+use aws_smithy_types::base64;
+use bytes::{Bytes, BytesMut};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+/// A `SendRequest` client that keeps uploads and objects in memory.
+///
+/// This is meant for testing code that uses multipart uploads without needing
+/// S3 or a mock of it.  The client follows the rules that S3 does: every part
+/// but the last must be at least 5 MiB, the parts in a request to complete an
+/// upload must be in ascending order and must have been uploaded, and an upload
+/// no longer exists after it is completed or aborted.  Part checksums are
+/// verified, and the object checksum is computed in the same way as S3 does
+/// when the upload has a checksum algorithm.  A part can be copied from an
+/// object that was uploaded with the same client.  An upload encrypted with a
+/// key provided by the customer needs the key in every part and in the request
+/// to complete it, but nothing is encrypted.  A request that breaks
+/// one of the rules fails with an [`InMemoryError`] named after the S3 error
+/// code.
+///
+/// Clones of the client share the same storage, so one clone can be given to
+/// an `UploadBuilder` while another is used to inspect the objects uploaded.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_multipart_upload::{InMemoryClient, ObjectUri, UploadBuilder};
+/// use aws_multipart_upload::codec::JsonLinesEncoder;
+/// use aws_multipart_upload::write::UploadStreamExt as _;
+/// use futures::stream::{self, StreamExt as _};
+/// use serde_json::{Value, json};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> aws_multipart_upload::error::Result<()> {
+/// let client = InMemoryClient::new();
+/// let uri = ObjectUri::from(("my-bucket", "my/key.jsonl"));
+/// let upload = UploadBuilder::new(client.clone())
+///     .with_encoder(JsonLinesEncoder::new())
+///     .with_uri(uri.clone())
+///     .build();
+///
+/// let items: Vec<Value> = (0..3).map(|n| json!({"n": n})).collect();
+/// stream::iter(items).collect_upload(upload).await?;
+///
+/// let object = client.object(&uri).unwrap();
+/// assert_eq!(&object[..], b"{\"n\":0}\n{\"n\":1}\n{\"n\":2}\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryClient {
+    store: Arc<Mutex<Store>>,
+}
+
+impl InMemoryClient {
+    /// Create a new, empty `InMemoryClient`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bytes of the object at `uri` if an upload to it completed.
+    pub fn object(&self, uri: &ObjectUri) -> Option<Bytes> {
+        self.lock().objects.get(uri).cloned()
+    }
+
+    /// Returns the URIs of all objects that have been uploaded.
+    pub fn object_uris(&self) -> Vec<ObjectUri> {
+        self.lock().objects.keys().cloned().collect()
+    }
+
+    /// Returns the uploads that have been created but not yet completed or
+    /// aborted.
+    pub fn in_progress(&self) -> Vec<UploadData> {
+        self.lock()
+            .uploads
+            .iter()
+            .map(|(id, upload)| UploadData::new(id.clone(), upload.uri.clone()))
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SendRequest for InMemoryClient {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        req.validate()?;
+        let mut store = self.lock();
+        store.next_id += 1;
+        let id = UploadId::from(format!("in-memory-upload-{}", store.next_id));
+        let upload = InProgress {
+            uri: req.uri.clone(),
+            initiated: SystemTime::now(),
+            checksum: req.checksum_algorithm(),
+            key_md5: key_md5(req.encryption().and_then(Encryption::customer_key)),
+            parts: BTreeMap::new(),
+        };
+        store.uploads.insert(id.clone(), upload);
+        Ok(UploadData::new(id, req.uri))
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        req.validate()?;
+        let (id, uri, part) = (req.id(), req.uri(), req.part_number());
+        let body = req.body().to_bytes().await?;
+        let mut store = self.lock();
+        let upload = store.upload(id, uri).upload_ctx(id, uri, part)?;
+        if !(1..=AWS_MAX_PART_COUNT as i32).contains(&*part) {
+            return Err(InMemoryError::InvalidArgument).upload_ctx(id, uri, part);
+        }
+        upload
+            .check_key(req.customer_key())
+            .upload_ctx(id, uri, part)?;
+
+        let checksum = match (req.checksum(), upload.checksum) {
+            (Some(sent), _) if *sent != sent.algorithm.checksum(&body) => {
+                return Err(InMemoryError::BadDigest).upload_ctx(id, uri, part);
+            }
+            (Some(sent), _) => Some(sent.clone()),
+            (None, algorithm) => algorithm.map(|a| a.checksum(&body)),
+        };
+
+        let stored = StoredPart {
+            etag: etag(&body),
+            body,
+            checksum,
+        };
+        let completed = stored.to_completed_part(id, part);
+        upload.parts.insert(part, stored);
+        Ok(completed)
+    }
+
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        req.validate()?;
+        let (id, uri, part) = (req.id(), req.uri(), req.part_number());
+        let source = req.source();
+        let mut store = self.lock();
+        let object = store
+            .objects
+            .get(source.uri())
+            .cloned()
+            .ok_or(InMemoryError::NoSuchKey)
+            .upload_ctx(id, uri, part)?;
+        let body = match source.range() {
+            Some(range) if range.end > object.len() as u64 => {
+                return Err(InMemoryError::InvalidRange).upload_ctx(id, uri, part);
+            }
+            Some(range) => object.slice(range.start as usize..range.end as usize),
+            None => object,
+        };
+        if body.len() as u64 > AWS_MAX_PART_SIZE.as_u64() {
+            return Err(InMemoryError::EntityTooLarge).upload_ctx(id, uri, part);
+        }
+        let upload = store.upload(id, uri).upload_ctx(id, uri, part)?;
+        if !(1..=AWS_MAX_PART_COUNT as i32).contains(&*part) {
+            return Err(InMemoryError::InvalidArgument).upload_ctx(id, uri, part);
+        }
+        upload
+            .check_key(req.customer_key())
+            .upload_ctx(id, uri, part)?;
+
+        let stored = StoredPart {
+            etag: etag(&body),
+            checksum: upload.checksum.map(|a| a.checksum(&body)),
+            body,
+        };
+        let completed = stored.to_completed_part(id, part);
+        upload.parts.insert(part, stored);
+        Ok(completed)
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        req.validate()?;
+        let (id, uri) = (req.id(), req.uri());
+        let completed = req.completed_parts();
+        let last = completed.max_part_number();
+        let mut store = self.lock();
+        let upload = store.upload(id, uri).upload_ctx(id, uri, last)?;
+        upload
+            .check_key(req.customer_key())
+            .upload_ctx(id, uri, last)?;
+        let body = upload.assemble(completed).upload_ctx(id, uri, last)?;
+        let checksum = upload.object_checksum(completed, &body);
+
+        store.uploads.remove(id);
+        let etag = EntityTag::from(format!("{}-{}", etag(&body), completed.count()));
+        store.objects.insert(uri.clone(), body);
+        Ok(CompletedUpload::new(uri.clone(), etag).with_checksum(checksum))
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        let mut store = self.lock();
+        store
+            .upload(&req.id, &req.uri)
+            .upload_ctx(&req.id, &req.uri, PartNumber::default())?;
+        store.uploads.remove(&req.id);
+        Ok(())
+    }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        req.validate()?;
+        let (id, uri) = (req.id(), req.uri());
+        let mut store = self.lock();
+        let upload = store
+            .upload(id, uri)
+            .upload_ctx(id, uri, PartNumber::default())?;
+
+        let mut parts = CompletedParts::default();
+        for (part, stored) in &upload.parts {
+            parts.push(stored.to_completed_part(id, *part));
+        }
+        Ok(parts)
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        req.validate()?;
+        let store = self.lock();
+        let mut uploads = store
+            .uploads
+            .iter()
+            .filter(|(_, upload)| upload.uri.bucket == req.bucket)
+            .filter(|(_, upload)| {
+                let prefix = req.prefix();
+                prefix.is_none_or(|pfx| upload.uri.key.starts_with(&**pfx))
+            })
+            .map(|(id, upload)| {
+                let data = UploadData::new(id.clone(), upload.uri.clone());
+                IncompleteUpload::new(data, upload.initiated)
+            })
+            .collect::<Vec<_>>();
+        // S3 lists uploads by key, then by the time they were created.
+        uploads.sort_by(|a, b| {
+            let (key_a, key_b) = (&*a.data.uri.key, &*b.data.uri.key);
+            key_a.cmp(key_b).then(a.initiated.cmp(&b.initiated))
+        });
+        Ok(uploads)
+    }
+}
+
+/// Error for a request to the `InMemoryClient` that S3 would reject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum InMemoryError {
+    /// The upload does not exist, or it was already completed or aborted.
+    #[error("NoSuchUpload: the specified multipart upload does not exist")]
+    NoSuchUpload,
+    /// A part that is not the last part is smaller than 5 MiB.
+    #[error("EntityTooSmall: proposed upload is smaller than the minimum allowed size")]
+    EntityTooSmall,
+    /// A part copied from an object is larger than 5 GiB.
+    #[error("EntityTooLarge: proposed upload exceeds the maximum allowed size")]
+    EntityTooLarge,
+    /// The object to copy a part from does not exist.
+    #[error("NoSuchKey: the specified key does not exist")]
+    NoSuchKey,
+    /// The byte range to copy a part from is not within the object.
+    #[error("InvalidRange: the requested range is not satisfiable")]
+    InvalidRange,
+    /// A part in the request to complete the upload was not uploaded or has a
+    /// different entity tag.
+    #[error("InvalidPart: one or more of the specified parts could not be found")]
+    InvalidPart,
+    /// The parts in the request to complete the upload are not in ascending
+    /// order by part number.
+    #[error("InvalidPartOrder: the list of parts was not in ascending order")]
+    InvalidPartOrder,
+    /// The checksum sent with a part does not match the part body.
+    #[error("BadDigest: the checksum specified did not match what was received")]
+    BadDigest,
+    /// The part number is not between 1 and 10,000, or there are no parts in
+    /// the request to complete the upload.
+    #[error("InvalidArgument: part number must be between 1 and 10000")]
+    InvalidArgument,
+    /// The upload is encrypted with a key provided by the customer, and a part
+    /// or the request to complete the upload does not have the same key.
+    #[error("InvalidRequest: the request must have the key the upload is encrypted with")]
+    InvalidRequest,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    next_id: u64,
+    uploads: HashMap<UploadId, InProgress>,
+    objects: HashMap<ObjectUri, Bytes>,
+}
+
+impl Store {
+    fn upload(&mut self, id: &UploadId, uri: &ObjectUri) -> Result<&mut InProgress, InMemoryError> {
+        self.uploads
+            .get_mut(id)
+            .filter(|upload| &upload.uri == uri)
+            .ok_or(InMemoryError::NoSuchUpload)
+    }
+}
+
+#[derive(Debug)]
+struct InProgress {
+    uri: ObjectUri,
+    initiated: SystemTime,
+    checksum: Option<ChecksumAlgorithm>,
+    key_md5: Option<String>,
+    parts: BTreeMap<PartNumber, StoredPart>,
+}
+
+impl InProgress {
+    /// Check that a request has the key that the upload is encrypted with.
+    fn check_key(&self, key: Option<&CustomerKey>) -> Result<(), InMemoryError> {
+        if self.key_md5 != key_md5(key) {
+            return Err(InMemoryError::InvalidRequest);
+        }
+        Ok(())
+    }
+
+    /// Check the parts against the rules and concatenate them.
+    fn assemble(&self, completed: &CompletedParts) -> Result<Bytes, InMemoryError> {
+        if completed.is_empty() {
+            return Err(InMemoryError::InvalidArgument);
+        }
+        let ascending = completed
+            .windows(2)
+            .all(|w| w[0].part_number < w[1].part_number);
+        if !ascending {
+            return Err(InMemoryError::InvalidPartOrder);
+        }
+
+        let mut body = BytesMut::new();
+        for (n, part) in completed.iter().enumerate() {
+            let stored = self
+                .parts
+                .get(&part.part_number)
+                .filter(|stored| *stored.etag == *part.etag)
+                .filter(|stored| part.checksum.is_none() || part.checksum == stored.checksum)
+                .ok_or(InMemoryError::InvalidPart)?;
+            let is_last = n + 1 == completed.count();
+            if !is_last && (stored.body.len() as u64) < AWS_MIN_PART_SIZE.as_u64() {
+                return Err(InMemoryError::EntityTooSmall);
+            }
+            body.extend_from_slice(&stored.body);
+        }
+
+        Ok(body.freeze())
+    }
+
+    /// The checksum of the object made from the `completed` parts.
+    ///
+    /// A composite checksum is the checksum of the concatenated part checksums
+    /// and the number of parts.
+    fn object_checksum(&self, completed: &CompletedParts, body: &[u8]) -> Option<Checksum> {
+        let algorithm = self.checksum?;
+        if algorithm.checksum_type() == ChecksumType::FullObject {
+            return Some(algorithm.checksum(body));
+        }
+
+        let mut digests = Vec::new();
+        for part in completed.iter() {
+            let checksum = self.parts.get(&part.part_number)?.checksum.as_ref()?;
+            digests.extend(base64::decode(&checksum.value).ok()?);
+        }
+        let composite = algorithm.checksum(&digests);
+        let value = format!("{}-{}", composite.value, completed.count());
+        Some(Checksum::new(algorithm, value))
+    }
+}
+
+#[derive(Debug)]
+struct StoredPart {
+    etag: EntityTag,
+    body: Bytes,
+    checksum: Option<Checksum>,
+}
+
+impl StoredPart {
+    fn to_completed_part(&self, id: &UploadId, part: PartNumber) -> CompletedPart {
+        CompletedPart::new(id.clone(), self.etag.clone(), part, self.body.len())
+            .with_checksum(self.checksum.clone())
+    }
+}
+
+fn etag(bytes: &[u8]) -> EntityTag {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    EntityTag::from(format!("{:016x}", hasher.finish()))
+}
+
+fn key_md5(key: Option<&CustomerKey>) -> Option<String> {
+    key.map(|key| key.key_md5().to_string())
+}
@@ -1,28 +1,47 @@
-use self::part::CompletedPart;
+use self::part::{CompletedPart, CompletedParts};
 use self::request::*;
 use crate::create_upload::CreateMultipartUploadOutput as CreateResponse;
-use crate::error::{ErrorRepr, Result};
+use crate::error::{Error, ErrorRepr, Result};
 use crate::uri::ObjectUri;
 
-use futures::future::LocalBoxFuture;
+use futures::future::{BoxFuture, LocalBoxFuture};
 use std::borrow::Cow;
 use std::fmt::{self, Formatter};
 use std::ops::Deref;
 use std::sync::Arc;
 
+#[cfg(feature = "testing")]
+mod memory;
+#[cfg(feature = "testing")]
+pub use memory::{InMemoryClient, InMemoryError};
+mod attributes;
+pub use attributes::{BucketAccess, ObjectAttributes};
+mod checksum;
+pub use checksum::{Checksum, ChecksumAlgorithm};
+mod cleanup;
+pub use cleanup::{AbortIncomplete, AbortSummary};
+mod encryption;
+pub use encryption::{CustomerKey, Encryption};
 pub mod part;
 pub mod request;
+mod retry;
+pub use retry::RetryPolicy;
 mod sdk;
 pub use sdk::SdkClient;
 
 /// `SendRequest` represents the atomic operations in a multipart upload.
-pub trait SendRequest {
+///
+/// The futures returned by this trait are `Send`, as is the client itself, so
+/// that a multipart upload built from it can be moved to another thread, e.g.,
+/// in `tokio::spawn` on a multi-threaded runtime.  See [`LocalSendRequest`] for
+/// a version without this requirement.
+pub trait SendRequest: Send + Sync {
     /// Send a request to create a new multipart upload, returning an
     /// [`UploadData`] having the upload ID assignment.
     fn send_create_upload_request(
         &self,
         req: CreateRequest,
-    ) -> impl Future<Output = Result<UploadData>>;
+    ) -> impl Future<Output = Result<UploadData>> + Send;
 
     /// Send a request to upload a part to a multipart upload, returning the
     /// [`CompletedPart`] containing entity tag and part number, which are required
@@ -30,7 +49,20 @@ pub trait SendRequest {
     fn send_new_part_upload_request(
         &self,
         req: UploadPartRequest,
-    ) -> impl Future<Output = Result<CompletedPart>>;
+    ) -> impl Future<Output = Result<CompletedPart>> + Send;
+
+    /// Send a request to upload a part to a multipart upload that is copied
+    /// from an existing object, returning the [`CompletedPart`] like a part
+    /// upload request does.
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> impl Future<Output = Result<CompletedPart>> + Send {
+        let _ = req;
+        async { Err(unsupported("UploadPartCopy")) }
+    }
 
     /// Send a request to complete a multipart upload, returning a
     /// [`CompletedUpload`], which has the unique entity tag of the object as well
@@ -38,17 +70,45 @@ pub trait SendRequest {
     fn send_complete_upload_request(
         &self,
         req: CompleteRequest,
-    ) -> impl Future<Output = Result<CompletedUpload>>;
+    ) -> impl Future<Output = Result<CompletedUpload>> + Send;
 
     /// Send a request to abort a multipart upload returning an empty response if
     /// successful.
-    fn send_abort_upload_request(&self, req: AbortRequest) -> impl Future<Output = Result<()>>;
+    fn send_abort_upload_request(
+        &self,
+        req: AbortRequest,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Send a request to list the parts uploaded to a multipart upload so far,
+    /// returning them as [`CompletedParts`], which is needed to resume it.
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_list_parts_request(
+        &self,
+        req: ListPartsRequest,
+    ) -> impl Future<Output = Result<CompletedParts>> + Send {
+        let _ = req;
+        async { Err(unsupported("ListParts")) }
+    }
+
+    /// Send a request to list the multipart uploads in a bucket that have not
+    /// been completed or aborted, returning them with the time they were
+    /// created.
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> impl Future<Output = Result<Vec<IncompleteUpload>>> + Send {
+        let _ = req;
+        async { Err(unsupported("ListMultipartUploads")) }
+    }
 }
 
 impl<D, T> SendRequest for T
 where
     D: SendRequest,
-    T: Deref<Target = D>,
+    T: Deref<Target = D> + Send + Sync,
 {
     async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
         self.deref().send_create_upload_request(req).await
@@ -58,6 +118,13 @@ where
         self.deref().send_new_part_upload_request(req).await
     }
 
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        self.deref().send_upload_part_copy_request(req).await
+    }
+
     async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
         self.deref().send_complete_upload_request(req).await
     }
@@ -65,15 +132,175 @@ where
     async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
         self.deref().send_abort_upload_request(req).await
     }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        self.deref().send_list_parts_request(req).await
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        self.deref().send_list_uploads_request(req).await
+    }
+}
+
+/// `SendRequest` without the `Send` requirement.
+///
+/// This is for clients whose request futures are not `Send`, for example when
+/// the upload is driven by a single-threaded runtime.  Every `SendRequest` is
+/// also a `LocalSendRequest`.
+pub trait LocalSendRequest {
+    /// Send a request to create a new multipart upload, returning an
+    /// [`UploadData`] having the upload ID assignment.
+    fn send_create_upload_request(
+        &self,
+        req: CreateRequest,
+    ) -> impl Future<Output = Result<UploadData>>;
+
+    /// Send a request to upload a part to a multipart upload, returning the
+    /// [`CompletedPart`] containing entity tag and part number.
+    fn send_new_part_upload_request(
+        &self,
+        req: UploadPartRequest,
+    ) -> impl Future<Output = Result<CompletedPart>>;
+
+    /// Send a request to upload a part to a multipart upload that is copied
+    /// from an existing object, returning the [`CompletedPart`].
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> impl Future<Output = Result<CompletedPart>> {
+        let _ = req;
+        async { Err(unsupported("UploadPartCopy")) }
+    }
+
+    /// Send a request to complete a multipart upload, returning a
+    /// [`CompletedUpload`].
+    fn send_complete_upload_request(
+        &self,
+        req: CompleteRequest,
+    ) -> impl Future<Output = Result<CompletedUpload>>;
+
+    /// Send a request to abort a multipart upload returning an empty response if
+    /// successful.
+    fn send_abort_upload_request(&self, req: AbortRequest) -> impl Future<Output = Result<()>>;
+
+    /// Send a request to list the parts uploaded to a multipart upload so far.
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_list_parts_request(
+        &self,
+        req: ListPartsRequest,
+    ) -> impl Future<Output = Result<CompletedParts>> {
+        let _ = req;
+        async { Err(unsupported("ListParts")) }
+    }
+
+    /// Send a request to list the multipart uploads in a bucket that have not
+    /// been completed or aborted.
+    ///
+    /// By default, this returns an error saying the operation is unsupported.
+    fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> impl Future<Output = Result<Vec<IncompleteUpload>>> {
+        let _ = req;
+        async { Err(unsupported("ListMultipartUploads")) }
+    }
+}
+
+impl<T: SendRequest> LocalSendRequest for T {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        SendRequest::send_create_upload_request(self, req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        SendRequest::send_new_part_upload_request(self, req).await
+    }
+
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        SendRequest::send_upload_part_copy_request(self, req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        SendRequest::send_complete_upload_request(self, req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        SendRequest::send_abort_upload_request(self, req).await
+    }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        SendRequest::send_list_parts_request(self, req).await
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        SendRequest::send_list_uploads_request(self, req).await
+    }
+}
+
+/// A type-erased client of the multipart upload API.
+///
+/// This constructs the request futures for each operation of the upload and
+/// is implemented by [`UploadClient`], where the futures are `Send`, and by
+/// [`LocalUploadClient`], where they are not.  The upload writers are generic
+/// over this trait.
+pub trait ErasedClient: Clone + fmt::Debug {
+    /// Future for a request to create an upload.
+    type CreateUpload: Future<Output = Result<UploadData>> + fmt::Debug;
+    /// Future for a request to upload a part.
+    type UploadPart: Future<Output = Result<CompletedPart>> + fmt::Debug;
+    /// Future for a request to complete an upload.
+    type CompleteUpload: Future<Output = Result<CompletedUpload>> + fmt::Debug;
+    /// Future for a request to abort an upload.
+    type AbortUpload: Future<Output = Result<()>> + fmt::Debug;
+    /// Future for a request to list the parts of an upload.
+    type ListParts: Future<Output = Result<CompletedParts>> + fmt::Debug;
+
+    /// Returns the future for sending the `CreateRequest`.
+    fn create_upload(&self, req: CreateRequest) -> Self::CreateUpload;
+
+    /// Returns the future for sending the `UploadPartRequest`.
+    fn upload_part(&self, req: UploadPartRequest) -> Self::UploadPart;
+
+    /// Returns the future for sending the `UploadPartCopyRequest`, which is
+    /// the same as for a part upload so that they can be polled together.
+    fn upload_part_copy(&self, req: UploadPartCopyRequest) -> Self::UploadPart;
+
+    /// Returns the future for sending the `CompleteRequest`.
+    fn complete_upload(&self, req: CompleteRequest) -> Self::CompleteUpload;
+
+    /// Returns the future for sending the `AbortRequest`.
+    fn abort_upload(&self, req: AbortRequest) -> Self::AbortUpload;
+
+    /// Returns the future for sending the `ListPartsRequest`.
+    fn list_parts(&self, req: ListPartsRequest) -> Self::ListParts;
+
+    /// Set the policy for retrying the requests to create an upload, upload a
+    /// part, and complete an upload.
+    fn with_retry_policy(self, policy: RetryPolicy) -> Self;
 }
 
 /// A client of the multipart upload API.
 ///
 /// This can be built from any type that implements `SendRequest`, such as the
-/// [`SdkClient`].
+/// [`SdkClient`].  The client is `Send + Sync` and the request futures it makes
+/// are `Send`.
+///
+/// Failed requests are not retried unless a [`RetryPolicy`] is set.
 #[derive(Clone)]
 pub struct UploadClient {
     pub(crate) inner: Arc<dyn BoxedSendRequest>,
+    pub(crate) retry: RetryPolicy,
 }
 
 impl UploadClient {
@@ -85,6 +312,7 @@ impl UploadClient {
         let inner = SendRequestInner::new(client);
         Self {
             inner: Arc::new(inner),
+            retry: RetryPolicy::no_retry(),
         }
     }
 }
@@ -98,6 +326,13 @@ impl SendRequest for UploadClient {
         self.inner.send_upload_part(req).await
     }
 
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        self.inner.send_upload_part_copy(req).await
+    }
+
     async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
         self.inner.send_complete_upload(req).await
     }
@@ -105,12 +340,170 @@ impl SendRequest for UploadClient {
     async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
         self.inner.send_abort_upload(req).await
     }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        self.inner.send_list_parts(req).await
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        self.inner.send_list_uploads(req).await
+    }
+}
+
+impl ErasedClient for UploadClient {
+    type CreateUpload = SendCreateUpload;
+    type UploadPart = SendUploadPart;
+    type CompleteUpload = SendCompleteUpload;
+    type AbortUpload = SendAbortUpload;
+    type ListParts = SendListParts;
+
+    fn create_upload(&self, req: CreateRequest) -> Self::CreateUpload {
+        SendCreateUpload::new(self, req)
+    }
+
+    fn upload_part(&self, req: UploadPartRequest) -> Self::UploadPart {
+        SendUploadPart::new(self, req)
+    }
+
+    fn upload_part_copy(&self, req: UploadPartCopyRequest) -> Self::UploadPart {
+        SendUploadPart::copy(self, req)
+    }
+
+    fn complete_upload(&self, req: CompleteRequest) -> Self::CompleteUpload {
+        SendCompleteUpload::new(self, req)
+    }
+
+    fn abort_upload(&self, req: AbortRequest) -> Self::AbortUpload {
+        SendAbortUpload::new(self, req)
+    }
+
+    fn list_parts(&self, req: ListPartsRequest) -> Self::ListParts {
+        SendListParts::new(self, req)
+    }
+
+    fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: policy,
+            ..self
+        }
+    }
 }
 
 impl fmt::Debug for UploadClient {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("UploadClient")
             .field("inner", &"SendRequest")
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+/// `UploadClient` without the `Send` requirement.
+///
+/// This can be built from any type that implements `LocalSendRequest`.
+#[derive(Clone)]
+pub struct LocalUploadClient {
+    pub(crate) inner: Arc<dyn LocalBoxedSendRequest>,
+    pub(crate) retry: RetryPolicy,
+}
+
+impl LocalUploadClient {
+    /// Create a new `LocalUploadClient`.
+    pub fn new<C>(client: C) -> Self
+    where
+        C: LocalSendRequest + 'static,
+    {
+        let inner = SendRequestInner::new(client);
+        Self {
+            inner: Arc::new(inner),
+            retry: RetryPolicy::no_retry(),
+        }
+    }
+}
+
+impl LocalSendRequest for LocalUploadClient {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        self.inner.send_upload_part(req).await
+    }
+
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        self.inner.send_upload_part_copy(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload(req).await
+    }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        self.inner.send_list_parts(req).await
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        self.inner.send_list_uploads(req).await
+    }
+}
+
+impl ErasedClient for LocalUploadClient {
+    type CreateUpload = LocalSendCreateUpload;
+    type UploadPart = LocalSendUploadPart;
+    type CompleteUpload = LocalSendCompleteUpload;
+    type AbortUpload = LocalSendAbortUpload;
+    type ListParts = LocalSendListParts;
+
+    fn create_upload(&self, req: CreateRequest) -> Self::CreateUpload {
+        LocalSendCreateUpload::new(self, req)
+    }
+
+    fn upload_part(&self, req: UploadPartRequest) -> Self::UploadPart {
+        LocalSendUploadPart::new(self, req)
+    }
+
+    fn upload_part_copy(&self, req: UploadPartCopyRequest) -> Self::UploadPart {
+        LocalSendUploadPart::copy(self, req)
+    }
+
+    fn complete_upload(&self, req: CompleteRequest) -> Self::CompleteUpload {
+        LocalSendCompleteUpload::new(self, req)
+    }
+
+    fn abort_upload(&self, req: AbortRequest) -> Self::AbortUpload {
+        LocalSendAbortUpload::new(self, req)
+    }
+
+    fn list_parts(&self, req: ListPartsRequest) -> Self::ListParts {
+        LocalSendListParts::new(self, req)
+    }
+
+    fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: policy,
+            ..self
+        }
+    }
+}
+
+impl fmt::Debug for LocalUploadClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalUploadClient")
+            .field("inner", &"LocalSendRequest")
+            .field("retry", &self.retry)
             .finish()
     }
 }
@@ -205,31 +598,103 @@ impl UploadData {
 }
 
 /// Object-safe `SendRequest`.
-pub(crate) trait BoxedSendRequest {
+pub(crate) trait BoxedSendRequest: Send + Sync {
+    fn send_create_upload(&self, req: CreateRequest) -> BoxFuture<'_, Result<UploadData>>;
+
+    fn send_upload_part(&self, req: UploadPartRequest) -> BoxFuture<'_, Result<CompletedPart>>;
+
+    fn send_upload_part_copy(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> BoxFuture<'_, Result<CompletedPart>>;
+
+    fn send_complete_upload(&self, req: CompleteRequest) -> BoxFuture<'_, Result<CompletedUpload>>;
+
+    fn send_abort_upload(&self, req: AbortRequest) -> BoxFuture<'_, Result<()>>;
+
+    fn send_list_parts(&self, req: ListPartsRequest) -> BoxFuture<'_, Result<CompletedParts>>;
+
+    fn send_list_uploads(
+        &self,
+        req: ListUploadsRequest,
+    ) -> BoxFuture<'_, Result<Vec<IncompleteUpload>>>;
+}
+
+/// Object-safe `LocalSendRequest`.
+pub(crate) trait LocalBoxedSendRequest {
     fn send_create_upload(&self, req: CreateRequest) -> LocalBoxFuture<'_, Result<UploadData>>;
 
     fn send_upload_part(&self, req: UploadPartRequest)
     -> LocalBoxFuture<'_, Result<CompletedPart>>;
 
+    fn send_upload_part_copy(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> LocalBoxFuture<'_, Result<CompletedPart>>;
+
     fn send_complete_upload(
         &self,
         req: CompleteRequest,
     ) -> LocalBoxFuture<'_, Result<CompletedUpload>>;
 
     fn send_abort_upload(&self, req: AbortRequest) -> LocalBoxFuture<'_, Result<()>>;
+
+    fn send_list_parts(&self, req: ListPartsRequest) -> LocalBoxFuture<'_, Result<CompletedParts>>;
+
+    fn send_list_uploads(
+        &self,
+        req: ListUploadsRequest,
+    ) -> LocalBoxFuture<'_, Result<Vec<IncompleteUpload>>>;
 }
 
-/// Implements `BoxedSendRequest` for any `T: SendRequest` so that we can
-/// construct `UploadClient`.
+/// Implements `BoxedSendRequest` for any `T: SendRequest` and
+/// `LocalBoxedSendRequest` for any `T: LocalSendRequest` so that we can
+/// construct `UploadClient` and `LocalUploadClient`.
 struct SendRequestInner<T>(T);
 
-impl<T: SendRequest> SendRequestInner<T> {
+impl<T> SendRequestInner<T> {
     pub(super) fn new(inner: T) -> Self {
         Self(inner)
     }
 }
 
 impl<T: SendRequest> BoxedSendRequest for SendRequestInner<T> {
+    fn send_create_upload(&self, req: CreateRequest) -> BoxFuture<'_, Result<UploadData>> {
+        Box::pin(SendRequest::send_create_upload_request(&self.0, req))
+    }
+
+    fn send_upload_part(&self, req: UploadPartRequest) -> BoxFuture<'_, Result<CompletedPart>> {
+        Box::pin(SendRequest::send_new_part_upload_request(&self.0, req))
+    }
+
+    fn send_upload_part_copy(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> BoxFuture<'_, Result<CompletedPart>> {
+        Box::pin(SendRequest::send_upload_part_copy_request(&self.0, req))
+    }
+
+    fn send_complete_upload(&self, req: CompleteRequest) -> BoxFuture<'_, Result<CompletedUpload>> {
+        Box::pin(SendRequest::send_complete_upload_request(&self.0, req))
+    }
+
+    fn send_abort_upload(&self, req: AbortRequest) -> BoxFuture<'_, Result<()>> {
+        Box::pin(SendRequest::send_abort_upload_request(&self.0, req))
+    }
+
+    fn send_list_parts(&self, req: ListPartsRequest) -> BoxFuture<'_, Result<CompletedParts>> {
+        Box::pin(SendRequest::send_list_parts_request(&self.0, req))
+    }
+
+    fn send_list_uploads(
+        &self,
+        req: ListUploadsRequest,
+    ) -> BoxFuture<'_, Result<Vec<IncompleteUpload>>> {
+        Box::pin(SendRequest::send_list_uploads_request(&self.0, req))
+    }
+}
+
+impl<T: LocalSendRequest> LocalBoxedSendRequest for SendRequestInner<T> {
     fn send_create_upload(&self, req: CreateRequest) -> LocalBoxFuture<'_, Result<UploadData>> {
         Box::pin(self.0.send_create_upload_request(req))
     }
@@ -241,6 +706,13 @@ impl<T: SendRequest> BoxedSendRequest for SendRequestInner<T> {
         Box::pin(self.0.send_new_part_upload_request(req))
     }
 
+    fn send_upload_part_copy(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> LocalBoxFuture<'_, Result<CompletedPart>> {
+        Box::pin(self.0.send_upload_part_copy_request(req))
+    }
+
     fn send_complete_upload(
         &self,
         req: CompleteRequest,
@@ -251,4 +723,20 @@ impl<T: SendRequest> BoxedSendRequest for SendRequestInner<T> {
     fn send_abort_upload(&self, req: AbortRequest) -> LocalBoxFuture<'_, Result<()>> {
         Box::pin(self.0.send_abort_upload_request(req))
     }
+
+    fn send_list_parts(&self, req: ListPartsRequest) -> LocalBoxFuture<'_, Result<CompletedParts>> {
+        Box::pin(self.0.send_list_parts_request(req))
+    }
+
+    fn send_list_uploads(
+        &self,
+        req: ListUploadsRequest,
+    ) -> LocalBoxFuture<'_, Result<Vec<IncompleteUpload>>> {
+        Box::pin(self.0.send_list_uploads_request(req))
+    }
+}
+
+/// The error for an operation that a client does not implement.
+fn unsupported(op: &'static str) -> Error {
+    ErrorRepr::Unsupported(op).into()
 }
@@ -1,14 +1,19 @@
-use super::UploadId;
+use super::{Checksum, ChecksumAlgorithm, UploadId};
 use crate::complete_upload::CompleteMultipartUploadOutput as CompleteResponse;
 use crate::error::{ErrorRepr, Result};
+use crate::part_copy::UploadPartCopyOutput as CopyResponse;
 use crate::part_upload::UploadPartOutput as UploadResponse;
 
-use aws_sdk_s3::primitives::ByteStream;
-use bytes::{BufMut as _, BytesMut};
+use aws_sdk_s3::primitives::{ByteStream, Length};
+use aws_sdk_s3::types::Part;
+use bytes::{BufMut as _, Bytes, BytesMut};
 use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
-use std::io::{Result as IoResult, Write};
+use std::fs::OpenOptions;
+use std::io::{self, Result as IoResult, Write};
 use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Body of the multipart upload request.
 ///
@@ -49,6 +54,15 @@ impl PartBody {
         let bytes = buf.freeze();
         bytes.into()
     }
+
+    /// Convert this type into immutable [`Bytes`].
+    ///
+    /// This conversion is also zero-cost, and the resulting `Bytes` are cheap
+    /// to clone, which is what allows a part to be sent again if the request
+    /// failed.
+    pub fn freeze(self) -> Bytes {
+        self.0.freeze()
+    }
 }
 
 impl Write for PartBody {
@@ -90,6 +104,123 @@ impl AsRef<[u8]> for PartBody {
     }
 }
 
+/// Body of the request to upload a part.
+///
+/// The body is either the bytes of the part in memory or, if the upload is
+/// configured to stage parts on disk, a [`PartFile`] holding them.
+#[derive(Clone, Debug)]
+pub enum RequestBody {
+    /// The bytes of the part in memory.
+    Bytes(Bytes),
+    /// The part staged in a local file.
+    File(Arc<PartFile>),
+}
+
+impl RequestBody {
+    /// Size in bytes of the body.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.len(),
+            Self::File(file) => file.len(),
+        }
+    }
+
+    /// Returns whether the body is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes of the body, reading them from the file if it was
+    /// staged on disk.
+    pub async fn to_bytes(&self) -> Result<Bytes> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes.clone()),
+            Self::File(file) => {
+                let bytes = tokio::fs::read(file.path())
+                    .await
+                    .map_err(ErrorRepr::Disk)?;
+                Ok(bytes.into())
+            }
+        }
+    }
+
+    /// Convert the body into a [`ByteStream`] for the request to upload the
+    /// part.
+    ///
+    /// A body staged on disk is streamed from the file rather than read into
+    /// memory first.
+    pub async fn to_sdk_body(&self) -> Result<ByteStream> {
+        match self {
+            Self::Bytes(bytes) => Ok(ByteStream::from(bytes.clone())),
+            Self::File(file) => ByteStream::read_from()
+                .path(file.path())
+                .length(Length::Exact(file.len() as u64))
+                .build()
+                .await
+                .map_err(|e| ErrorRepr::Disk(io::Error::other(e)).into()),
+        }
+    }
+}
+
+impl From<Bytes> for RequestBody {
+    fn from(value: Bytes) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+/// A part staged in a file on local disk.
+///
+/// The file is deleted when this is dropped, which is when the request to
+/// upload the part has finished.
+#[derive(Debug)]
+pub struct PartFile {
+    path: PathBuf,
+    len: usize,
+}
+
+impl PartFile {
+    /// Write `bytes` to a new file in the directory `dir`.
+    pub(crate) fn create(dir: &Path, bytes: &[u8]) -> io::Result<Self> {
+        let name = format!("part-{:016x}.tmp", fastrand::u64(..));
+        let path = dir.join(name);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        // From here the file is removed if writing to it fails.
+        let part = Self {
+            path,
+            len: bytes.len(),
+        };
+        file.write_all(bytes)?;
+        Ok(part)
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Size in bytes of the part.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the part is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Drop for PartFile {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            trace!(path = ?self.path, error = %e, "failed to remove part file");
+        }
+    }
+}
+
 /// Number we assign to a part when uploading.
 ///
 /// This, along with the entity tag found in the response, is required in the
@@ -169,6 +300,14 @@ impl EntityTag {
             .ok_or_else(|| ErrorRepr::Missing("UploadResponse", "e_tag"))
     }
 
+    pub(crate) fn try_from_copy_resp(value: &CopyResponse) -> Result<Self, ErrorRepr> {
+        value
+            .copy_part_result()
+            .and_then(|result| result.e_tag())
+            .map(Self::from)
+            .ok_or_else(|| ErrorRepr::Missing("CopyResponse", "e_tag"))
+    }
+
     pub(crate) fn try_from_complete_resp(value: &CompleteResponse) -> Result<Self, ErrorRepr> {
         value
             .e_tag
@@ -226,6 +365,9 @@ pub struct CompletedPart {
     pub part_number: PartNumber,
     /// The size of this part in bytes.
     pub part_size: usize,
+    /// The checksum of this part if the upload is configured with a checksum
+    /// algorithm.
+    pub checksum: Option<Checksum>,
 }
 
 impl CompletedPart {
@@ -236,8 +378,32 @@ impl CompletedPart {
             etag,
             part_number,
             part_size,
+            checksum: None,
         }
     }
+
+    /// Set the checksum of the part.
+    pub fn with_checksum(self, checksum: Option<Checksum>) -> Self {
+        Self { checksum, ..self }
+    }
+
+    pub(crate) fn try_from_part(id: &UploadId, value: &Part) -> Result<Self, ErrorRepr> {
+        let etag = value
+            .e_tag()
+            .map(EntityTag::from)
+            .ok_or_else(|| ErrorRepr::Missing("Part", "e_tag"))?;
+        let part_number = value
+            .part_number()
+            .map(PartNumber::new)
+            .ok_or_else(|| ErrorRepr::Missing("Part", "part_number"))?;
+        let part_size = value.size().unwrap_or_default() as usize;
+        let checksum = Checksum::from_values(
+            value.checksum_crc32_c(),
+            value.checksum_crc64_nvme(),
+            value.checksum_sha256(),
+        );
+        Ok(Self::new(id.clone(), etag, part_number, part_size).with_checksum(checksum))
+    }
 }
 
 /// All completed part uploads for a multipart upload.
@@ -301,10 +467,14 @@ impl DerefMut for CompletedParts {
 impl From<&CompletedParts> for aws_sdk_s3::types::CompletedMultipartUpload {
     fn from(value: &CompletedParts) -> Self {
         let completed_parts = value.0.iter().fold(Vec::new(), |mut acc, v| {
+            let checksum = |algorithm| v.checksum.as_ref()?.value_for(algorithm);
             acc.push(
                 aws_sdk_s3::types::CompletedPart::builder()
                     .e_tag(v.etag.to_string())
                     .part_number(*v.part_number)
+                    .set_checksum_crc32_c(checksum(ChecksumAlgorithm::Crc32c))
+                    .set_checksum_crc64_nvme(checksum(ChecksumAlgorithm::Crc64Nvme))
+                    .set_checksum_sha256(checksum(ChecksumAlgorithm::Sha256))
                     .build(),
             );
 
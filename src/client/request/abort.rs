@@ -1,5 +1,5 @@
 use super::AbortRequestBuilder;
-use crate::client::{UploadClient, UploadId};
+use crate::client::{BucketAccess, LocalUploadClient, UploadClient, UploadId};
 use crate::error::Result;
 use crate::uri::ObjectUri;
 
@@ -8,7 +8,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// Sending a request to abort an in-progress upload.
-pub struct SendAbortUpload(pub(crate) Pin<Box<dyn Future<Output = Result<()>>>>);
+pub struct SendAbortUpload(pub(crate) Pin<Box<dyn Future<Output = Result<()>> + Send>>);
 
 impl SendAbortUpload {
     /// Create a new `SendAbortUpload`.
@@ -35,17 +35,56 @@ impl Debug for SendAbortUpload {
     }
 }
 
+/// `SendAbortUpload` without the `Send` requirement, made by a [`LocalUploadClient`].
+pub struct LocalSendAbortUpload(pub(crate) Pin<Box<dyn Future<Output = Result<()>>>>);
+
+impl LocalSendAbortUpload {
+    /// Create a new `LocalSendAbortUpload`.
+    pub fn new(client: &LocalUploadClient, req: AbortRequest) -> Self {
+        let cli = client.clone();
+        Self(Box::pin(
+            async move { cli.inner.send_abort_upload(req).await },
+        ))
+    }
+}
+
+impl Future for LocalSendAbortUpload {
+    type Output = Result<()>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for LocalSendAbortUpload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSendAbortUpload")
+            .field(&"Future<Output = Result<()>>")
+            .finish()
+    }
+}
+
 /// Request object for aborting a multipart upload.
 #[derive(Debug, Clone)]
 pub struct AbortRequest {
     pub(crate) id: UploadId,
     pub(crate) uri: ObjectUri,
+    pub(crate) access: BucketAccess,
 }
 
 impl AbortRequest {
     /// Create a new `AbortRequest` from the minimum required.
     pub fn new(id: UploadId, uri: ObjectUri) -> Self {
-        Self { id, uri }
+        Self {
+            id,
+            uri,
+            access: BucketAccess::default(),
+        }
+    }
+
+    /// Set the expected owner of the bucket and whether the requester pays for
+    /// the request.
+    pub fn with_bucket_access(self, access: BucketAccess) -> Self {
+        Self { access, ..self }
     }
 
     /// Set the required properties on the SDK request builder for the operation.
@@ -54,5 +93,7 @@ impl AbortRequest {
             .bucket(&*self.uri.bucket)
             .key(&*self.uri.key)
             .upload_id(&*self.id)
+            .set_expected_bucket_owner(self.access.sdk_expected_owner())
+            .set_request_payer(self.access.sdk_request_payer())
     }
 }
@@ -1,6 +1,8 @@
 use super::CompleteRequestBuilder;
 use crate::client::part::{CompletedParts, EntityTag};
-use crate::client::{UploadClient, UploadData, UploadId};
+use crate::client::{
+    BucketAccess, Checksum, CustomerKey, LocalUploadClient, UploadClient, UploadData, UploadId,
+};
 use crate::error::{ErrorRepr, Result};
 use crate::uri::ObjectUri;
 
@@ -10,14 +12,19 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// Sending a request to complete an upload.
-pub struct SendCompleteUpload(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedUpload>>>>);
+pub struct SendCompleteUpload(
+    pub(crate) Pin<Box<dyn Future<Output = Result<CompletedUpload>> + Send>>,
+);
 
 impl SendCompleteUpload {
     /// Create a new `SendCompleteUpload`.
     pub fn new(client: &UploadClient, req: CompleteRequest) -> Self {
         let cli = client.clone();
         Self(Box::pin(async move {
-            cli.inner.send_complete_upload(req).await
+            let send = || cli.inner.send_complete_upload(req.clone());
+            cli.retry
+                .retry("complete_upload", &req.uri.bucket, send)
+                .await
         }))
     }
 }
@@ -37,12 +44,47 @@ impl Debug for SendCompleteUpload {
     }
 }
 
+/// `SendCompleteUpload` without the `Send` requirement, made by a [`LocalUploadClient`].
+pub struct LocalSendCompleteUpload(
+    pub(crate) Pin<Box<dyn Future<Output = Result<CompletedUpload>>>>,
+);
+
+impl LocalSendCompleteUpload {
+    /// Create a new `LocalSendCompleteUpload`.
+    pub fn new(client: &LocalUploadClient, req: CompleteRequest) -> Self {
+        let cli = client.clone();
+        Self(Box::pin(async move {
+            let send = || cli.inner.send_complete_upload(req.clone());
+            cli.retry
+                .retry("complete_upload", &req.uri.bucket, send)
+                .await
+        }))
+    }
+}
+
+impl Future for LocalSendCompleteUpload {
+    type Output = Result<CompletedUpload>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for LocalSendCompleteUpload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSendCompleteUpload")
+            .field(&"Future<Output = Result<CompletedUpload>>")
+            .finish()
+    }
+}
+
 /// Request object for completing a multipart upload.
 #[derive(Debug, Clone)]
 pub struct CompleteRequest {
     pub(crate) id: UploadId,
     pub(crate) uri: ObjectUri,
     pub(crate) completed_parts: CompletedParts,
+    pub(crate) customer_key: Option<CustomerKey>,
+    pub(crate) access: BucketAccess,
 }
 
 impl CompleteRequest {
@@ -52,18 +94,41 @@ impl CompleteRequest {
             id: data.get_id(),
             uri: data.get_uri(),
             completed_parts,
+            customer_key: None,
+            access: BucketAccess::default(),
+        }
+    }
+
+    /// Set the key to send with the request, which is required if the upload
+    /// is encrypted with a key provided by the customer.
+    pub fn with_customer_key(self, key: Option<CustomerKey>) -> Self {
+        Self {
+            customer_key: key,
+            ..self
         }
     }
 
+    /// Set the expected owner of the bucket and whether the requester pays for
+    /// the request.
+    pub fn with_bucket_access(self, access: BucketAccess) -> Self {
+        Self { access, ..self }
+    }
+
     /// Set the required properties on the SDK request builder for the operation.
     pub fn with_builder(&self, builder: CompleteRequestBuilder) -> CompleteRequestBuilder {
         let parts = CompletedMultipartUpload::from(&self.completed_parts);
+        let (algorithm, key, md5) = CustomerKey::headers(self.customer_key.as_ref());
 
         builder
             .upload_id(&*self.id)
             .bucket(&*self.uri.bucket)
             .key(&*self.uri.key)
             .multipart_upload(parts)
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(md5)
+            .set_expected_bucket_owner(self.access.sdk_expected_owner())
+            .set_request_payer(self.access.sdk_request_payer())
     }
 
     /// Returns a reference to the assigned `UploadId` for this request.
@@ -81,6 +146,17 @@ impl CompleteRequest {
         &self.completed_parts
     }
 
+    /// Returns a reference to the `CustomerKey` sent with the request if it
+    /// was set.
+    pub fn customer_key(&self) -> Option<&CustomerKey> {
+        self.customer_key.as_ref()
+    }
+
+    /// Returns a reference to the `BucketAccess` sent with the request.
+    pub fn bucket_access(&self) -> &BucketAccess {
+        &self.access
+    }
+
     pub(crate) fn validate(&self) -> Result<()> {
         if self.id.is_empty() || self.uri.is_empty() {
             return Err(
@@ -98,11 +174,23 @@ pub struct CompletedUpload {
     pub uri: ObjectUri,
     /// The entity tag of the created object.
     pub etag: EntityTag,
+    /// The checksum of the created object if the upload was configured with a
+    /// checksum algorithm.
+    pub checksum: Option<Checksum>,
 }
 
 impl CompletedUpload {
     /// Create a new value from object URI and entity tag.
     pub fn new(uri: ObjectUri, etag: EntityTag) -> Self {
-        Self { uri, etag }
+        Self {
+            uri,
+            etag,
+            checksum: None,
+        }
+    }
+
+    /// Set the checksum of the object.
+    pub fn with_checksum(self, checksum: Option<Checksum>) -> Self {
+        Self { checksum, ..self }
     }
 }
@@ -1,5 +1,8 @@
 use super::CreateRequestBuilder;
-use crate::client::{UploadClient, UploadData};
+use crate::client::{
+    ChecksumAlgorithm, CustomerKey, Encryption, LocalUploadClient, ObjectAttributes, UploadClient,
+    UploadData,
+};
 use crate::error::{ErrorRepr, Result};
 use crate::uri::ObjectUri;
 
@@ -8,15 +11,18 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// Sending a request to create a new upload.
-pub struct SendCreateUpload(pub(crate) Pin<Box<dyn Future<Output = Result<UploadData>>>>);
+pub struct SendCreateUpload(pub(crate) Pin<Box<dyn Future<Output = Result<UploadData>> + Send>>);
 
 impl SendCreateUpload {
     /// Create a new `SendCreateUpload`.
     pub fn new(client: &UploadClient, req: CreateRequest) -> Self {
         let cli = client.clone();
-        Self(Box::pin(
-            async move { cli.inner.send_create_upload(req).await },
-        ))
+        Self(Box::pin(async move {
+            let send = || cli.inner.send_create_upload(req.clone());
+            cli.retry
+                .retry("create_upload", &req.uri.bucket, send)
+                .await
+        }))
     }
 }
 
@@ -35,21 +41,104 @@ impl Debug for SendCreateUpload {
     }
 }
 
+/// `SendCreateUpload` without the `Send` requirement, made by a [`LocalUploadClient`].
+pub struct LocalSendCreateUpload(pub(crate) Pin<Box<dyn Future<Output = Result<UploadData>>>>);
+
+impl LocalSendCreateUpload {
+    /// Create a new `LocalSendCreateUpload`.
+    pub fn new(client: &LocalUploadClient, req: CreateRequest) -> Self {
+        let cli = client.clone();
+        Self(Box::pin(async move {
+            let send = || cli.inner.send_create_upload(req.clone());
+            cli.retry
+                .retry("create_upload", &req.uri.bucket, send)
+                .await
+        }))
+    }
+}
+
+impl Future for LocalSendCreateUpload {
+    type Output = Result<UploadData>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for LocalSendCreateUpload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSendCreateUpload")
+            .field(&"Future<Output = Result<UploadData>>")
+            .finish()
+    }
+}
+
 /// Request object for creating a new multipart upload.
 #[derive(Debug, Clone)]
 pub struct CreateRequest {
     pub(crate) uri: ObjectUri,
+    pub(crate) checksum: Option<ChecksumAlgorithm>,
+    pub(crate) encryption: Option<Encryption>,
+    pub(crate) attributes: ObjectAttributes,
 }
 
 impl CreateRequest {
     /// Create a new `CreateRequest` from the minimum required.
     pub fn new(uri: ObjectUri) -> Self {
-        Self { uri }
+        Self {
+            uri,
+            checksum: None,
+            encryption: None,
+            attributes: ObjectAttributes::default(),
+        }
+    }
+
+    /// Set the algorithm for the checksums of the upload.
+    pub fn with_checksum(self, algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            checksum: Some(algorithm),
+            ..self
+        }
+    }
+
+    /// Set the server-side encryption of the object.
+    pub fn with_encryption(self, encryption: Encryption) -> Self {
+        Self {
+            encryption: Some(encryption),
+            ..self
+        }
+    }
+
+    /// Set the attributes of the object.
+    pub fn with_attributes(self, attributes: ObjectAttributes) -> Self {
+        Self { attributes, ..self }
     }
 
     /// Set the required properties on the SDK request builder for the operation.
     pub fn with_builder(&self, builder: CreateRequestBuilder) -> CreateRequestBuilder {
-        builder.bucket(&*self.uri.bucket).key(&*self.uri.key)
+        let encryption = self.encryption.as_ref();
+        let customer_key = encryption.and_then(Encryption::customer_key);
+        let (algorithm, key, md5) = CustomerKey::headers(customer_key);
+        let attributes = &self.attributes;
+        let access = attributes.bucket_access();
+        builder
+            .bucket(&*self.uri.bucket)
+            .key(&*self.uri.key)
+            .set_content_type(attributes.content_type().map(String::from))
+            .set_content_encoding(attributes.content_encoding().map(String::from))
+            .set_cache_control(attributes.cache_control().map(String::from))
+            .set_metadata(attributes.sdk_metadata())
+            .set_tagging(attributes.tagging())
+            .set_storage_class(attributes.storage_class().cloned())
+            .set_expected_bucket_owner(access.sdk_expected_owner())
+            .set_request_payer(access.sdk_request_payer())
+            .set_checksum_algorithm(self.checksum.map(Into::into))
+            .set_checksum_type(self.checksum.map(ChecksumAlgorithm::checksum_type))
+            .set_server_side_encryption(encryption.and_then(Encryption::algorithm))
+            .set_ssekms_key_id(encryption.and_then(Encryption::kms_key_id))
+            .set_ssekms_encryption_context(encryption.and_then(Encryption::kms_context))
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(md5)
     }
 
     /// Returns a reference to the `ObjectUri` for this request.
@@ -57,6 +146,22 @@ impl CreateRequest {
         &self.uri
     }
 
+    /// Returns the algorithm for the checksums of the upload if there is one.
+    pub fn checksum_algorithm(&self) -> Option<ChecksumAlgorithm> {
+        self.checksum
+    }
+
+    /// Returns a reference to the server-side encryption of the object if it
+    /// was set.
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Returns a reference to the attributes of the object.
+    pub fn attributes(&self) -> &ObjectAttributes {
+        &self.attributes
+    }
+
     pub(crate) fn validate(&self) -> Result<()> {
         if self.uri.is_empty() {
             return Err(ErrorRepr::Missing("CreateRequest", "empty object uri").into());
@@ -0,0 +1,151 @@
+use super::ListPartsRequestBuilder;
+use crate::client::part::CompletedParts;
+use crate::client::{
+    BucketAccess, CustomerKey, LocalUploadClient, UploadClient, UploadData, UploadId,
+};
+use crate::error::{ErrorRepr, Result};
+use crate::uri::ObjectUri;
+
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Sending a request to list the parts uploaded to an upload so far.
+pub struct SendListParts(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedParts>> + Send>>);
+
+impl SendListParts {
+    /// Create a new `SendListParts`.
+    pub fn new(client: &UploadClient, req: ListPartsRequest) -> Self {
+        let cli = client.clone();
+        Self(Box::pin(async move {
+            let send = || cli.inner.send_list_parts(req.clone());
+            cli.retry.retry("list_parts", &req.uri.bucket, send).await
+        }))
+    }
+}
+
+impl Future for SendListParts {
+    type Output = Result<CompletedParts>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for SendListParts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendListParts")
+            .field(&"Future<Output = Result<CompletedParts>>")
+            .finish()
+    }
+}
+
+/// `SendListParts` without the `Send` requirement, made by a [`LocalUploadClient`].
+pub struct LocalSendListParts(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedParts>>>>);
+
+impl LocalSendListParts {
+    /// Create a new `LocalSendListParts`.
+    pub fn new(client: &LocalUploadClient, req: ListPartsRequest) -> Self {
+        let cli = client.clone();
+        Self(Box::pin(async move {
+            let send = || cli.inner.send_list_parts(req.clone());
+            cli.retry.retry("list_parts", &req.uri.bucket, send).await
+        }))
+    }
+}
+
+impl Future for LocalSendListParts {
+    type Output = Result<CompletedParts>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for LocalSendListParts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSendListParts")
+            .field(&"Future<Output = Result<CompletedParts>>")
+            .finish()
+    }
+}
+
+/// Request object for listing the parts of a multipart upload.
+#[derive(Debug, Clone)]
+pub struct ListPartsRequest {
+    pub(crate) id: UploadId,
+    pub(crate) uri: ObjectUri,
+    pub(crate) customer_key: Option<CustomerKey>,
+    pub(crate) access: BucketAccess,
+}
+
+impl ListPartsRequest {
+    /// Create a new `ListPartsRequest` from the minimum required.
+    pub fn new(data: &UploadData) -> Self {
+        Self {
+            id: data.get_id(),
+            uri: data.get_uri(),
+            customer_key: None,
+            access: BucketAccess::default(),
+        }
+    }
+
+    /// Set the key to send with the request, which is required if the upload
+    /// is encrypted with a key provided by the customer.
+    pub fn with_customer_key(self, key: Option<CustomerKey>) -> Self {
+        Self {
+            customer_key: key,
+            ..self
+        }
+    }
+
+    /// Set the expected owner of the bucket and whether the requester pays for
+    /// the request.
+    pub fn with_bucket_access(self, access: BucketAccess) -> Self {
+        Self { access, ..self }
+    }
+
+    /// Set the required properties on the SDK request builder for the operation.
+    ///
+    /// The response is paginated, so this is only the first page of parts.
+    pub fn with_builder(&self, builder: ListPartsRequestBuilder) -> ListPartsRequestBuilder {
+        let (algorithm, key, md5) = CustomerKey::headers(self.customer_key.as_ref());
+        builder
+            .upload_id(&*self.id)
+            .bucket(&*self.uri.bucket)
+            .key(&*self.uri.key)
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(md5)
+            .set_expected_bucket_owner(self.access.sdk_expected_owner())
+            .set_request_payer(self.access.sdk_request_payer())
+    }
+
+    /// Returns a reference to the assigned `UploadId` for this request.
+    pub fn id(&self) -> &UploadId {
+        &self.id
+    }
+
+    /// Returns a reference to the `ObjectUri` for this request.
+    pub fn uri(&self) -> &ObjectUri {
+        &self.uri
+    }
+
+    /// Returns a reference to the `CustomerKey` sent with the request if it
+    /// was set.
+    pub fn customer_key(&self) -> Option<&CustomerKey> {
+        self.customer_key.as_ref()
+    }
+
+    /// Returns a reference to the `BucketAccess` sent with the request.
+    pub fn bucket_access(&self) -> &BucketAccess {
+        &self.access
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.id.is_empty() || self.uri.is_empty() {
+            return Err(
+                ErrorRepr::Missing("ListPartsRequest", "empty upload id and/or uri").into(),
+            );
+        }
+        Ok(())
+    }
+}
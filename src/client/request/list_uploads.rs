@@ -0,0 +1,100 @@
+use super::ListUploadsRequestBuilder;
+use crate::client::UploadData;
+use crate::error::{ErrorRepr, Result};
+use crate::uri::{Bucket, KeyPrefix};
+
+use aws_sdk::types::MultipartUpload;
+use std::time::{Duration, SystemTime};
+
+/// Request object for listing the multipart uploads in a bucket that have not
+/// been completed or aborted.
+#[derive(Debug, Clone)]
+pub struct ListUploadsRequest {
+    pub(crate) bucket: Bucket,
+    pub(crate) prefix: Option<KeyPrefix>,
+}
+
+impl ListUploadsRequest {
+    /// Create a new `ListUploadsRequest` for all uploads in the bucket.
+    pub fn new<T: Into<Bucket>>(bucket: T) -> Self {
+        Self {
+            bucket: bucket.into(),
+            prefix: None,
+        }
+    }
+
+    /// Only list uploads to an object key with this prefix.
+    pub fn with_prefix<T: Into<KeyPrefix>>(self, prefix: T) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            ..self
+        }
+    }
+
+    /// Set the required properties on the SDK request builder for the operation.
+    ///
+    /// The response is paginated, so this is only the first page of uploads.
+    pub fn with_builder(&self, builder: ListUploadsRequestBuilder) -> ListUploadsRequestBuilder {
+        builder
+            .bucket(&*self.bucket)
+            .set_prefix(self.prefix.as_deref().map(String::from))
+    }
+
+    /// Returns a reference to the `Bucket` for this request.
+    pub fn bucket(&self) -> &Bucket {
+        &self.bucket
+    }
+
+    /// Returns a reference to the `KeyPrefix` for this request if there is one.
+    pub fn prefix(&self) -> Option<&KeyPrefix> {
+        self.prefix.as_ref()
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.bucket.is_empty() {
+            return Err(ErrorRepr::Missing("ListUploadsRequest", "empty bucket").into());
+        }
+        Ok(())
+    }
+}
+
+/// A multipart upload that has not been completed or aborted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompleteUpload {
+    /// The upload ID and the URI of the object being uploaded.
+    pub data: UploadData,
+    /// When the upload was created.
+    pub initiated: SystemTime,
+}
+
+impl IncompleteUpload {
+    /// Create a new value from the upload data and the time it was created.
+    pub fn new(data: UploadData, initiated: SystemTime) -> Self {
+        Self { data, initiated }
+    }
+
+    /// Returns how long ago the upload was created.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.initiated)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn try_from_upload(
+        bucket: &Bucket,
+        value: &MultipartUpload,
+    ) -> Result<Self, ErrorRepr> {
+        let id = value
+            .upload_id()
+            .ok_or_else(|| ErrorRepr::Missing("MultipartUpload", "upload_id"))?;
+        let key = value
+            .key()
+            .ok_or_else(|| ErrorRepr::Missing("MultipartUpload", "key"))?;
+        let initiated = value
+            .initiated()
+            .and_then(|dt| SystemTime::try_from(*dt).ok())
+            .ok_or_else(|| ErrorRepr::Missing("MultipartUpload", "initiated"))?;
+        let data = UploadData::new(id, (bucket.clone(), key));
+        Ok(Self::new(data, initiated))
+    }
+}
@@ -1,19 +1,31 @@
 pub use crate::abort_upload::builders::AbortMultipartUploadFluentBuilder as AbortRequestBuilder;
 pub use crate::complete_upload::builders::CompleteMultipartUploadFluentBuilder as CompleteRequestBuilder;
 pub use crate::create_upload::builders::CreateMultipartUploadFluentBuilder as CreateRequestBuilder;
+pub use crate::list_parts::builders::ListPartsFluentBuilder as ListPartsRequestBuilder;
+pub use crate::list_uploads::builders::ListMultipartUploadsFluentBuilder as ListUploadsRequestBuilder;
+pub use crate::part_copy::builders::UploadPartCopyFluentBuilder as UploadPartCopyRequestBuilder;
 pub use crate::part_upload::builders::UploadPartFluentBuilder as UploadPartRequestBuilder;
 
 mod abort;
-pub use abort::{AbortRequest, SendAbortUpload};
+pub use abort::{AbortRequest, LocalSendAbortUpload, SendAbortUpload};
 
 mod complete;
-pub use complete::{CompleteRequest, CompletedUpload, SendCompleteUpload};
+pub use complete::{CompleteRequest, CompletedUpload, LocalSendCompleteUpload, SendCompleteUpload};
 
 mod create;
-pub use create::{CreateRequest, SendCreateUpload};
+pub use create::{CreateRequest, LocalSendCreateUpload, SendCreateUpload};
+
+mod list_parts;
+pub use list_parts::{ListPartsRequest, LocalSendListParts, SendListParts};
+
+mod list_uploads;
+pub use list_uploads::{IncompleteUpload, ListUploadsRequest};
 
 mod upload_part;
-pub use upload_part::{SendUploadPart, UploadPartRequest};
+pub use upload_part::{LocalSendUploadPart, SendUploadPart, UploadPartRequest};
+
+mod upload_part_copy;
+pub use upload_part_copy::{CopySource, UploadPartCopyRequest};
 
 /// Add additional properties to the request objects being sent.
 pub trait RequestBuilder {
@@ -36,6 +48,17 @@ pub trait RequestBuilder {
         builder
     }
 
+    /// Set additional properties on [`UploadPartCopyRequestBuilder`] beyond
+    /// what [`UploadPartCopyRequest`] provides.
+    ///
+    /// [`UploadPartCopyRequest`]: self::upload_part_copy::UploadPartCopyRequest
+    fn with_upload_part_copy_builder(
+        &self,
+        builder: UploadPartCopyRequestBuilder,
+    ) -> UploadPartCopyRequestBuilder {
+        builder
+    }
+
     /// Set additional properties on [`CompleteRequestBuilder`] beyond what
     /// [`CompleteRequest`] provides.
     ///
@@ -51,6 +74,25 @@ pub trait RequestBuilder {
     fn with_abort_builder(&self, builder: AbortRequestBuilder) -> AbortRequestBuilder {
         builder
     }
+
+    /// Set additional properties on [`ListPartsRequestBuilder`] beyond what
+    /// [`ListPartsRequest`] provides.
+    ///
+    /// [`ListPartsRequest`]: self::list_parts::ListPartsRequest
+    fn with_list_parts_builder(&self, builder: ListPartsRequestBuilder) -> ListPartsRequestBuilder {
+        builder
+    }
+
+    /// Set additional properties on [`ListUploadsRequestBuilder`] beyond what
+    /// [`ListUploadsRequest`] provides.
+    ///
+    /// [`ListUploadsRequest`]: self::list_uploads::ListUploadsRequest
+    fn with_list_uploads_builder(
+        &self,
+        builder: ListUploadsRequestBuilder,
+    ) -> ListUploadsRequestBuilder {
+        builder
+    }
 }
 
 /// Default implementation of `RequestBuilder` that doesn't modify the request
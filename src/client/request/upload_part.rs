@@ -1,23 +1,52 @@
-use super::UploadPartRequestBuilder;
-use crate::client::part::{CompletedPart, PartBody, PartNumber};
-use crate::client::{UploadClient, UploadData, UploadId};
+use super::{UploadPartCopyRequest, UploadPartRequestBuilder};
+use crate::client::part::{CompletedPart, PartBody, PartFile, PartNumber, RequestBody};
+use crate::client::{
+    BucketAccess, Checksum, ChecksumAlgorithm, CustomerKey, LocalUploadClient, UploadClient,
+    UploadData, UploadId,
+};
 use crate::error::{ErrorRepr, Result};
+use crate::instrument::ActiveTask;
 use crate::uri::ObjectUri;
+use crate::write::Reservation;
 
+use aws_sdk_s3::primitives::ByteStream;
 use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// Sending a request to add a part to an existing multpart upload.
-pub struct SendUploadPart(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedPart>>>>);
+pub struct SendUploadPart(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedPart>> + Send>>);
 
 impl SendUploadPart {
     /// Create a new `SendUploadPart`.
-    pub fn new(client: &UploadClient, req: UploadPartRequest) -> Self {
+    pub fn new(client: &UploadClient, mut req: UploadPartRequest) -> Self {
         let cli = client.clone();
-        Self(Box::pin(
-            async move { cli.inner.send_upload_part(req).await },
-        ))
+        let task = ActiveTask::new(&req.uri.bucket);
+        let reservation = req.reservation.take();
+        Self(Box::pin(async move {
+            let _task = task;
+            // The bytes stay in the budget until the request finishes or is
+            // cancelled by dropping this future.
+            let _reservation = reservation;
+            let send = || cli.inner.send_upload_part(req.clone());
+            cli.retry.retry("upload_part", &req.uri.bucket, send).await
+        }))
+    }
+
+    /// Create a new `SendUploadPart` for a part copied from an existing
+    /// object.
+    pub fn copy(client: &UploadClient, req: UploadPartCopyRequest) -> Self {
+        let cli = client.clone();
+        let task = ActiveTask::new(&req.uri.bucket);
+        Self(Box::pin(async move {
+            let _task = task;
+            let send = || cli.inner.send_upload_part_copy(req.clone());
+            cli.retry
+                .retry("upload_part_copy", &req.uri.bucket, send)
+                .await
+        }))
     }
 }
 
@@ -36,13 +65,69 @@ impl Debug for SendUploadPart {
     }
 }
 
+/// `SendUploadPart` without the `Send` requirement, made by a [`LocalUploadClient`].
+pub struct LocalSendUploadPart(pub(crate) Pin<Box<dyn Future<Output = Result<CompletedPart>>>>);
+
+impl LocalSendUploadPart {
+    /// Create a new `LocalSendUploadPart`.
+    pub fn new(client: &LocalUploadClient, mut req: UploadPartRequest) -> Self {
+        let cli = client.clone();
+        let task = ActiveTask::new(&req.uri.bucket);
+        let reservation = req.reservation.take();
+        Self(Box::pin(async move {
+            let _task = task;
+            let _reservation = reservation;
+            let send = || cli.inner.send_upload_part(req.clone());
+            cli.retry.retry("upload_part", &req.uri.bucket, send).await
+        }))
+    }
+
+    /// Create a new `LocalSendUploadPart` for a part copied from an existing
+    /// object.
+    pub fn copy(client: &LocalUploadClient, req: UploadPartCopyRequest) -> Self {
+        let cli = client.clone();
+        let task = ActiveTask::new(&req.uri.bucket);
+        Self(Box::pin(async move {
+            let _task = task;
+            let send = || cli.inner.send_upload_part_copy(req.clone());
+            cli.retry
+                .retry("upload_part_copy", &req.uri.bucket, send)
+                .await
+        }))
+    }
+}
+
+impl Future for LocalSendUploadPart {
+    type Output = Result<CompletedPart>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl Debug for LocalSendUploadPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSendUploadPart")
+            .field(&"Future<Output = Result<CompletedPart>>")
+            .finish()
+    }
+}
+
 /// Request object for uploading a new part.
+///
+/// The [`PartBody`] is frozen when the request is created, so the request is
+/// cheap to clone and can be sent again if it fails.  If the upload stages
+/// parts on disk, the body is moved to a [`PartFile`] before the request is
+/// sent, see [`RequestBody`].
 #[derive(Debug, Clone)]
 pub struct UploadPartRequest {
     pub(crate) id: UploadId,
     pub(crate) uri: ObjectUri,
-    pub(crate) body: PartBody,
+    pub(crate) body: RequestBody,
     pub(crate) part_number: PartNumber,
+    pub(crate) checksum: Option<Checksum>,
+    pub(crate) customer_key: Option<CustomerKey>,
+    pub(crate) access: BucketAccess,
+    pub(crate) reservation: Option<Arc<Reservation>>,
 }
 
 impl UploadPartRequest {
@@ -51,19 +136,72 @@ impl UploadPartRequest {
         Self {
             id: data.get_id(),
             uri: data.get_uri(),
-            body,
+            body: RequestBody::Bytes(body.freeze()),
             part_number,
+            checksum: None,
+            customer_key: None,
+            access: BucketAccess::default(),
+            reservation: None,
         }
     }
 
+    /// Compute the checksum of the part body with `algorithm` to send with the
+    /// request.
+    ///
+    /// The checksum of a body staged on disk was computed before it was
+    /// written to the file, so this does nothing in that case.
+    pub fn with_checksum(self, algorithm: ChecksumAlgorithm) -> Self {
+        let RequestBody::Bytes(bytes) = &self.body else {
+            return self;
+        };
+        let checksum = algorithm.checksum(bytes);
+        Self {
+            checksum: Some(checksum),
+            ..self
+        }
+    }
+
+    /// Set the key to send with the request, which is required if the upload
+    /// is encrypted with a key provided by the customer.
+    pub fn with_customer_key(self, key: Option<CustomerKey>) -> Self {
+        Self {
+            customer_key: key,
+            ..self
+        }
+    }
+
+    /// Set the expected owner of the bucket and whether the requester pays for
+    /// the request.
+    pub fn with_bucket_access(self, access: BucketAccess) -> Self {
+        Self { access, ..self }
+    }
+
     /// Set the required properties on the SDK request builder for the operation.
-    pub fn with_builder(&mut self, builder: UploadPartRequestBuilder) -> UploadPartRequestBuilder {
+    ///
+    /// This sets the body if it is in memory.  A body staged on disk is not
+    /// set, because it is streamed from the file with the `ByteStream` made by
+    /// [`RequestBody::to_sdk_body`].
+    pub fn with_builder(&self, builder: UploadPartRequestBuilder) -> UploadPartRequestBuilder {
+        let (algorithm, key, md5) = CustomerKey::headers(self.customer_key.as_ref());
+        let body = match &self.body {
+            RequestBody::Bytes(bytes) => Some(ByteStream::from(bytes.clone())),
+            RequestBody::File(_) => None,
+        };
         builder
             .upload_id(&*self.id)
             .bucket(&*self.uri.bucket)
             .key(&*self.uri.key)
             .part_number(*self.part_number)
-            .body(self.body.as_sdk_body())
+            .content_length(self.body.len() as i64)
+            .set_body(body)
+            .set_checksum_crc32_c(self.checksum_value(ChecksumAlgorithm::Crc32c))
+            .set_checksum_crc64_nvme(self.checksum_value(ChecksumAlgorithm::Crc64Nvme))
+            .set_checksum_sha256(self.checksum_value(ChecksumAlgorithm::Sha256))
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(md5)
+            .set_expected_bucket_owner(self.access.sdk_expected_owner())
+            .set_request_payer(self.access.sdk_request_payer())
     }
 
     /// Returns a reference to the assigned `UploadId` for this request.
@@ -76,8 +214,8 @@ impl UploadPartRequest {
         &self.uri
     }
 
-    /// Returns a reference to the `PartBody` for this request.
-    pub fn body(&self) -> &PartBody {
+    /// Returns a reference to the body of this request.
+    pub fn body(&self) -> &RequestBody {
         &self.body
     }
 
@@ -86,6 +224,52 @@ impl UploadPartRequest {
         self.part_number
     }
 
+    /// Returns a reference to the `Checksum` of the part body if it was set.
+    pub fn checksum(&self) -> Option<&Checksum> {
+        self.checksum.as_ref()
+    }
+
+    /// Returns a reference to the `CustomerKey` sent with the request if it
+    /// was set.
+    pub fn customer_key(&self) -> Option<&CustomerKey> {
+        self.customer_key.as_ref()
+    }
+
+    /// Returns a reference to the `BucketAccess` sent with the request.
+    pub fn bucket_access(&self) -> &BucketAccess {
+        &self.access
+    }
+
+    fn checksum_value(&self, algorithm: ChecksumAlgorithm) -> Option<String> {
+        self.checksum.as_ref().and_then(|c| c.value_for(algorithm))
+    }
+
+    /// Move the body of the request to a new file in the directory `dir`.
+    ///
+    /// The file is written on a blocking thread, and the bytes in memory are
+    /// released once it has been, along with their reservation in the budget
+    /// of bytes in flight.
+    pub(crate) async fn stage(mut self, dir: PathBuf) -> Result<Self> {
+        let RequestBody::Bytes(bytes) = self.body else {
+            return Ok(self);
+        };
+        let reservation = self.reservation.take();
+        let file = tokio::task::spawn_blocking(move || {
+            let file = PartFile::create(&dir, &bytes);
+            drop((bytes, reservation));
+            file
+        })
+        .await
+        .map_err(std::io::Error::other)
+        .and_then(|res| res)
+        .map_err(ErrorRepr::Disk)?;
+        trace!(path = ?file.path(), part = %self.part_number, "staged part on disk");
+        Ok(Self {
+            body: RequestBody::File(Arc::new(file)),
+            ..self
+        })
+    }
+
     pub(crate) fn validate(&self) -> Result<()> {
         if self.id.is_empty() || self.uri.is_empty() {
             return Err(
@@ -95,3 +279,28 @@ impl UploadPartRequest {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::config::{BehaviorVersion, Region};
+
+    fn builder() -> UploadPartRequestBuilder {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .build();
+        aws_sdk_s3::Client::from_conf(config).upload_part()
+    }
+
+    #[test]
+    fn with_builder_sets_body_in_memory() {
+        let data = UploadData::new("id", ("bucket", "key"));
+        let body = PartBody::from(bytes::BytesMut::from(&b"part"[..]));
+        let req = UploadPartRequest::new(&data, body, PartNumber::new(1));
+        let builder = req.with_builder(builder());
+        let body = builder.get_body().as_ref().expect("body is set");
+        assert_eq!(body.bytes(), Some(&b"part"[..]));
+        assert_eq!(builder.get_content_length(), &Some(4));
+    }
+}
@@ -0,0 +1,211 @@
+use super::UploadPartCopyRequestBuilder;
+use crate::client::part::PartNumber;
+use crate::client::{BucketAccess, CustomerKey, UploadData, UploadId};
+use crate::error::{ErrorRepr, Result};
+use crate::uri::{ObjectUri, encode_key};
+
+use bytesize::ByteSize;
+use std::ops::Range;
+
+/// The existing object, or a byte range of it, that is copied to a part.
+///
+/// Like any other part, a copied part that is not the last part of the upload
+/// has to be at least 5 MiB, and no part can be more than 5 GiB, so an object
+/// larger than that is copied in ranges, see [`CopySource::parts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopySource {
+    pub(crate) uri: ObjectUri,
+    pub(crate) range: Option<Range<u64>>,
+    pub(crate) customer_key: Option<CustomerKey>,
+}
+
+impl CopySource {
+    /// Copy the whole object at `uri`.
+    pub fn new<T: Into<ObjectUri>>(uri: T) -> Self {
+        Self {
+            uri: uri.into(),
+            range: None,
+            customer_key: None,
+        }
+    }
+
+    /// Copy only the bytes of the object in `range`.
+    pub fn with_range(self, range: Range<u64>) -> Self {
+        Self {
+            range: Some(range),
+            ..self
+        }
+    }
+
+    /// Set the key that the object to copy is encrypted with, if it was
+    /// encrypted with a key provided by the customer.
+    pub fn with_customer_key(self, key: CustomerKey) -> Self {
+        Self {
+            customer_key: Some(key),
+            ..self
+        }
+    }
+
+    /// Split the object at `uri` having `size` bytes into ranges of at most
+    /// `part_size` bytes, in order.
+    ///
+    /// The object size can be found with a `HeadObject` request, for instance.
+    pub fn parts<T: Into<ObjectUri>>(uri: T, size: u64, part_size: ByteSize) -> Vec<Self> {
+        let source = Self::new(uri);
+        let step = part_size.as_u64().max(1);
+        (0..size)
+            .step_by(step as usize)
+            .map(|start| source.clone().with_range(start..size.min(start + step)))
+            .collect()
+    }
+
+    /// Returns a reference to the `ObjectUri` of the object to copy.
+    pub fn uri(&self) -> &ObjectUri {
+        &self.uri
+    }
+
+    /// Returns the byte range of the object to copy, if it is not the whole
+    /// object.
+    pub fn range(&self) -> Option<&Range<u64>> {
+        self.range.as_ref()
+    }
+
+    /// Returns the number of bytes that are copied, if it is known.
+    pub fn size(&self) -> Option<u64> {
+        self.range.as_ref().map(|r| r.end.saturating_sub(r.start))
+    }
+
+    /// The value of the `x-amz-copy-source` header, which is the bucket and
+    /// the URL-encoded key.
+    fn header(&self) -> String {
+        format!("{}/{}", &*self.uri.bucket, encode_key(&self.uri.key))
+    }
+
+    /// The value of the `x-amz-copy-source-range` header, where the range of
+    /// bytes is inclusive.
+    fn range_header(&self) -> Option<String> {
+        let range = self.range.as_ref()?;
+        Some(format!(
+            "bytes={}-{}",
+            range.start,
+            range.end.saturating_sub(1)
+        ))
+    }
+}
+
+/// Request object for adding a part copied from an existing object.
+#[derive(Debug, Clone)]
+pub struct UploadPartCopyRequest {
+    pub(crate) id: UploadId,
+    pub(crate) uri: ObjectUri,
+    pub(crate) source: CopySource,
+    pub(crate) part_number: PartNumber,
+    pub(crate) customer_key: Option<CustomerKey>,
+    pub(crate) access: BucketAccess,
+}
+
+impl UploadPartCopyRequest {
+    /// Create a new `UploadPartCopyRequest` from the minimum required.
+    pub fn new(data: &UploadData, source: CopySource, part_number: PartNumber) -> Self {
+        Self {
+            id: data.get_id(),
+            uri: data.get_uri(),
+            source,
+            part_number,
+            customer_key: None,
+            access: BucketAccess::default(),
+        }
+    }
+
+    /// Set the key to send with the request, which is required if the upload
+    /// is encrypted with a key provided by the customer.
+    pub fn with_customer_key(self, key: Option<CustomerKey>) -> Self {
+        Self {
+            customer_key: key,
+            ..self
+        }
+    }
+
+    /// Set the expected owner of the bucket and whether the requester pays for
+    /// the request.
+    pub fn with_bucket_access(self, access: BucketAccess) -> Self {
+        Self { access, ..self }
+    }
+
+    /// Set the required properties on the SDK request builder for the operation.
+    pub fn with_builder(
+        &self,
+        builder: UploadPartCopyRequestBuilder,
+    ) -> UploadPartCopyRequestBuilder {
+        let (algorithm, key, md5) = CustomerKey::headers(self.customer_key.as_ref());
+        let (src_algorithm, src_key, src_md5) =
+            CustomerKey::headers(self.source.customer_key.as_ref());
+        builder
+            .upload_id(&*self.id)
+            .bucket(&*self.uri.bucket)
+            .key(&*self.uri.key)
+            .part_number(*self.part_number)
+            .copy_source(self.source.header())
+            .set_copy_source_range(self.source.range_header())
+            .set_copy_source_sse_customer_algorithm(src_algorithm)
+            .set_copy_source_sse_customer_key(src_key)
+            .set_copy_source_sse_customer_key_md5(src_md5)
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(md5)
+            .set_expected_bucket_owner(self.access.sdk_expected_owner())
+            .set_request_payer(self.access.sdk_request_payer())
+    }
+
+    /// Returns a reference to the assigned `UploadId` for this request.
+    pub fn id(&self) -> &UploadId {
+        &self.id
+    }
+
+    /// Returns a reference to the `ObjectUri` for this request.
+    pub fn uri(&self) -> &ObjectUri {
+        &self.uri
+    }
+
+    /// Returns a reference to the `CopySource` for this request.
+    pub fn source(&self) -> &CopySource {
+        &self.source
+    }
+
+    /// Returns a reference to the `PartNumber` for this request.
+    pub fn part_number(&self) -> PartNumber {
+        self.part_number
+    }
+
+    /// Returns a reference to the `CustomerKey` sent with the request if it
+    /// was set.
+    pub fn customer_key(&self) -> Option<&CustomerKey> {
+        self.customer_key.as_ref()
+    }
+
+    /// Returns a reference to the `BucketAccess` sent with the request.
+    pub fn bucket_access(&self) -> &BucketAccess {
+        &self.access
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.id.is_empty() || self.uri.is_empty() {
+            return Err(
+                ErrorRepr::Missing("UploadPartCopyRequest", "empty upload id and/or uri").into(),
+            );
+        }
+        if self.source.uri.is_empty() {
+            return Err(ErrorRepr::Missing("UploadPartCopyRequest", "empty source uri").into());
+        }
+        if let Some(range) = self.source.range()
+            && range.is_empty()
+        {
+            let msg = format!(
+                "empty byte range {range:?} to copy from {}",
+                self.source.uri
+            );
+            return Err(ErrorRepr::Invalid(msg).into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::{Error, Result};
+use crate::instrument;
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(20);
+
+/// Policy for retrying a failed request.
+///
+/// This applies to the requests to create an upload, upload a part, and
+/// complete an upload.  A request is attempted at most `max_attempts` times,
+/// waiting between attempts for an exponentially increasing duration, as long
+/// as the error it failed with is considered retryable.
+///
+/// By default, a request is attempted three times starting with a backoff of
+/// 200ms, and an error is retryable according to [`Error::is_retryable`].
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable: Arc<dyn Fn(&Error) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    /// Returns a `RetryPolicy` that makes only one attempt.
+    pub fn no_retry() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set the maximum number of times to attempt the request, including the
+    /// first attempt.
+    pub fn max_attempts(self, n: u32) -> Self {
+        Self {
+            max_attempts: n.max(1),
+            ..self
+        }
+    }
+
+    /// Set the duration to wait before the first retry.
+    ///
+    /// This doubles for every subsequent retry.
+    pub fn initial_backoff(self, backoff: Duration) -> Self {
+        Self {
+            initial_backoff: backoff,
+            ..self
+        }
+    }
+
+    /// Set the maximum duration to wait before any retry.
+    pub fn max_backoff(self, backoff: Duration) -> Self {
+        Self {
+            max_backoff: backoff,
+            ..self
+        }
+    }
+
+    /// Set whether to wait for a random duration between zero and the current
+    /// backoff instead of the backoff itself.
+    pub fn jitter(self, jitter: bool) -> Self {
+        Self { jitter, ..self }
+    }
+
+    /// Set the predicate deciding whether a request that failed with the given
+    /// error should be attempted again.
+    pub fn retry_if<F>(self, f: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            retryable: Arc::new(f),
+            ..self
+        }
+    }
+
+    /// The duration to wait after the `attempt`th attempt failed.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exp)
+            .min(self.max_backoff);
+        if self.jitter {
+            backoff.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+
+    /// Run the request made by `f` for the `op` operation on `bucket` until it
+    /// succeeds or the policy says to stop.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) async fn retry<T, F, Fut>(
+        &self,
+        op: &'static str,
+        bucket: &str,
+        mut f: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Ok(v) => {
+                    if attempt > 1 {
+                        trace!(op, attempts = attempt, "request succeeded after retry");
+                    }
+                    return Ok(v);
+                }
+                Err(e) if attempt < self.max_attempts && (self.retryable)(&e) => {
+                    let backoff = self.backoff(attempt);
+                    trace!(op, attempt, ?backoff, error = %e, "retrying failed request");
+                    instrument::retry(op, bucket);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    trace!(op, attempts = attempt, error = %e, "request failed");
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: true,
+            retryable: Arc::new(Error::is_retryable),
+        }
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("retryable", &"Fn(&Error) -> bool")
+            .finish()
+    }
+}
@@ -1,7 +1,10 @@
-use crate::client::part::{CompletedPart, EntityTag};
+use crate::client::part::{CompletedPart, CompletedParts, EntityTag};
 use crate::client::request::*;
-use crate::client::{DefaultRequestBuilder, RequestBuilder, SendRequest, UploadData, UploadId};
+use crate::client::{
+    Checksum, DefaultRequestBuilder, RequestBuilder, SendRequest, UploadData, UploadId,
+};
 use crate::error::{ErrorRepr, Result, UploadContext as _};
+use crate::instrument;
 
 use aws_config::ConfigLoader;
 
@@ -58,20 +61,38 @@ impl<B: RequestBuilder> SdkClient<B> {
         self.0.upload_part()
     }
 
+    /// Create a default `UploadPartCopyRequestBuilder` to set properties on for
+    /// an `UploadPartCopy` request.
+    pub(crate) fn new_part_copy_builder(&self) -> UploadPartCopyRequestBuilder {
+        self.0.upload_part_copy()
+    }
+
     /// Create a default `CompleteRequestBuilder` to set properties on for a
     /// `CompleteMultipartUpload` request.
     pub(crate) fn new_complete_builder(&self) -> CompleteRequestBuilder {
         self.0.complete_multipart_upload()
     }
 
-    /// Create a default `CompleteRequestBuilder` to set properties on for a
-    /// `CompleteMultipartUpload` request.
+    /// Create a default `AbortRequestBuilder` to set properties on for an
+    /// `AbortMultipartUpload` request.
     pub(crate) fn new_abort_builder(&self) -> AbortRequestBuilder {
         self.0.abort_multipart_upload()
     }
+
+    /// Create a default `ListPartsRequestBuilder` to set properties on for a
+    /// `ListParts` request.
+    pub(crate) fn new_list_parts_builder(&self) -> ListPartsRequestBuilder {
+        self.0.list_parts()
+    }
+
+    /// Create a default `ListUploadsRequestBuilder` to set properties on for a
+    /// `ListMultipartUploads` request.
+    pub(crate) fn new_list_uploads_builder(&self) -> ListUploadsRequestBuilder {
+        self.0.list_multipart_uploads()
+    }
 }
 
-impl<B: RequestBuilder> SendRequest for SdkClient<B> {
+impl<B: RequestBuilder + Send + Sync> SendRequest for SdkClient<B> {
     async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
         req.validate()?;
         let base = self.new_create_builder();
@@ -79,8 +100,7 @@ impl<B: RequestBuilder> SendRequest for SdkClient<B> {
         let request = self.1.with_create_builder(builder);
 
         let uri = req.uri();
-        let id = request
-            .send()
+        let id = instrument::timed("create_upload", &uri.bucket, request.send())
             .await
             .map_err(ErrorRepr::from)
             .and_then(|resp| UploadId::try_from_create_resp(&resp))?;
@@ -88,28 +108,51 @@ impl<B: RequestBuilder> SendRequest for SdkClient<B> {
         Ok(UploadData::new(id, uri.clone()))
     }
 
-    async fn send_new_part_upload_request(
-        &self,
-        mut req: UploadPartRequest,
-    ) -> Result<CompletedPart> {
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
         req.validate()?;
-        let part_size = req.body.size();
+        let part_size = req.body.len();
 
+        let id = req.id();
+        let uri = req.uri();
+        let part = req.part_number();
+
+        let body = req.body().to_sdk_body().await.upload_ctx(id, uri, part)?;
         let base = self.new_part_builder();
-        let builder = req.with_builder(base);
+        let builder = req.with_builder(base).body(body);
         let request = self.1.with_upload_part_builder(builder);
+        let etag = instrument::timed("upload_part", &uri.bucket, request.send())
+            .await
+            .map_err(ErrorRepr::from)
+            .and_then(|resp| EntityTag::try_from_upload_resp(&resp))
+            .upload_ctx(id, uri, part)?;
+
+        let checksum = req.checksum().cloned();
+        Ok(CompletedPart::new(id.clone(), etag, part, part_size).with_checksum(checksum))
+    }
 
+    async fn send_upload_part_copy_request(
+        &self,
+        req: UploadPartCopyRequest,
+    ) -> Result<CompletedPart> {
+        req.validate()?;
         let id = req.id();
         let uri = req.uri();
         let part = req.part_number();
-        let etag = request
-            .send()
+
+        let base = self.new_part_copy_builder();
+        let builder = req.with_builder(base);
+        let request = self.1.with_upload_part_copy_builder(builder);
+        let resp = instrument::timed("upload_part_copy", &uri.bucket, request.send())
             .await
             .map_err(ErrorRepr::from)
-            .and_then(|resp| EntityTag::try_from_upload_resp(&resp))
             .upload_ctx(id, uri, part)?;
+        let etag = EntityTag::try_from_copy_resp(&resp).upload_ctx(id, uri, part)?;
+        let checksum = resp.copy_part_result().and_then(Checksum::from_copy_result);
 
-        Ok(CompletedPart::new(id.clone(), etag, part, part_size))
+        // The response does not say how many bytes were copied, so the size is
+        // only known if the request copied a range.
+        let part_size = req.source().size().unwrap_or_default() as usize;
+        Ok(CompletedPart::new(id.clone(), etag, part, part_size).with_checksum(checksum))
     }
 
     async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
@@ -121,21 +164,96 @@ impl<B: RequestBuilder> SendRequest for SdkClient<B> {
         let id = req.id();
         let uri = req.uri();
         let part = req.completed_parts.max_part_number();
-        let etag = request
-            .send()
+        let resp = instrument::timed("complete_upload", &uri.bucket, request.send())
             .await
             .map_err(ErrorRepr::from)
-            .and_then(|resp| EntityTag::try_from_complete_resp(&resp))
             .upload_ctx(id, uri, part)?;
+        let etag = EntityTag::try_from_complete_resp(&resp).upload_ctx(id, uri, part)?;
+        let checksum = Checksum::from_complete_resp(&resp);
 
-        Ok(CompletedUpload::new(uri.clone(), etag))
+        Ok(CompletedUpload::new(uri.clone(), etag).with_checksum(checksum))
     }
 
     async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
         let base = self.new_abort_builder();
         let builder = req.with_builder(base);
         let request = self.1.with_abort_builder(builder);
-        let _ = request.send().await.map_err(ErrorRepr::from)?;
+        let _ = instrument::timed("abort_upload", &req.uri.bucket, request.send())
+            .await
+            .map_err(ErrorRepr::from)?;
         Ok(())
     }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        req.validate()?;
+        let id = req.id();
+        let uri = req.uri();
+        let mut parts = CompletedParts::default();
+        let mut marker = None;
+
+        // The response has at most 1,000 parts, so follow the marker until we
+        // have all of them.
+        loop {
+            let base = self.new_list_parts_builder();
+            let builder = req.with_builder(base).set_part_number_marker(marker);
+            let request = self.1.with_list_parts_builder(builder);
+            let resp = instrument::timed("list_parts", &uri.bucket, request.send())
+                .await
+                .map_err(ErrorRepr::from)
+                .upload_ctx(id, uri, parts.max_part_number())?;
+
+            for part in resp.parts() {
+                let part = CompletedPart::try_from_part(id, part)?;
+                parts.push(part);
+            }
+
+            match resp.next_part_number_marker() {
+                Some(next) if resp.is_truncated().unwrap_or_default() => {
+                    marker = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        parts.sort_ascending();
+        Ok(parts)
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        req.validate()?;
+        let mut uploads = Vec::new();
+        let mut markers = (None, None);
+
+        // Like parts, uploads come at most 1,000 to a response, and the next
+        // page starts after the key and upload ID markers.
+        loop {
+            let base = self.new_list_uploads_builder();
+            let builder = req
+                .with_builder(base)
+                .set_key_marker(markers.0)
+                .set_upload_id_marker(markers.1);
+            let request = self.1.with_list_uploads_builder(builder);
+            let resp = instrument::timed("list_uploads", req.bucket(), request.send())
+                .await
+                .map_err(ErrorRepr::from)?;
+
+            for upload in resp.uploads() {
+                let upload = IncompleteUpload::try_from_upload(req.bucket(), upload)?;
+                uploads.push(upload);
+            }
+
+            match resp.next_key_marker() {
+                Some(next) if resp.is_truncated().unwrap_or_default() => {
+                    let next_id = resp.next_upload_id_marker().map(String::from);
+                    markers = (Some(next.to_string()), next_id);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(uploads)
+    }
 }
@@ -0,0 +1,264 @@
+use crate::client::part::PartBody;
+use crate::codec::{EncodeError, EncodeErrorKind, PartEncoder};
+
+use apache_avro::error::Details;
+use apache_avro::{Codec, Error as AvroError, Schema};
+use bytes::BufMut as _;
+use bytesize::ByteSize;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// The magic bytes at the start of an Avro object container file.
+const MAGIC: &[u8] = b"Obj\x01";
+
+/// Default size of the uncompressed data in a block.
+const DEFAULT_BLOCK_SIZE: ByteSize = ByteSize::kib(64);
+
+/// `AvroEncoder` implements `PartEncoder` by writing items as the records of
+/// an Avro object container file.
+///
+/// Items are serialized as values of the schema the encoder is created with
+/// and buffered in a data block.  The block is compressed with the codec and
+/// written to the part, followed by the sync marker of the file, when its
+/// uncompressed size reaches the block size and when the part is uploaded, so
+/// a part has one or more whole blocks.  The header of the file, which has the
+/// schema, the codec, and the sync marker, is written at the start of the
+/// first part of every upload.
+///
+/// The number of bytes returned by `encode` is the size of the blocks that
+/// were written to the part, which means the part size and upload size an
+/// upload is configured with are measured in encoded bytes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::apache_avro::{Codec, Schema};
+/// use aws_multipart_upload::codec::AvroEncoder;
+/// use aws_multipart_upload::{SdkClient, UploadBuilder};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema = Schema::parse_str(
+///     r#"{
+///         "type": "record",
+///         "name": "event",
+///         "fields": [
+///             {"name": "id", "type": "long"},
+///             {"name": "name", "type": ["null", "string"]}
+///         ]
+///     }"#,
+/// )?;
+/// let encoder = AvroEncoder::new(schema).with_codec(Codec::Snappy);
+///
+/// let client = SdkClient::defaults().await;
+/// let upload = UploadBuilder::new(client)
+///     .with_encoder(encoder)
+///     .with_uri(("my-bucket", "my/key.avro"))
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+pub struct AvroEncoder {
+    schema: Arc<Schema>,
+    codec: Codec,
+    block_size: usize,
+    marker: [u8; 16],
+    header: bool,
+    block: Vec<u8>,
+    records: usize,
+    body: PartBody,
+}
+
+impl AvroEncoder {
+    /// Create an `AvroEncoder` for records with the given schema.
+    ///
+    /// The blocks are not compressed by default.
+    pub fn new(schema: Schema) -> Self {
+        Self::with_schema(Arc::new(schema), Codec::Null, DEFAULT_BLOCK_SIZE.as_u64())
+    }
+
+    /// Set the codec to compress blocks with.
+    pub fn with_codec(self, codec: Codec) -> Self {
+        Self { codec, ..self }
+    }
+
+    /// Set the size of the uncompressed data in a block, at which the block is
+    /// written to the part.
+    ///
+    /// The default is 64 KiB.
+    pub fn with_block_size(self, block_size: ByteSize) -> Self {
+        Self {
+            block_size: block_size.as_u64() as usize,
+            ..self
+        }
+    }
+
+    /// Create an encoder for a new file with the same configuration.
+    fn with_schema(schema: Arc<Schema>, codec: Codec, block_size: u64) -> Self {
+        Self {
+            schema,
+            codec,
+            block_size: block_size as usize,
+            marker: fastrand::u128(..).to_le_bytes(),
+            header: false,
+            block: Vec::new(),
+            records: 0,
+            body: PartBody::default(),
+        }
+    }
+
+    /// Write the header of the file to the part if it has not been written.
+    fn write_header(&mut self) -> Result<usize, AvroError> {
+        if self.header {
+            return Ok(0);
+        }
+        let schema = serde_json::to_string(&*self.schema)
+            .map_err(|e| Details::SerializeValue(e.to_string()))?;
+        let codec: &'static str = self.codec.into();
+
+        let start = self.body.len();
+        self.body.put_slice(MAGIC);
+        // The metadata is a map with a single block of two entries.
+        put_long(&mut self.body, 2);
+        for (key, value) in [
+            ("avro.schema", schema.as_bytes()),
+            ("avro.codec", codec.as_bytes()),
+        ] {
+            put_bytes(&mut self.body, key.as_bytes());
+            put_bytes(&mut self.body, value);
+        }
+        put_long(&mut self.body, 0);
+        self.body.put_slice(&self.marker);
+        self.header = true;
+        Ok(self.body.len() - start)
+    }
+
+    /// Write the buffered records to the part as a block, returning the number
+    /// of bytes added to the part.
+    fn write_block(&mut self) -> Result<usize, AvroError> {
+        if self.records == 0 {
+            return Ok(0);
+        }
+        let mut bytes = self.write_header()?;
+        let mut block = std::mem::take(&mut self.block);
+        self.codec.compress(&mut block)?;
+
+        let start = self.body.len();
+        put_long(&mut self.body, self.records as i64);
+        put_bytes(&mut self.body, &block);
+        self.body.put_slice(&self.marker);
+        self.records = 0;
+        bytes += self.body.len() - start;
+        Ok(bytes)
+    }
+}
+
+impl<Item: Serialize> PartEncoder<Item> for AvroEncoder {
+    type Error = AvroError;
+
+    fn content_type(&self) -> Option<&str> {
+        Some("application/avro")
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("avro".into())
+    }
+
+    fn restore(&self) -> Result<Self, Self::Error> {
+        let block_size = self.block_size as u64;
+        Ok(Self::with_schema(
+            Arc::clone(&self.schema),
+            self.codec,
+            block_size,
+        ))
+    }
+
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        self.write_header()
+    }
+
+    fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
+        let value = apache_avro::to_value(item)?.resolve(&self.schema)?;
+        let datum = apache_avro::to_avro_datum(&self.schema, value)?;
+        self.block.extend_from_slice(&datum);
+        self.records += 1;
+        if self.block.len() < self.block_size {
+            return Ok(0);
+        }
+        self.write_block()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.write_block()?;
+        Ok(())
+    }
+
+    fn into_body(mut self) -> Result<PartBody, Self::Error> {
+        self.write_block()?;
+        Ok(self.body)
+    }
+
+    fn finish(mut self) -> Result<PartBody, Self::Error> {
+        // An upload with no records is still a valid file with a header.
+        self.write_header()?;
+        self.write_block()?;
+        Ok(self.body)
+    }
+
+    fn clear(&self) -> Result<Self, Self::Error> {
+        // The next part continues the same file, so it has the same marker and
+        // the header is not repeated.
+        Ok(Self {
+            marker: self.marker,
+            header: self.header,
+            ..Self::with_schema(Arc::clone(&self.schema), self.codec, self.block_size as u64)
+        })
+    }
+}
+
+impl Debug for AvroEncoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AvroEncoder")
+            .field("schema", &self.schema)
+            .field("codec", &self.codec)
+            .field("block_size", &self.block_size)
+            .field("header", &self.header)
+            .field("records", &self.records)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+impl EncodeError for AvroError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn kind(&self) -> EncodeErrorKind {
+        match self.details() {
+            Details::Validation
+            | Details::ValidationWithReason { .. }
+            | Details::SerializeValue(_)
+            | Details::SerializeValueWithSchema { .. } => EncodeErrorKind::Data,
+            Details::SnappyCompress(_) | Details::WriteBytes(_) => EncodeErrorKind::Io,
+            _ => EncodeErrorKind::Unknown,
+        }
+    }
+}
+
+/// Write `n` as an Avro long, which is a variable-length zig-zag integer.
+fn put_long(body: &mut PartBody, n: i64) {
+    let mut z = ((n << 1) ^ (n >> 63)) as u64;
+    while z >= 0x80 {
+        body.put_u8((z as u8) | 0x80);
+        z >>= 7;
+    }
+    body.put_u8(z as u8);
+}
+
+/// Write `bytes` as Avro bytes, which are prefixed by their length.
+fn put_bytes(body: &mut PartBody, bytes: &[u8]) {
+    put_long(body, bytes.len() as i64);
+    body.put_slice(bytes);
+}
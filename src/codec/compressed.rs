@@ -0,0 +1,413 @@
+use crate::client::part::PartBody;
+use crate::codec::{EncodeError, EncodeErrorKind, PartEncoder};
+
+use bytesize::ByteSize;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Default number of bytes from the inner encoder to compress at once.
+const DEFAULT_CHUNK_SIZE: ByteSize = ByteSize::kib(256);
+
+/// A compression format for the output of a [`Compressed`] encoder.
+pub trait Compression {
+    /// The writer that compresses bytes into a `PartBody`.
+    type Writer: Write;
+
+    /// Create a writer for a new compressed stream.
+    fn new_writer(&self, sink: PartBody) -> io::Result<Self::Writer>;
+
+    /// Returns a mutable reference to the compressed bytes written so far.
+    fn get_mut(writer: &mut Self::Writer) -> &mut PartBody;
+
+    /// Write the end of the stream, returning the remaining compressed bytes.
+    fn finish(writer: Self::Writer) -> io::Result<PartBody>;
+
+    /// The media type of the compressed stream.
+    ///
+    /// The default is `None`.
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// The file extension of the compressed stream, without the leading dot.
+    ///
+    /// The default is `None`.
+    fn extension(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// `Compressed` is a `PartEncoder` that compresses the output of another one.
+///
+/// The inner encoder's output is compressed as one stream across all parts of
+/// the upload: each part ends at a flush point of the stream and the last part
+/// has the end of the stream, so the parts put together are a valid compressed
+/// object.  The size returned by `encode` is in compressed bytes, which means
+/// the part size and upload size an upload is configured with are measured in
+/// what is actually uploaded.
+///
+/// The inner encoder's output is compressed in chunks of a configurable size,
+/// so the size of a part can exceed the target part size by up to roughly the
+/// size of one compressed chunk.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "gzip")]
+/// # {
+/// use aws_multipart_upload::{SdkClient, UploadBuilder};
+/// use aws_multipart_upload::codec::{Compressed, Gzip, JsonLinesEncoder};
+///
+/// # async fn example() -> aws_multipart_upload::error::Result<()> {
+/// let client = SdkClient::defaults().await;
+/// let encoder = Compressed::new(JsonLinesEncoder::new(), Gzip::default());
+/// let upload = UploadBuilder::new(client)
+///     .with_encoder(encoder)
+///     .with_uri(("my-bucket", "my/key.jsonl.gz"))
+///     .build();
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+pub struct Compressed<E, C: Compression> {
+    inner: E,
+    codec: C,
+    stream: Arc<Mutex<Option<C::Writer>>>,
+    body: PartBody,
+    pending: u64,
+    written: bool,
+    unreported: usize,
+    chunk_size: u64,
+}
+
+impl<E, C: Compression> Compressed<E, C> {
+    /// Compress the output of `inner` with `codec`.
+    pub fn new(inner: E, codec: C) -> Self {
+        Self {
+            inner,
+            codec,
+            stream: Arc::default(),
+            body: PartBody::default(),
+            pending: 0,
+            written: false,
+            unreported: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE.as_u64(),
+        }
+    }
+
+    /// Set the number of bytes the inner encoder writes before they are
+    /// compressed.
+    ///
+    /// The default is 256 KiB.
+    pub fn with_chunk_size(self, chunk_size: ByteSize) -> Self {
+        Self {
+            chunk_size: chunk_size.as_u64(),
+            ..self
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<C::Writer>> {
+        self.stream.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Move the compressed bytes written so far to the body of this part.
+    fn drain(&mut self, writer: &mut C::Writer) -> usize {
+        let compressed = C::get_mut(writer).split();
+        let bytes = compressed.len();
+        self.body.unsplit(compressed);
+        bytes
+    }
+}
+
+impl<E, C> Compressed<E, C>
+where
+    C: Compression + Clone,
+{
+    /// Compress everything the inner encoder has written, returning the number
+    /// of compressed bytes added to the part.
+    fn compress<Item>(&mut self) -> Result<usize, CompressedError<<E as PartEncoder<Item>>::Error>>
+    where
+        E: PartEncoder<Item>,
+    {
+        self.inner.flush().map_err(CompressedError::Encode)?;
+        let next = self.inner.clear().map_err(CompressedError::Encode)?;
+        let prev = std::mem::replace(&mut self.inner, next);
+        let uncompressed = prev.into_body().map_err(CompressedError::Encode)?;
+        Ok(self.write_stream(&uncompressed)?)
+    }
+
+    /// Compress the bytes `uncompressed` from the inner encoder, returning the
+    /// number of compressed bytes added to the part.
+    fn write_stream(&mut self, uncompressed: &[u8]) -> io::Result<usize> {
+        self.pending = 0;
+        if uncompressed.is_empty() {
+            return Ok(0);
+        }
+
+        let stream = Arc::clone(&self.stream);
+        let mut guard = stream.lock().unwrap_or_else(PoisonError::into_inner);
+        let writer = match guard.as_mut() {
+            Some(writer) => writer,
+            None => guard.insert(self.codec.new_writer(PartBody::default())?),
+        };
+        writer.write_all(uncompressed)?;
+        self.written = true;
+        Ok(self.drain(writer))
+    }
+
+    /// Compress everything written and flush the stream so that the body of
+    /// this part ends on a boundary the decoder can read up to, returning the
+    /// number of compressed bytes added to the part.
+    fn sync<Item>(&mut self) -> Result<usize, CompressedError<<E as PartEncoder<Item>>::Error>>
+    where
+        E: PartEncoder<Item>,
+    {
+        let mut bytes = self.compress::<Item>()?;
+        if !self.written {
+            return Ok(bytes);
+        }
+
+        let stream = Arc::clone(&self.stream);
+        let mut guard = stream.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(writer) = guard.as_mut() {
+            writer.flush()?;
+            bytes += self.drain(writer);
+        }
+        self.written = false;
+        Ok(bytes)
+    }
+}
+
+impl<Item, E, C> PartEncoder<Item> for Compressed<E, C>
+where
+    E: PartEncoder<Item>,
+    C: Compression + Clone,
+{
+    type Error = CompressedError<E::Error>;
+
+    fn content_type(&self) -> Option<&str> {
+        self.codec.content_type()
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        match (self.inner.extension(), self.codec.extension()) {
+            (Some(inner), Some(ext)) => Some(format!("{inner}.{ext}").into()),
+            (inner, ext) => inner.or_else(|| ext.map(Cow::from)),
+        }
+    }
+
+    fn restore(&self) -> Result<Self, Self::Error> {
+        let inner = self.inner.restore().map_err(CompressedError::Encode)?;
+        Ok(Self {
+            inner,
+            codec: self.codec.clone(),
+            stream: Arc::default(),
+            body: PartBody::default(),
+            pending: 0,
+            written: false,
+            unreported: 0,
+            chunk_size: self.chunk_size,
+        })
+    }
+
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        let bytes = self.inner.begin().map_err(CompressedError::Encode)?;
+        self.pending += bytes as u64;
+        Ok(0)
+    }
+
+    fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
+        let bytes = self.inner.encode(item).map_err(CompressedError::Encode)?;
+        self.pending += bytes as u64;
+        // Bytes added to the part by a flush are reported with the next item.
+        let unreported = std::mem::take(&mut self.unreported);
+        if self.pending < self.chunk_size {
+            return Ok(unreported);
+        }
+        Ok(unreported + self.compress::<Item>()?)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.unreported += self.sync::<Item>()?;
+        Ok(())
+    }
+
+    fn into_body(mut self) -> Result<PartBody, Self::Error> {
+        self.sync::<Item>()?;
+        Ok(self.body)
+    }
+
+    fn finish(mut self) -> Result<PartBody, Self::Error> {
+        // The inner encoder finishes the object, which may write a trailer.
+        self.inner.flush().map_err(CompressedError::Encode)?;
+        let next = self.inner.clear().map_err(CompressedError::Encode)?;
+        let prev = std::mem::replace(&mut self.inner, next);
+        let uncompressed = prev.finish().map_err(CompressedError::Encode)?;
+        self.write_stream(&uncompressed)?;
+        // An object nothing was written to is still a valid, empty stream.
+        let writer = self.lock().take();
+        let writer = match writer {
+            Some(writer) => writer,
+            None => self.codec.new_writer(PartBody::default())?,
+        };
+        let rest = C::finish(writer)?;
+        self.body.extend_from_slice(&rest);
+        Ok(self.body)
+    }
+
+    fn clear(&self) -> Result<Self, Self::Error> {
+        let inner = self.inner.clear().map_err(CompressedError::Encode)?;
+        Ok(Self {
+            inner,
+            codec: self.codec.clone(),
+            stream: Arc::clone(&self.stream),
+            body: PartBody::default(),
+            pending: 0,
+            written: false,
+            unreported: 0,
+            chunk_size: self.chunk_size,
+        })
+    }
+}
+
+impl<E: Debug, C: Compression + Debug> Debug for Compressed<E, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressed")
+            .field("inner", &self.inner)
+            .field("codec", &self.codec)
+            .field("body", &self.body)
+            .field("pending", &self.pending)
+            .field("written", &self.written)
+            .field("unreported", &self.unreported)
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+/// Error from the `Compressed` encoder.
+#[derive(Debug, thiserror::Error)]
+pub enum CompressedError<Er> {
+    /// The inner encoder returned an error.
+    #[error(transparent)]
+    Encode(Er),
+    /// The output of the inner encoder could not be compressed.
+    #[error("compression error: {0}")]
+    Compress(#[from] io::Error),
+}
+
+impl<Er: EncodeError> EncodeError for CompressedError<Er> {
+    fn message(&self) -> String {
+        match self {
+            Self::Encode(e) => e.message(),
+            Self::Compress(e) => e.to_string(),
+        }
+    }
+
+    fn kind(&self) -> EncodeErrorKind {
+        match self {
+            Self::Encode(e) => e.kind(),
+            Self::Compress(_) => EncodeErrorKind::Io,
+        }
+    }
+}
+
+/// The gzip compression format.
+#[cfg(feature = "gzip")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Gzip {
+    level: u32,
+}
+
+#[cfg(feature = "gzip")]
+impl Gzip {
+    /// Create a `Gzip` with compression level `level`, from 0 to 9.
+    pub fn new(level: u32) -> Self {
+        Self { level }
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Default for Gzip {
+    fn default() -> Self {
+        Self::new(flate2::Compression::default().level())
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Compression for Gzip {
+    type Writer = flate2::write::GzEncoder<PartBody>;
+
+    fn new_writer(&self, sink: PartBody) -> io::Result<Self::Writer> {
+        let level = flate2::Compression::new(self.level);
+        Ok(flate2::write::GzEncoder::new(sink, level))
+    }
+
+    fn get_mut(writer: &mut Self::Writer) -> &mut PartBody {
+        writer.get_mut()
+    }
+
+    fn finish(writer: Self::Writer) -> io::Result<PartBody> {
+        writer.finish()
+    }
+
+    fn content_type(&self) -> Option<&str> {
+        Some("application/gzip")
+    }
+
+    fn extension(&self) -> Option<&str> {
+        Some("gz")
+    }
+}
+
+/// The zstd compression format.
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Zstd {
+    level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl Zstd {
+    /// Create a `Zstd` with compression level `level`.
+    ///
+    /// The level `0` means the default level of the zstd library.
+    pub fn new(level: i32) -> Self {
+        Self { level }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Default for Zstd {
+    fn default() -> Self {
+        Self::new(zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Compression for Zstd {
+    type Writer = zstd::stream::write::Encoder<'static, PartBody>;
+
+    fn new_writer(&self, sink: PartBody) -> io::Result<Self::Writer> {
+        zstd::stream::write::Encoder::new(sink, self.level)
+    }
+
+    fn get_mut(writer: &mut Self::Writer) -> &mut PartBody {
+        writer.get_mut()
+    }
+
+    fn finish(writer: Self::Writer) -> io::Result<PartBody> {
+        writer.finish()
+    }
+
+    fn content_type(&self) -> Option<&str> {
+        Some("application/zstd")
+    }
+
+    fn extension(&self) -> Option<&str> {
+        Some("zst")
+    }
+}
@@ -5,6 +5,7 @@ use crate::codec::{EncodeError, EncodeErrorKind, PartEncoder};
 use bytesize::ByteSize;
 use csv::{Error as CsvError, Writer, WriterBuilder};
 use serde::Serialize;
+use std::borrow::Cow;
 
 /// `CsvEncoder` implements `PartEncoder` by writing items to the part in CSV
 /// format.
@@ -31,6 +32,17 @@ impl CsvEncoder {
             ..self
         }
     }
+
+    /// Create a CSV writer for a new part, which writes a header row from the
+    /// next item if `has_headers` is set.
+    fn new_writer(&self, has_headers: bool) -> Writer<PartBody> {
+        let cap = self.writer.get_ref().capacity();
+        let part = PartBody::with_capacity(cap);
+        WriterBuilder::new()
+            .buffer_capacity(self.capacity as usize)
+            .has_headers(has_headers)
+            .from_writer(part)
+    }
 }
 
 impl Default for CsvEncoder {
@@ -54,28 +66,30 @@ impl Default for CsvEncoder {
 impl<Item: Serialize> PartEncoder<Item> for CsvEncoder {
     type Error = CsvError;
 
-    fn restore(&self) -> Result<Self, Self::Error> {
-        let cap = self.writer.get_ref().capacity();
-        let part = PartBody::with_capacity(cap);
-        let mut builder = WriterBuilder::new();
-        let writer = if self.write_header {
-            builder
-                .buffer_capacity(self.capacity as usize)
-                .from_writer(part)
-        } else {
-            builder
-                .buffer_capacity(self.capacity as usize)
-                .has_headers(false)
-                .from_writer(part)
-        };
+    fn content_type(&self) -> Option<&str> {
+        Some("text/csv")
+    }
 
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("csv".into())
+    }
+
+    fn restore(&self) -> Result<Self, Self::Error> {
         Ok(Self {
-            writer,
+            writer: self.new_writer(false),
             write_header: self.write_header,
             capacity: self.capacity,
         })
     }
 
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        // The header row comes from the first item, so it is written with it.
+        if self.write_header {
+            self.writer = self.new_writer(true);
+        }
+        Ok(0)
+    }
+
     fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
         let before = self.writer.get_ref().size();
         self.writer.serialize(item)?;
@@ -97,16 +111,8 @@ impl<Item: Serialize> PartEncoder<Item> for CsvEncoder {
     }
 
     fn clear(&self) -> Result<Self, Self::Error> {
-        let cap = self.writer.get_ref().capacity();
-        let part = PartBody::with_capacity(cap);
-        let mut builder = WriterBuilder::new();
-        let writer = builder
-            .buffer_capacity(self.capacity as usize)
-            .has_headers(false)
-            .from_writer(part);
-
         Ok(Self {
-            writer,
+            writer: self.new_writer(false),
             write_header: self.write_header,
             capacity: self.capacity,
         })
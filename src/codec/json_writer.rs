@@ -4,6 +4,7 @@ use crate::codec::PartEncoder;
 
 use bytes::BufMut as _;
 use serde::Serialize;
+use std::borrow::Cow;
 use std::ops::DerefMut;
 
 /// `JsonLinesEncoder` implements `PartEncoder` by writing lines of JSON to the
@@ -31,6 +32,14 @@ impl Default for JsonLinesEncoder {
 impl<Item: Serialize> PartEncoder<Item> for JsonLinesEncoder {
     type Error = serde_json::Error;
 
+    fn content_type(&self) -> Option<&str> {
+        Some("application/x-ndjson")
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("jsonl".into())
+    }
+
     fn restore(&self) -> Result<Self, Self::Error> {
         let capacity = self.writer.capacity();
         Ok(Self {
@@ -55,3 +64,127 @@ impl<Item: Serialize> PartEncoder<Item> for JsonLinesEncoder {
         Ok(self.writer)
     }
 }
+
+/// `JsonArrayEncoder` implements `PartEncoder` by writing the items of an
+/// upload as the elements of one JSON array.
+///
+/// The opening bracket is written at the start of every upload and the closing
+/// bracket in its last part, with a comma between consecutive items whether or
+/// not they are in the same part, so the object is a single JSON document.
+#[derive(Debug, Clone)]
+pub struct JsonArrayEncoder {
+    writer: PartBody,
+    pretty: bool,
+    open: bool,
+    empty: bool,
+}
+
+impl JsonArrayEncoder {
+    /// Create a `JsonArrayEncoder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write each element pretty-printed on its own lines.
+    pub fn pretty(self) -> Self {
+        Self {
+            pretty: true,
+            ..self
+        }
+    }
+
+    /// Write the opening bracket if it has not been written.
+    fn open(&mut self) -> usize {
+        if self.open {
+            return 0;
+        }
+        self.open = true;
+        self.writer.deref_mut().put_u8(b'[');
+        1
+    }
+}
+
+impl Default for JsonArrayEncoder {
+    fn default() -> Self {
+        Self {
+            writer: PartBody::with_capacity(AWS_MIN_PART_SIZE.as_u64() as usize),
+            pretty: false,
+            open: false,
+            empty: true,
+        }
+    }
+}
+
+impl<Item: Serialize> PartEncoder<Item> for JsonArrayEncoder {
+    type Error = serde_json::Error;
+
+    fn content_type(&self) -> Option<&str> {
+        Some("application/json")
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("json".into())
+    }
+
+    fn restore(&self) -> Result<Self, Self::Error> {
+        let capacity = self.writer.capacity();
+        Ok(Self {
+            writer: PartBody::with_capacity(capacity),
+            pretty: self.pretty,
+            open: false,
+            empty: true,
+        })
+    }
+
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        Ok(self.open())
+    }
+
+    fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
+        let it = if self.pretty {
+            // A newline can only be between tokens, so this indents the
+            // element as a whole.
+            let it = serde_json::to_string_pretty(&item)?;
+            format!("\n  {}", it.replace('\n', "\n  ")).into_bytes()
+        } else {
+            serde_json::to_vec(&item)?
+        };
+        let mut bytes = self.open() + it.len();
+        self.writer.deref_mut().reserve(bytes + 1);
+        if !self.empty {
+            self.writer.deref_mut().put_u8(b',');
+            bytes += 1;
+        }
+        self.writer.deref_mut().put(it.as_ref());
+        self.empty = false;
+        Ok(bytes)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn into_body(self) -> Result<PartBody, Self::Error> {
+        Ok(self.writer)
+    }
+
+    fn finish(mut self) -> Result<PartBody, Self::Error> {
+        self.open();
+        if self.pretty && !self.empty {
+            self.writer.deref_mut().put_u8(b'\n');
+        }
+        self.writer.deref_mut().put_u8(b']');
+        Ok(self.writer)
+    }
+
+    fn clear(&self) -> Result<Self, Self::Error> {
+        // The next part continues the same array.
+        let capacity = self.writer.capacity();
+        Ok(Self {
+            writer: PartBody::with_capacity(capacity),
+            pretty: self.pretty,
+            open: self.open,
+            empty: self.empty,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use crate::client::part::PartBody;
 use crate::codec::PartEncoder;
 
 use bytes::BufMut as _;
+use std::borrow::Cow;
 use std::convert::Infallible;
 use std::ops::DerefMut;
 
@@ -15,7 +16,7 @@ pub struct LinesEncoder {
 }
 
 impl LinesEncoder {
-    /// Set the header to write as the first row of the first part.
+    /// Set the header to write as the first line of every upload.
     pub fn with_header<T: Into<String>>(self, header: T) -> Self {
         Self {
             header: Some(header.into()),
@@ -36,18 +37,33 @@ impl Default for LinesEncoder {
 impl<Item: AsRef<str>> PartEncoder<Item> for LinesEncoder {
     type Error = Infallible;
 
+    fn content_type(&self) -> Option<&str> {
+        Some("text/plain")
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("txt".into())
+    }
+
     fn restore(&self) -> Result<Self, Self::Error> {
         let capacity = self.writer.capacity();
-        let mut writer = PartBody::with_capacity(capacity);
-        if let Some(h) = self.header.as_deref() {
-            writer.put(h.as_bytes());
-        }
         Ok(Self {
-            writer,
+            writer: PartBody::with_capacity(capacity),
             header: self.header.clone(),
         })
     }
 
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        let Some(header) = self.header.as_deref() else {
+            return Ok(0);
+        };
+        let bytes = header.len();
+        self.writer.deref_mut().reserve(bytes + 1);
+        self.writer.deref_mut().put(header.as_bytes());
+        self.writer.deref_mut().put_u8(b'\n');
+        Ok(bytes + 1)
+    }
+
     fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
         let item = item.as_ref();
         let bytes = item.len();
@@ -6,6 +6,28 @@
 use crate::client::part::PartBody;
 
 use bytes::BufMut;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+#[cfg(feature = "avro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "avro")))]
+mod avro_writer;
+#[cfg(feature = "avro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "avro")))]
+pub use avro_writer::AvroEncoder;
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "gzip", feature = "zstd"))))]
+mod compressed;
+#[cfg(feature = "gzip")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+pub use compressed::Gzip;
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+pub use compressed::Zstd;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "gzip", feature = "zstd"))))]
+pub use compressed::{Compressed, CompressedError, Compression};
 
 #[cfg(feature = "csv")]
 #[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
@@ -18,21 +40,57 @@ mod error;
 pub use error::{EncodeError, EncodeErrorKind};
 
 mod json_writer;
-pub use json_writer::JsonLinesEncoder;
+pub use json_writer::{JsonArrayEncoder, JsonLinesEncoder};
 
 mod lines_writer;
 pub use lines_writer::LinesEncoder;
 
+#[cfg(feature = "parquet")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
+mod parquet_writer;
+#[cfg(feature = "parquet")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
+pub use parquet_writer::ParquetEncoder;
+
 /// Encoding for items in a part of a multipart upload.
 pub trait PartEncoder<Item> {
     /// The type of value returned when encoding items is not successful.
     type Error: EncodeError;
 
+    /// The media type of the objects this encoder writes.
+    ///
+    /// This is the `Content-Type` of the uploaded objects unless their
+    /// attributes have a different one.  The default is `None`, which leaves
+    /// it to S3.
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// The file extension of the objects this encoder writes, without the
+    /// leading dot.
+    ///
+    /// This is what the `{ext}` placeholder of a [`KeyTemplate`] is replaced
+    /// with.  The default is `None`.
+    ///
+    /// [`KeyTemplate`]: crate::uri::KeyTemplate
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
     /// Restore this encoder's state for a new upload.
     fn restore(&self) -> Result<Self, Self::Error>
     where
         Self: Sized;
 
+    /// Write the start of a new object, returning the number of bytes written.
+    ///
+    /// This is called before the first item of every upload is encoded.
+    /// Override this method if the format has a header that has to be written
+    /// at the beginning of the object.  The default writes nothing.
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+
     /// Encode this item in the part, returning the number of bytes written.
     fn encode(&mut self, item: Item) -> Result<usize, Self::Error>;
 
@@ -42,6 +100,17 @@ pub trait PartEncoder<Item> {
     /// Convert the encoder to a `PartBody`.
     fn into_body(self) -> Result<PartBody, Self::Error>;
 
+    /// Convert the encoder to the `PartBody` of the last part in the upload.
+    ///
+    /// Override this method if the format has a trailer that has to be written
+    /// at the end of the object.  The default is the same as `into_body`.
+    fn finish(self) -> Result<PartBody, Self::Error>
+    where
+        Self: Sized,
+    {
+        self.into_body()
+    }
+
     /// Clear the encoder to prepare for a new part.
     ///
     /// Override this method to provide an alternative means of building the
@@ -79,3 +148,34 @@ impl<T: AsRef<[u8]>> PartEncoder<T> for PartBody {
         Ok(self)
     }
 }
+
+/// What the encoder of an upload tells about the objects it writes, which is
+/// known once the encoder has begun an object.
+#[derive(Debug, Default)]
+pub(crate) struct EncoderInfo {
+    content_type: OnceLock<String>,
+    extension: OnceLock<String>,
+}
+
+impl EncoderInfo {
+    /// Record what `encoder` tells, if nothing was recorded yet.
+    pub(crate) fn record<Item, E: PartEncoder<Item>>(&self, encoder: &E) {
+        if let Some(content_type) = encoder.content_type() {
+            self.content_type.get_or_init(|| content_type.to_string());
+        }
+        if let Some(extension) = encoder.extension() {
+            self.extension.get_or_init(|| extension.into_owned());
+        }
+    }
+
+    /// The content type of the objects, for those without one in their
+    /// attributes.
+    pub(crate) fn content_type(&self) -> Option<&str> {
+        self.content_type.get().map(String::as_str)
+    }
+
+    /// The file extension of the objects.
+    pub(crate) fn extension(&self) -> Option<&str> {
+        self.extension.get().map(String::as_str)
+    }
+}
@@ -0,0 +1,236 @@
+use crate::AWS_MIN_PART_SIZE;
+use crate::client::part::PartBody;
+use crate::codec::{EncodeError, EncodeErrorKind, PartEncoder};
+
+use arrow_json::reader::{Decoder, ReaderBuilder};
+use arrow_schema::SchemaRef;
+use bytesize::ByteSize;
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Default number of rows to buffer before writing them to the row group.
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// `ParquetEncoder` implements `PartEncoder` by writing items as the rows of a
+/// Parquet file.
+///
+/// Items are converted to rows of the Arrow schema the encoder is created with,
+/// buffered, and written in batches to the row group in progress.  The row
+/// group is written to the part when its estimated size reaches the row group
+/// size and when the part is uploaded, so a part has one or more whole row
+/// groups.  The Parquet footer is written to the last part of the upload.
+///
+/// The number of bytes returned by `encode` is the size of the row groups that
+/// were written to the part, which means the part size and upload size an
+/// upload is configured with are measured in encoded bytes.  For that reason
+/// the row group size should be smaller than the part size.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::arrow_schema::{DataType, Field, Schema};
+/// use aws_multipart_upload::codec::ParquetEncoder;
+/// use aws_multipart_upload::{SdkClient, UploadBuilder};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let schema = Schema::new(vec![
+///     Field::new("id", DataType::Int64, false),
+///     Field::new("name", DataType::Utf8, true),
+/// ]);
+/// let encoder = ParquetEncoder::try_new(Arc::new(schema))?;
+///
+/// let client = SdkClient::defaults().await;
+/// let upload = UploadBuilder::new(client)
+///     .with_encoder(encoder)
+///     .with_uri(("my-bucket", "my/key.parquet"))
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+pub struct ParquetEncoder {
+    schema: SchemaRef,
+    props: WriterProperties,
+    rows: Decoder,
+    writer: Arc<Mutex<Option<ArrowWriter<PartBody>>>>,
+    body: PartBody,
+    batch_size: usize,
+    row_group_size: usize,
+}
+
+impl ParquetEncoder {
+    /// Create a `ParquetEncoder` for rows with the given schema.
+    ///
+    /// This fails if the schema has a type that rows cannot be converted to.
+    pub fn try_new(schema: SchemaRef) -> Result<Self, ParquetError> {
+        let rows = ReaderBuilder::new(Arc::clone(&schema)).build_decoder()?;
+        Ok(Self {
+            schema,
+            props: WriterProperties::default(),
+            rows,
+            writer: Arc::default(),
+            body: PartBody::default(),
+            batch_size: DEFAULT_BATCH_SIZE,
+            row_group_size: AWS_MIN_PART_SIZE.as_u64() as usize,
+        })
+    }
+
+    /// Set the properties of the Parquet writer, e.g., the compression codec.
+    pub fn with_properties(self, props: WriterProperties) -> Self {
+        Self { props, ..self }
+    }
+
+    /// Set the number of rows to buffer before they are written to the row
+    /// group.
+    ///
+    /// The default is 1024 rows.
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Set the target size of a row group.
+    ///
+    /// The default is 5 MiB, the minimum part size.
+    pub fn with_row_group_size(self, row_group_size: ByteSize) -> Self {
+        Self {
+            row_group_size: row_group_size.as_u64() as usize,
+            ..self
+        }
+    }
+
+    /// Create an encoder with the same configuration writing to `writer`.
+    fn with_writer(
+        &self,
+        writer: Arc<Mutex<Option<ArrowWriter<PartBody>>>>,
+    ) -> Result<Self, ParquetError> {
+        let rows = ReaderBuilder::new(Arc::clone(&self.schema)).build_decoder()?;
+        Ok(Self {
+            schema: Arc::clone(&self.schema),
+            props: self.props.clone(),
+            rows,
+            writer,
+            body: PartBody::default(),
+            batch_size: self.batch_size,
+            row_group_size: self.row_group_size,
+        })
+    }
+
+    /// Write the buffered rows to the row group in progress, returning the
+    /// number of bytes added to the part.
+    ///
+    /// The row group is closed if it has reached the target size or if `close`
+    /// is set.
+    fn write_rows(&mut self, close: bool) -> Result<usize, ParquetError> {
+        let batch = self.rows.flush()?;
+        let writer = Arc::clone(&self.writer);
+        let mut guard = writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let writer = match (guard.as_mut(), batch.is_some()) {
+            (Some(writer), _) => writer,
+            (None, true) => guard.insert(ArrowWriter::try_new(
+                PartBody::default(),
+                Arc::clone(&self.schema),
+                Some(self.props.clone()),
+            )?),
+            (None, false) => return Ok(0),
+        };
+
+        if let Some(batch) = batch {
+            writer.write(&batch)?;
+        }
+        if close || writer.in_progress_size() >= self.row_group_size {
+            writer.flush()?;
+        }
+        writer.sync()?;
+        let written = writer.inner_mut().split();
+        let bytes = written.len();
+        self.body.unsplit(written);
+        Ok(bytes)
+    }
+}
+
+impl<Item: Serialize> PartEncoder<Item> for ParquetEncoder {
+    type Error = ParquetError;
+
+    fn content_type(&self) -> Option<&str> {
+        Some("application/vnd.apache.parquet")
+    }
+
+    fn extension(&self) -> Option<Cow<'_, str>> {
+        Some("parquet".into())
+    }
+
+    fn restore(&self) -> Result<Self, Self::Error> {
+        self.with_writer(Arc::default())
+    }
+
+    fn encode(&mut self, item: Item) -> Result<usize, Self::Error> {
+        self.rows.serialize(&[item])?;
+        if self.rows.len() < self.batch_size {
+            return Ok(0);
+        }
+        self.write_rows(false)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.write_rows(true)?;
+        Ok(())
+    }
+
+    fn into_body(mut self) -> Result<PartBody, Self::Error> {
+        self.write_rows(true)?;
+        Ok(self.body)
+    }
+
+    fn finish(mut self) -> Result<PartBody, Self::Error> {
+        self.write_rows(false)?;
+        let writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(mut writer) = writer {
+            writer.finish()?;
+            self.body.unsplit(writer.inner_mut().split());
+        }
+        Ok(self.body)
+    }
+
+    fn clear(&self) -> Result<Self, Self::Error> {
+        self.with_writer(Arc::clone(&self.writer))
+    }
+}
+
+impl Debug for ParquetEncoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetEncoder")
+            .field("schema", &self.schema)
+            .field("props", &self.props)
+            .field("rows", &self.rows.len())
+            .field("body", &self.body)
+            .field("batch_size", &self.batch_size)
+            .field("row_group_size", &self.row_group_size)
+            .finish()
+    }
+}
+
+impl EncodeError for ParquetError {
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn kind(&self) -> EncodeErrorKind {
+        match self {
+            ParquetError::EOF(_) | ParquetError::NeedMoreData(_) => EncodeErrorKind::Eof,
+            ParquetError::ArrowError(_) => EncodeErrorKind::Data,
+            _ => EncodeErrorKind::Unknown,
+        }
+    }
+}
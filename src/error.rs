@@ -1,13 +1,17 @@
 //! Types for working with errors.
-use crate::client::UploadId;
 use crate::client::part::PartNumber;
+use crate::client::{UploadData, UploadId};
 use crate::codec::{EncodeError, EncodeErrorKind};
 use crate::uri::ObjectUri;
 
-use aws_sdk::error::SdkError;
+use aws_sdk::error::{ProvideErrorMetadata, SdkError};
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter};
 
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use crate::client::InMemoryError;
+
 /// A specialized `Result` type for errors originating in this crate.
 pub type Result<T, E = Error> = ::std::result::Result<T, E>;
 
@@ -18,19 +22,49 @@ pub struct Error(pub(crate) ErrorRepr);
 impl Error {
     /// Returns the details of the upload that failed if available.
     pub fn failed_upload(&self) -> Option<&FailedUpload> {
-        if let ErrorRepr::UploadFailed { failed, .. } = &self.0 {
-            return Some(failed);
+        match &self.0 {
+            ErrorRepr::UploadFailed { failed, .. }
+            | ErrorRepr::Aborted { failed, .. }
+            | ErrorRepr::AbortFailed { failed, .. } => Some(failed),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the upload was aborted because of this error.
+    ///
+    /// This is `None` if aborting the upload was not attempted, and otherwise
+    /// is whether the request to abort it succeeded.
+    pub fn aborted(&self) -> Option<bool> {
+        match &self.0 {
+            ErrorRepr::Aborted { .. } => Some(true),
+            ErrorRepr::AbortFailed { .. } => Some(false),
+            _ => None,
         }
-        None
+    }
+
+    /// Returns whether the error is likely to be transient, so that the request
+    /// that failed with it could succeed if sent again.
+    ///
+    /// This is the case for timeouts, dropped connections, and AWS responding
+    /// that it is throttling requests or is temporarily unavailable.
+    pub fn is_retryable(&self) -> bool {
+        self.0.is_retryable()
     }
 
     /// Returns the category under which this error falls.
     pub fn kind(&self) -> ErrorKind {
         match self.0 {
-            ErrorRepr::Sdk(_) => ErrorKind::Sdk,
-            ErrorRepr::Missing(_, _) => ErrorKind::Config,
+            ErrorRepr::Sdk { .. } => ErrorKind::Sdk,
+            ErrorRepr::Missing(_, _) | ErrorRepr::Unsupported(_) | ErrorRepr::Invalid(_) => {
+                ErrorKind::Config
+            }
             ErrorRepr::Encoding(_, _) => ErrorKind::Encoding,
-            ErrorRepr::UploadFailed { .. } => ErrorKind::Upload,
+            ErrorRepr::UploadFailed { .. }
+            | ErrorRepr::Aborted { .. }
+            | ErrorRepr::AbortFailed { .. }
+            | ErrorRepr::TooManyParts => ErrorKind::Upload,
+            ErrorRepr::Read(_) => ErrorKind::Read,
+            ErrorRepr::Disk(_) => ErrorKind::Disk,
             ErrorRepr::DynStd(_) => ErrorKind::Unknown,
             ErrorRepr::Other { kind, .. } => kind,
         }
@@ -39,7 +73,7 @@ impl Error {
     /// Convert an arbitrary [`std::error::Error`] to this error type.
     pub fn from_dyn_std<E>(e: E) -> Self
     where
-        E: StdError + 'static,
+        E: StdError + Send + Sync + 'static,
     {
         let err = Box::new(e);
         Self(ErrorRepr::DynStd(err))
@@ -81,6 +115,10 @@ pub enum ErrorKind {
     Sdk,
     /// There was an error operating the upload.
     Upload,
+    /// There was an error reading the source of an upload, such as a file.
+    Read,
+    /// There was an error staging a part in a file on local disk.
+    Disk,
     /// The origin of the error is not known.
     Unknown,
 }
@@ -90,6 +128,8 @@ impl Display for ErrorKind {
         match self {
             Self::Config => write!(f, "config"),
             Self::Encoding => write!(f, "encoding"),
+            Self::Read => write!(f, "read"),
+            Self::Disk => write!(f, "disk"),
             Self::Sdk => write!(f, "sdk"),
             Self::Upload => write!(f, "upload"),
             Self::Unknown => write!(f, "unknown"),
@@ -133,6 +173,18 @@ impl Display for FailedUpload {
     }
 }
 
+impl From<FailedUpload> for UploadData {
+    fn from(value: FailedUpload) -> Self {
+        Self::new(value.id, value.uri)
+    }
+}
+
+impl From<&FailedUpload> for UploadData {
+    fn from(value: &FailedUpload) -> Self {
+        Self::new(value.id.clone(), value.uri.clone())
+    }
+}
+
 /// Appending upload data to the error if available.
 pub(crate) trait UploadContext<T> {
     fn upload_ctx(self, id: &UploadId, uri: &ObjectUri, part: PartNumber) -> Result<T>;
@@ -140,7 +192,7 @@ pub(crate) trait UploadContext<T> {
 
 impl<T, E> UploadContext<T> for Result<T, E>
 where
-    E: StdError + 'static,
+    E: StdError + Send + Sync + 'static,
 {
     fn upload_ctx(self, id: &UploadId, uri: &ObjectUri, part: PartNumber) -> Result<T> {
         match self {
@@ -162,28 +214,99 @@ where
 pub(crate) enum ErrorRepr {
     #[error("{0} missing required field: {1}")]
     Missing(&'static str, &'static str),
+    #[error("operation not supported by client: {0}")]
+    Unsupported(&'static str),
+    #[error("invalid configuration: {0}")]
+    Invalid(String),
+    #[error("upload reached the maximum of 10,000 parts")]
+    TooManyParts,
+    #[error("error reading upload source: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("error staging part on disk: {0}")]
+    Disk(#[source] std::io::Error),
     #[error("encoding error: {0} {1}")]
     Encoding(String, EncodeErrorKind),
     #[error("upload failed: {failed}: {source}")]
     UploadFailed {
         failed: FailedUpload,
-        source: Box<dyn StdError>,
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    #[error("upload aborted after error: {failed}: {source}")]
+    Aborted {
+        failed: FailedUpload,
+        source: Box<Error>,
+    },
+    #[error("upload failed and could not be aborted: {failed}: {source}: {abort}")]
+    AbortFailed {
+        failed: FailedUpload,
+        source: Box<Error>,
+        abort: Box<Error>,
+    },
+    #[error("error from aws_sdk: {source}")]
+    Sdk {
+        source: Box<dyn StdError + Send + Sync>,
+        retryable: bool,
     },
-    #[error("error from aws_sdk: {0}")]
-    Sdk(#[source] Box<dyn StdError>),
     #[error("{kind} error: {msg}")]
     Other { kind: ErrorKind, msg: &'static str },
     #[error(transparent)]
-    DynStd(Box<dyn StdError>),
+    DynStd(Box<dyn StdError + Send + Sync>),
+}
+
+impl ErrorRepr {
+    /// The error resulting from aborting the upload `failed` after `source`.
+    pub(crate) fn aborted(failed: FailedUpload, source: Error, abort: Result<()>) -> Self {
+        let source = Box::new(source);
+        match abort {
+            Ok(()) => Self::Aborted { failed, source },
+            Err(e) => Self::AbortFailed {
+                failed,
+                source,
+                abort: Box::new(e),
+            },
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Sdk { retryable, .. } => *retryable,
+            Self::UploadFailed { source, .. } => source
+                .downcast_ref::<ErrorRepr>()
+                .is_some_and(ErrorRepr::is_retryable),
+            _ => false,
+        }
+    }
 }
 
+// Error codes from S3 meaning the request can be retried.
+const RETRYABLE_CODES: &[&str] = &[
+    "InternalError",
+    "RequestTimeout",
+    "ServiceUnavailable",
+    "SlowDown",
+    "Throttling",
+    "ThrottlingException",
+];
+
 impl<E, R> From<SdkError<E, R>> for ErrorRepr
 where
-    E: StdError + 'static,
-    R: std::fmt::Debug + 'static,
+    E: ProvideErrorMetadata + StdError + Send + Sync + 'static,
+    R: std::fmt::Debug + Send + Sync + 'static,
 {
     fn from(value: SdkError<E, R>) -> Self {
-        Self::Sdk(Box::new(value))
+        let retryable = match &value {
+            SdkError::TimeoutError(_) | SdkError::ResponseError(_) => true,
+            SdkError::DispatchFailure(e) => e.is_io() || e.is_timeout(),
+            SdkError::ServiceError(e) => e
+                .err()
+                .code()
+                .is_some_and(|code| RETRYABLE_CODES.contains(&code)),
+            _ => false,
+        };
+        Self::Sdk {
+            source: Box::new(value),
+            retryable,
+        }
     }
 }
 
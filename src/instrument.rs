@@ -0,0 +1,153 @@
+//! Metrics emitted with the `metrics` feature.
+//!
+//! Without the feature these functions do nothing.
+#![cfg_attr(not(feature = "metrics"), allow(unused_variables, dead_code))]
+use std::time::{Duration, Instant};
+
+/// Seconds taken by a request to S3, labeled by `operation` and `bucket`.
+///
+/// The operation is one of `create_upload`, `upload_part`, `upload_part_copy`,
+/// `complete_upload`, `abort_upload`, `list_parts`, and `list_uploads`.
+const REQUEST_DURATION: &str = "aws_multipart_upload_request_duration_seconds";
+/// Requests sent to S3, labeled by `operation`, `bucket` and `status`, which
+/// is `"ok"` or `"error"`.  The operations are those of [`REQUEST_DURATION`].
+const REQUESTS: &str = "aws_multipart_upload_requests_total";
+/// Requests that were attempted again after failing, labeled by `operation`
+/// and `bucket`.  The operations are those of [`REQUEST_DURATION`].
+const RETRIES: &str = "aws_multipart_upload_retries_total";
+/// Bytes in each part sent, labeled by `bucket`.
+const PART_SIZE: &str = "aws_multipart_upload_part_size_bytes";
+/// Bytes in parts sent, labeled by `bucket`.
+const BYTES: &str = "aws_multipart_upload_bytes_total";
+/// Part upload requests in flight, labeled by `bucket`.
+const ACTIVE_TASKS: &str = "aws_multipart_upload_active_tasks";
+/// Uploads that were finished, labeled by `bucket` and `outcome`, which is
+/// `"completed"`, `"failed"` or `"aborted"`.
+const UPLOADS: &str = "aws_multipart_upload_uploads_total";
+/// Items written to a part by an encoder.
+const ENCODED_ITEMS: &str = "aws_multipart_upload_encoded_items_total";
+/// Items that an encoder failed to write to a part.
+const ENCODE_ERRORS: &str = "aws_multipart_upload_encode_errors_total";
+
+/// Register the descriptions and units of the metrics this crate emits with
+/// the installed recorder.
+///
+/// The metrics are:
+///
+/// * `aws_multipart_upload_request_duration_seconds`: histogram of the time a
+///   request to S3 takes, labeled by `operation` and `bucket`.
+/// * `aws_multipart_upload_requests_total`: counter of requests to S3, labeled
+///   by `operation`, `bucket`, and `status`, which is `"ok"` or `"error"`.
+/// * `aws_multipart_upload_retries_total`: counter of requests attempted again
+///   after failing, labeled by `operation` and `bucket`.
+/// * `aws_multipart_upload_part_size_bytes`: histogram of the size of the parts
+///   sent, labeled by `bucket`.
+/// * `aws_multipart_upload_bytes_total`: counter of the bytes in parts sent,
+///   labeled by `bucket`.
+/// * `aws_multipart_upload_active_tasks`: gauge of the part upload requests in
+///   flight, labeled by `bucket`.
+/// * `aws_multipart_upload_uploads_total`: counter of finished uploads, labeled
+///   by `bucket` and `outcome`, which is `"completed"`, `"failed"`, or
+///   `"aborted"`.
+/// * `aws_multipart_upload_encoded_items_total`: counter of items written to a
+///   part by an encoder.
+/// * `aws_multipart_upload_encode_errors_total`: counter of items an encoder
+///   failed to write.
+///
+/// The operations are `create_upload`, `upload_part`, `upload_part_copy`,
+/// `complete_upload`, `abort_upload`, `list_parts`, and `list_uploads`.
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub fn describe_metrics() {
+    use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+
+    describe_histogram!(
+        REQUEST_DURATION,
+        Unit::Seconds,
+        "Time taken by a request to S3"
+    );
+    describe_counter!(REQUESTS, Unit::Count, "Requests sent to S3");
+    describe_counter!(
+        RETRIES,
+        Unit::Count,
+        "Requests attempted again after failing"
+    );
+    describe_histogram!(PART_SIZE, Unit::Bytes, "Size of the parts sent");
+    describe_counter!(BYTES, Unit::Bytes, "Bytes in the parts sent");
+    describe_gauge!(ACTIVE_TASKS, Unit::Count, "Part upload requests in flight");
+    describe_counter!(UPLOADS, Unit::Count, "Uploads that were finished");
+    describe_counter!(ENCODED_ITEMS, Unit::Count, "Items written to a part");
+    describe_counter!(
+        ENCODE_ERRORS,
+        Unit::Count,
+        "Items that failed to be written"
+    );
+}
+
+/// Time the request `fut` for the `op` operation on `bucket`.
+pub(crate) async fn timed<T, E, F>(op: &'static str, bucket: &str, fut: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let start = Instant::now();
+    let out = fut.await;
+    request(op, bucket, start.elapsed(), out.is_ok());
+    out
+}
+
+fn request(op: &'static str, bucket: &str, elapsed: Duration, ok: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let bucket = bucket.to_string();
+        let status = if ok { "ok" } else { "error" };
+        metrics::histogram!(REQUEST_DURATION, "operation" => op, "bucket" => bucket.clone())
+            .record(elapsed.as_secs_f64());
+        metrics::counter!(REQUESTS, "operation" => op, "bucket" => bucket, "status" => status)
+            .increment(1);
+    }
+}
+
+pub(crate) fn retry(op: &'static str, bucket: &str) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(RETRIES, "operation" => op, "bucket" => bucket.to_string()).increment(1);
+}
+
+pub(crate) fn part_sent(bucket: &str, bytes: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        let bucket = bucket.to_string();
+        metrics::histogram!(PART_SIZE, "bucket" => bucket.clone()).record(bytes as f64);
+        metrics::counter!(BYTES, "bucket" => bucket).increment(bytes as u64);
+    }
+}
+
+/// Counts a part upload request to `bucket` as in flight until dropped.
+pub(crate) struct ActiveTask(String);
+
+impl ActiveTask {
+    pub(crate) fn new(bucket: &str) -> Self {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(ACTIVE_TASKS, "bucket" => bucket.to_string()).increment(1.0);
+        Self(bucket.to_string())
+    }
+}
+
+impl Drop for ActiveTask {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        metrics::gauge!(ACTIVE_TASKS, "bucket" => self.0.clone()).decrement(1.0);
+    }
+}
+
+pub(crate) fn upload_finished(bucket: &str, outcome: &'static str) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(UPLOADS, "bucket" => bucket.to_string(), "outcome" => outcome).increment(1);
+}
+
+pub(crate) fn encoded(ok: bool) {
+    #[cfg(feature = "metrics")]
+    match ok {
+        true => metrics::counter!(ENCODED_ITEMS).increment(1),
+        false => metrics::counter!(ENCODE_ERRORS).increment(1),
+    }
+}
@@ -25,6 +25,10 @@
 //! continuing indefinitely.  The capability is generally captured in the trait alias/abbreviation
 //! [`AwsMultipartUpload`].
 //!
+//! A `MultipartUpload` is `Send`, so it can be moved into a task spawned on a multi-threaded runtime.
+//! For clients that cannot satisfy this, there is [`LocalSendRequest`], which is used to build a
+//! [`LocalMultipartUpload`] with [`UploadBuilder::new_local`].
+//!
 //! Combinators from the `multipart-write` crate can be used to chain and compose types here.  The
 //! extension traits [`UploadWriteExt`] and [`UploadStreamExt`] expand on this to allow creating
 //! general `AwsMultipartUpload`s, futures for running uploads, and streams as an additional context
@@ -48,11 +52,11 @@
 //! # use aws_multipart_upload::codec::JsonLinesEncoder;
 //! # use aws_multipart_upload::error::Result;
 //! # use aws_multipart_upload::request::*;
-//! # use multipart_write::MultipartWriteExt as _;
+//! # use multipart_write::MultipartWriteExt;
 //! # use serde_json::{Value, json};
 //! # use std::sync::{Arc, RwLock};
 //! # #[derive(Default)]
-//! # struct SdkClient(Arc<RwLock<Vec<PartBody>>>);
+//! # struct SdkClient(Arc<RwLock<Vec<RequestBody>>>);
 //! # impl SdkClient { async fn defaults() -> Self { Self::default() } }
 //! # impl SendRequest for SdkClient {
 //! #     async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
@@ -61,7 +65,7 @@
 //! #     async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
 //! #         let mut inner = self.0.write().unwrap();
 //! #         inner.push(req.body().clone());
-//! #         Ok(CompletedPart::new("".into(), "".into(), req.part_number(), req.body().size()))
+//! #         Ok(CompletedPart::new("".into(), "".into(), req.part_number(), req.body().len()))
 //! #     }
 //! #     async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
 //! #         Ok(CompletedUpload::new(req.uri().clone(), "".into()))
@@ -73,7 +77,7 @@
 //! # mod __m {
 //! use aws_multipart_upload::{ByteSize, SdkClient, UploadBuilder};
 //! use aws_multipart_upload::codec::JsonLinesEncoder;
-//! use multipart_write::MultipartWriteExt as _;
+//! use multipart_write::MultipartWriteExt;
 //! use serde_json::{Value, json};
 //! # }
 //! # async fn f() -> aws_multipart_upload::error::Result<()> {
@@ -90,7 +94,7 @@
 //! let mut upl = UploadBuilder::new(client)
 //!     .upload_size(ByteSize::mib(20))
 //!     .part_size(ByteSize::mib(5))
-//!     .with_encoder(JsonLinesEncoder)
+//!     .with_encoder(JsonLinesEncoder::new())
 //!     .with_uri(("a-bucket-us-east-1", "an/object/key.jsonl"))
 //!     .build();
 //!
@@ -106,7 +110,7 @@
 //!
 //!     // We've reached target upload size:
 //!     if status.should_upload {
-//!         let res = upl.complete().await?;
+//!         let res = MultipartWriteExt::<Value>::complete(&mut upl).await?;
 //!         println!("created {} with entity tag {}", res.uri, res.etag);
 //!         break;
 //!     }
@@ -123,34 +127,68 @@
 //! [`UploadStreamExt`]: self::write::UploadStreamExt
 //! [readme-eg]: https://github.com/quasi-coherent/aws-multipart-upload/blob/master/README.md#Example
 //! [repo-eg]: https://github.com/quasi-coherent/aws-multipart-upload/tree/master/examples
-use self::request::PartBody;
-use self::uri::EmptyUri;
-use self::write::{PartBuffer, UploadWriteExt};
+use self::codec::EncoderInfo;
+use self::error::{ErrorRepr, Result};
+use self::request::{
+    ChecksumAlgorithm, CompletedUpload, CopySource, Encryption, ObjectAttributes, PartBody,
+    RetryPolicy, UploadData,
+};
+use self::uri::{Bucket, EmptyUri, KeyTemplate, OneTimeUse};
+use self::write::{InflightBytes, PartBuffer, PartSize, PartitionedUpload, Upload, UploadWriteExt};
 
 use aws_sdk::operation::abort_multipart_upload as abort_upload;
 use aws_sdk::operation::complete_multipart_upload as complete_upload;
 use aws_sdk::operation::create_multipart_upload as create_upload;
+use aws_sdk::operation::list_multipart_uploads as list_uploads;
+use aws_sdk::operation::list_parts;
 use aws_sdk::operation::upload_part as part_upload;
+use aws_sdk::operation::upload_part_copy as part_copy;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncRead;
 
+#[cfg(feature = "avro")]
+#[doc(hidden)]
+pub extern crate apache_avro;
+#[cfg(feature = "parquet")]
+#[doc(hidden)]
+pub extern crate arrow_schema;
 #[doc(hidden)]
 pub extern crate aws_config;
 #[doc(hidden)]
 pub extern crate aws_sdk_s3 as aws_sdk;
+#[cfg(feature = "parquet")]
+#[doc(hidden)]
+pub extern crate parquet;
 
 pub use bytesize::ByteSize;
 
 #[macro_use]
 mod trace;
 
+mod instrument;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use instrument::describe_metrics;
+
 mod client;
-pub use client::{SdkClient, SendRequest, UploadClient};
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use client::InMemoryClient;
+pub use client::{
+    AbortIncomplete, AbortSummary, ErasedClient, LocalSendRequest, LocalUploadClient, SdkClient,
+    SendRequest, UploadClient,
+};
 
 pub mod codec;
 pub mod error;
 
 pub mod write;
 #[doc(inline)]
-pub use write::{AwsMultipartUpload, MultipartUpload, Status};
+pub use write::{
+    AwsMultipartUpload, LocalMultipartUpload, MultipartUpload, MultipartUploadWriter, Status,
+};
 
 pub mod request {
     //! Request interface of the multipart upload API.
@@ -161,7 +199,10 @@ pub mod request {
     //! objects.
     pub use super::client::part::*;
     pub use super::client::request::*;
-    pub use super::client::{UploadData, UploadId};
+    pub use super::client::{
+        BucketAccess, Checksum, ChecksumAlgorithm, CustomerKey, Encryption, ObjectAttributes,
+        RetryPolicy, UploadData, UploadId,
+    };
 }
 
 pub mod uri;
@@ -172,18 +213,30 @@ pub use uri::{ObjectUri, ObjectUriIter};
 const AWS_MAX_OBJECT_SIZE: ByteSize = ByteSize::tib(5);
 const AWS_MIN_PART_SIZE: ByteSize = ByteSize::mib(5);
 const AWS_MAX_PART_SIZE: ByteSize = ByteSize::gib(5);
+const AWS_MAX_PART_COUNT: u64 = 10_000;
 const DEFAULT_MAX_OBJECT_SIZE: ByteSize = ByteSize::gib(5);
 const DEFAULT_MAX_PART_SIZE: ByteSize = ByteSize::mib(10);
+const DEFAULT_MAX_TASKS: usize = 10;
 
 /// Configures and builds a type for multipart uploads.
 #[derive(Debug)]
-pub struct UploadBuilder<E = PartBody> {
-    client: UploadClient,
+pub struct UploadBuilder<E = PartBody, C = UploadClient> {
+    client: C,
     max_bytes: ByteSize,
-    max_part_bytes: ByteSize,
+    part_size: PartSize,
     max_tasks: Option<usize>,
+    inflight: Option<InflightBytes>,
+    stage_dir: Option<PathBuf>,
+    abort_on_error: bool,
+    checksum: Option<ChecksumAlgorithm>,
+    encryption: Option<Encryption>,
+    attributes: ObjectAttributes,
+    max_part_age: Option<Duration>,
+    max_upload_age: Option<Duration>,
     encoder: E,
+    encoder_info: Arc<EncoderInfo>,
     iter: ObjectUriIter,
+    resume: Option<UploadData>,
 }
 
 impl UploadBuilder {
@@ -192,31 +245,71 @@ impl UploadBuilder {
     where
         C: SendRequest + 'static,
     {
+        UploadBuilder::with_client(UploadClient::new(client))
+    }
+}
+
+impl UploadBuilder<PartBody, LocalUploadClient> {
+    /// Create an `UploadBuilder` from a [`LocalSendRequest`] client.
+    ///
+    /// The resulting upload is not `Send`.
+    pub fn new_local<C>(client: C) -> Self
+    where
+        C: LocalSendRequest + 'static,
+    {
+        UploadBuilder::with_client(LocalUploadClient::new(client))
+    }
+}
+
+impl<C: ErasedClient> UploadBuilder<PartBody, C> {
+    fn with_client(client: C) -> Self {
         Self {
-            client: UploadClient::new(client),
+            client,
             max_bytes: DEFAULT_MAX_OBJECT_SIZE,
-            max_part_bytes: DEFAULT_MAX_PART_SIZE,
-            max_tasks: Some(10),
+            part_size: PartSize::default(),
+            max_tasks: Some(DEFAULT_MAX_TASKS),
+            inflight: None,
+            stage_dir: None,
+            abort_on_error: false,
+            checksum: None,
+            encryption: None,
+            attributes: ObjectAttributes::default(),
+            max_part_age: None,
+            max_upload_age: None,
             encoder: PartBody::default(),
+            encoder_info: Arc::default(),
             iter: ObjectUriIter::new(EmptyUri),
+            resume: None,
         }
     }
+}
 
+impl<C> UploadBuilder<PartBody, C> {
     /// Set a builder for what will be used as an encoding for items going into
     /// a part in the multipart upload.
-    pub fn with_encoder<E>(self, encoder: E) -> UploadBuilder<E> {
+    pub fn with_encoder<E>(self, encoder: E) -> UploadBuilder<E, C> {
         UploadBuilder {
             client: self.client,
             max_bytes: self.max_bytes,
-            max_part_bytes: self.max_part_bytes,
+            part_size: self.part_size,
             max_tasks: self.max_tasks,
+            inflight: self.inflight,
+            stage_dir: self.stage_dir,
+            abort_on_error: self.abort_on_error,
+            checksum: self.checksum,
+            encryption: self.encryption,
+            attributes: self.attributes,
+            max_part_age: self.max_part_age,
+            max_upload_age: self.max_upload_age,
             encoder,
+            encoder_info: self.encoder_info,
             iter: self.iter,
+            resume: self.resume,
         }
     }
 }
 
-impl<E> UploadBuilder<E> {
+impl<E, C: ErasedClient> UploadBuilder<E, C> {
     /// Set the target size of the upload.
     pub fn upload_size(self, limit: ByteSize) -> Self {
         Self {
@@ -226,13 +319,63 @@ impl<E> UploadBuilder<E> {
     }
 
     /// Set the target size of a part.
+    ///
+    /// This replaces any growth set by [`grow_part_size`].
+    ///
+    /// [`grow_part_size`]: UploadBuilder::grow_part_size
     pub fn part_size(self, limit: ByteSize) -> Self {
         Self {
-            // Clamp to AWS_MIN <= max_part_bytes <= min(AWS_MAX, usize::MAX).
-            max_part_bytes: limit
-                .max(AWS_MIN_PART_SIZE)
-                .min(AWS_MAX_PART_SIZE)
-                .min(ByteSize::b(usize::MAX as u64)),
+            part_size: PartSize::new(limit),
+            ..self
+        }
+    }
+
+    /// Multiply the target size of a part by `factor` after every `every`
+    /// parts.
+    ///
+    /// An upload can have at most 10,000 parts, so a growing part size is how
+    /// to make an upload larger than 10,000 times the initial part size.  See
+    /// [`PartSize`] for more, including how a `factor` or `every` of 0 is
+    /// treated.
+    pub fn grow_part_size(self, factor: u64, every: u64) -> Self {
+        Self {
+            part_size: self.part_size.grow(factor, every),
+            ..self
+        }
+    }
+
+    /// Set the schedule for the target size of a part.
+    pub fn part_size_schedule(self, part_size: PartSize) -> Self {
+        Self { part_size, ..self }
+    }
+
+    /// Set the maximum time data can wait in a part that is not uploaded yet.
+    ///
+    /// The time is measured from the first item written to the part.  When it
+    /// runs out, the upload is completed by the stream from [`into_upload`],
+    /// even if no more items have arrived, since a part smaller than the
+    /// minimum part size cannot be followed by another part.
+    ///
+    /// [`into_upload`]: crate::write::UploadStreamExt::into_upload
+    pub fn max_part_age(self, age: Duration) -> Self {
+        Self {
+            max_part_age: Some(age),
+            ..self
+        }
+    }
+
+    /// Set the maximum time an upload can be in progress before it is
+    /// completed.
+    ///
+    /// The time is measured from the first item written to the upload.  Like
+    /// [`max_part_age`], the upload is completed by the stream from
+    /// [`into_upload`] when the time runs out.
+    ///
+    /// [`max_part_age`]: UploadBuilder::max_part_age
+    /// [`into_upload`]: crate::write::UploadStreamExt::into_upload
+    pub fn max_upload_age(self, age: Duration) -> Self {
+        Self {
+            max_upload_age: Some(age),
             ..self
         }
     }
@@ -245,6 +388,110 @@ impl<E> UploadBuilder<E> {
         }
     }
 
+    /// Set the maximum number of bytes of parts being uploaded at one time.
+    ///
+    /// No new part is started while the parts in flight add up to the limit,
+    /// which bounds the memory they hold independently of the part size.  See
+    /// [`InflightBytes`] to share one limit between several uploads.
+    pub fn max_inflight_bytes(self, limit: ByteSize) -> Self {
+        Self {
+            inflight: Some(InflightBytes::new(limit)),
+            ..self
+        }
+    }
+
+    /// Set the budget that the bytes of parts being uploaded count against.
+    ///
+    /// Clones of the same [`InflightBytes`] set on several builders limit the
+    /// bytes in flight across all of their uploads together.
+    pub fn inflight_bytes(self, budget: InflightBytes) -> Self {
+        Self {
+            inflight: Some(budget),
+            ..self
+        }
+    }
+
+    /// Stage parts in files in the directory `dir` instead of holding them in
+    /// memory while they are uploaded.
+    ///
+    /// Each part is written to a new file when it is sent, and its upload
+    /// request streams the body from the file, which is deleted when the
+    /// request has finished.  The part that items are being encoded to is
+    /// still built in memory.
+    pub fn stage_parts_in<P: Into<PathBuf>>(self, dir: P) -> Self {
+        Self {
+            stage_dir: Some(dir.into()),
+            ..self
+        }
+    }
+
+    /// Set the policy for retrying failed requests to create an upload, upload
+    /// a part, or complete an upload.
+    ///
+    /// By default, failed requests are not retried.
+    pub fn retry_policy(self, policy: RetryPolicy) -> Self {
+        Self {
+            client: self.client.with_retry_policy(policy),
+            ..self
+        }
+    }
+
+    /// Set whether to abort an upload that fails with an unrecoverable error.
+    ///
+    /// An error is unrecoverable once the retry policy gives up on it.  The
+    /// error that is returned reports whether aborting the upload succeeded in
+    /// [`Error::aborted`].
+    ///
+    /// By default, a failed upload is left in place.
+    ///
+    /// [`Error::aborted`]: crate::error::Error::aborted
+    pub fn abort_on_error(self, abort: bool) -> Self {
+        Self {
+            abort_on_error: abort,
+            ..self
+        }
+    }
+
+    /// Set the algorithm for the checksums S3 uses to verify uploaded data.
+    ///
+    /// The checksum of each part is computed from the part body and sent with
+    /// the part upload request, recorded in the [`CompletedPart`], and sent in
+    /// the request to complete the upload.  The checksum of the object is then
+    /// available in the [`CompletedUpload`].
+    ///
+    /// [`CompletedPart`]: crate::request::CompletedPart
+    /// [`CompletedUpload`]: crate::request::CompletedUpload
+    pub fn checksum(self, algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            checksum: Some(algorithm),
+            ..self
+        }
+    }
+
+    /// Set the server-side encryption of the uploaded objects.
+    ///
+    /// This is requested when an upload is created.  With a key provided by
+    /// the customer (SSE-C), the key is also sent with every part and with the
+    /// request to complete the upload, because S3 does not store it.  The key
+    /// is not in the `Debug` output of any type, nor in traces.
+    pub fn encryption(self, encryption: Encryption) -> Self {
+        Self {
+            encryption: Some(encryption),
+            ..self
+        }
+    }
+
+    /// Set the default attributes of the uploaded objects.
+    ///
+    /// The attributes an object has from [`with_object_iter`] take precedence
+    /// over these, and the content type of the encoder is used if neither
+    /// has one.
+    ///
+    /// [`with_object_iter`]: UploadBuilder::with_object_iter
+    pub fn attributes(self, attributes: ObjectAttributes) -> Self {
+        Self { attributes, ..self }
+    }
+
     /// Set the destination object URI for a single upload.
     ///
     /// The resulting `MultipartUpload` is only one-time-use.
@@ -260,18 +507,238 @@ impl<E> UploadBuilder<E> {
     pub fn with_uri_iter<I>(self, inner: I) -> Self
     where
         I: IntoIterator<Item = ObjectUri> + 'static,
+        I::IntoIter: Send,
     {
         let iter = ObjectUriIter::new(inner);
         Self { iter, ..self }
     }
 
+    /// Use the iterator to start a new upload when one completes, creating it
+    /// with the attributes paired with the object URI.
+    pub fn with_object_iter<I>(self, inner: I) -> Self
+    where
+        I: IntoIterator<Item = (ObjectUri, ObjectAttributes)> + 'static,
+        I::IntoIter: Send,
+    {
+        let iter = ObjectUriIter::with_attributes(inner);
+        Self { iter, ..self }
+    }
+
+    /// Start a new upload when one completes, to an object in `bucket` with
+    /// the next key rendered from `template`.
+    ///
+    /// The `{ext}` placeholder is the extension of the encoder unless the
+    /// template has one of its own.
+    pub fn with_key_template<B: Into<Bucket>>(self, bucket: B, template: KeyTemplate) -> Self {
+        let iter = template
+            .encoder_info(Arc::clone(&self.encoder_info))
+            .into_uri_iter(bucket);
+        Self { iter, ..self }
+    }
+
+    /// Resume an existing upload, such as the one in a [`FailedUpload`].
+    ///
+    /// The `MultipartUpload` writes to this upload first, continuing with the
+    /// part number after the last of the parts already uploaded, which are
+    /// found with a `ListParts` request.  Any uploads after it come from the
+    /// configured object URIs as usual.
+    ///
+    /// Note that the configured upload size applies to the bytes written after
+    /// resuming the upload.
+    ///
+    /// [`FailedUpload`]: crate::error::FailedUpload
+    pub fn resume<T: Into<UploadData>>(self, upload: T) -> Self {
+        Self {
+            resume: Some(upload.into()),
+            ..self
+        }
+    }
+
     /// Build a `MultipartUpload` from this configuration.
-    pub fn build(self) -> MultipartUpload<E> {
-        let buf = PartBuffer::new(self.max_tasks);
-        buf.upload(&self.client, self.iter).encoded_upload(
-            self.encoder,
-            self.max_bytes,
-            self.max_part_bytes,
-        )
+    ///
+    /// The configuration is not checked: if the upload size is more than the
+    /// part size allows for in 10,000 parts, writing to the upload fails when
+    /// it has no parts left.  Use [`try_build`] to fail before that instead.
+    ///
+    /// [`try_build`]: UploadBuilder::try_build
+    pub fn build(self) -> MultipartUpload<E, C> {
+        let (max_bytes, part_size) = (self.max_bytes, self.part_size);
+        let (max_part_age, max_upload_age) = (self.max_part_age, self.max_upload_age);
+        let (upload, encoder, info) = self.into_upload();
+        upload
+            .encoded_upload(encoder, max_bytes, part_size)
+            .max_part_age(max_part_age)
+            .max_upload_age(max_upload_age)
+            .encoder_info(info)
+    }
+
+    /// Build a `MultipartUpload` from this configuration, checking that it is
+    /// possible to upload with.
+    ///
+    /// This fails if the upload size is more than the part size allows for in
+    /// 10,000 parts.
+    pub fn try_build(self) -> Result<MultipartUpload<E, C>> {
+        self.check()?;
+        Ok(self.build())
+    }
+
+    /// Build a [`PartitionedUpload`] from this configuration, which writes
+    /// items to an upload for each key that `key` computes from them.
+    ///
+    /// The upload of a key is to the object URI that `uri` maps the key to, and
+    /// is configured like the upload from [`build`] would be, except for the
+    /// object URIs and the upload to resume, which are not used.  The uploads
+    /// share the budget for the bytes in flight, so that the memory they hold
+    /// does not grow with the number of uploads that are open, which means
+    /// one has to be set with [`max_inflight_bytes`] or [`inflight_bytes`].
+    ///
+    /// This fails if there is no budget for the bytes in flight, or for the
+    /// same reason as [`try_build`].
+    ///
+    /// [`PartitionedUpload`]: crate::write::PartitionedUpload
+    /// [`build`]: UploadBuilder::build
+    /// [`try_build`]: UploadBuilder::try_build
+    /// [`max_inflight_bytes`]: UploadBuilder::max_inflight_bytes
+    /// [`inflight_bytes`]: UploadBuilder::inflight_bytes
+    pub fn build_partitioned<K, F, M>(
+        self,
+        key: F,
+        uri: M,
+    ) -> Result<PartitionedUpload<K, E, F, M, C>> {
+        self.check()?;
+        if self.inflight.is_none() {
+            let msg = "a partitioned upload needs a budget for the bytes in flight";
+            return Err(ErrorRepr::Invalid(msg.into()).into());
+        }
+        Ok(PartitionedUpload::new(self, key, uri))
+    }
+
+    /// Build an [`UploadWriter`] from this configuration, which uploads the
+    /// bytes written to it with `AsyncWrite`.
+    ///
+    /// The writer makes one upload, to the first of the configured object URIs
+    /// or to the upload to resume.  The encoder, upload size, and maximum ages
+    /// are not used.
+    ///
+    /// [`UploadWriter`]: crate::write::UploadWriter
+    pub fn build_writer(self) -> MultipartUploadWriter<C> {
+        let part_size = self.part_size;
+        let (upload, _, _) = self.into_upload();
+        upload.into_async_write(part_size)
+    }
+
+    /// Upload the file at `path` to the object `uri`.
+    ///
+    /// The file is split into parts of the configured part size, and the byte
+    /// ranges of up to [`max_active_tasks`] parts are read at the same time.
+    /// The parts are uploaded with the same limit on requests in flight as any
+    /// other upload, and with the configured retry policy, checksum algorithm,
+    /// and whether to abort on error.  The encoder, upload size, maximum ages,
+    /// and upload to resume are not used.
+    ///
+    /// This fails before creating the upload if the file is too large to be
+    /// uploaded in 10,000 parts of the part size.
+    ///
+    /// [`max_active_tasks`]: UploadBuilder::max_active_tasks
+    pub async fn upload_file<P, T>(self, path: P, uri: T) -> Result<CompletedUpload>
+    where
+        P: AsRef<Path>,
+        T: Into<ObjectUri>,
+    {
+        let reads = self.max_tasks.unwrap_or(DEFAULT_MAX_TASKS);
+        let upload = self.part_upload(uri.into());
+        let path = path.as_ref();
+        write::send_file(upload, path, self.part_size, reads, self.abort_on_error).await
+    }
+
+    /// Upload the bytes read from `reader` to the object `uri`.
+    ///
+    /// This is like [`upload_file`], except that a part is read only after the
+    /// previous one, while the parts that were read are uploaded.  An error
+    /// reading a part fails the upload.
+    ///
+    /// [`upload_file`]: UploadBuilder::upload_file
+    pub async fn upload_reader<R, T>(self, reader: R, uri: T) -> Result<CompletedUpload>
+    where
+        R: AsyncRead + Unpin,
+        T: Into<ObjectUri>,
+    {
+        let upload = self.part_upload(uri.into());
+        write::send_reader(upload, reader, self.part_size, self.abort_on_error).await
+    }
+
+    /// Make the object `uri` from parts copied from `sources`, in order.
+    ///
+    /// S3 copies the parts from the existing objects, so nothing is downloaded.
+    /// Every part but the last has to be at least 5 MiB and no part can be
+    /// more than 5 GiB, so a larger object is copied in ranges made by
+    /// [`CopySource::parts`].  The parts are copied with the same limit on
+    /// requests in flight as any other upload, and with the configured retry
+    /// policy, checksum algorithm, and whether to abort on error.  The encoder,
+    /// part size, upload size, maximum ages, and upload to resume are not used.
+    ///
+    /// [`CopySource::parts`]: crate::request::CopySource::parts
+    pub async fn upload_copy<I, T>(self, sources: I, uri: T) -> Result<CompletedUpload>
+    where
+        I: IntoIterator<Item = CopySource>,
+        T: Into<ObjectUri>,
+    {
+        let upload = self.part_upload(uri.into());
+        write::send_copies(upload, sources).await
+    }
+
+    /// Check that the configuration is possible to upload with.
+    fn check(&self) -> Result<()> {
+        let max_object_size = self.part_size.max_object_size();
+        if self.max_bytes > max_object_size {
+            let msg = format!(
+                "upload size {} is more than 10,000 parts of the part size can hold ({})",
+                self.max_bytes, max_object_size,
+            );
+            return Err(ErrorRepr::Invalid(msg).into());
+        }
+        Ok(())
+    }
+
+    /// The `Upload` this is configured for, the encoder for its parts, and
+    /// where the encoder records what it tells about the objects.
+    fn into_upload(self) -> (Upload<PartBuffer<C::UploadPart>, C>, E, Arc<EncoderInfo>) {
+        let buf = PartBuffer::new(self.max_tasks, self.inflight.clone());
+        let upload = match self.resume {
+            Some(data) => Upload::resume(buf, &self.client, self.iter, data),
+            None => buf.upload(&self.client, self.iter),
+        };
+        let upload = upload
+            .abort_on_error(self.abort_on_error)
+            .checksum(self.checksum)
+            .encryption(self.encryption)
+            .attributes(self.attributes)
+            .encoder_info(Arc::clone(&self.encoder_info))
+            .inflight_bytes(self.inflight)
+            .stage_parts_in(self.stage_dir);
+        (upload, self.encoder, self.encoder_info)
+    }
+
+    /// The `MultipartUpload` of the one object `uri` with parts from `encoder`.
+    fn object_upload(&self, encoder: E, uri: ObjectUri) -> MultipartUpload<E, C> {
+        self.part_upload(uri)
+            .encoder_info(Arc::clone(&self.encoder_info))
+            .encoded_upload(encoder, self.max_bytes, self.part_size)
+            .max_part_age(self.max_part_age)
+            .max_upload_age(self.max_upload_age)
+            .encoder_info(Arc::clone(&self.encoder_info))
+    }
+
+    /// The `Upload` of parts read from a source other than an encoder.
+    fn part_upload(&self, uri: ObjectUri) -> Upload<PartBuffer<C::UploadPart>, C> {
+        let iter = ObjectUriIter::new(OneTimeUse::new(uri));
+        PartBuffer::new(self.max_tasks, self.inflight.clone())
+            .upload(&self.client, iter)
+            .abort_on_error(self.abort_on_error)
+            .checksum(self.checksum)
+            .encryption(self.encryption.clone())
+            .attributes(self.attributes.clone())
+            .inflight_bytes(self.inflight.clone())
+            .stage_parts_in(self.stage_dir.clone())
     }
 }
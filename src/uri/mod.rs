@@ -0,0 +1,706 @@
+//! `ObjectUri` iterators.
+//!
+//! This module provides types that can help in building iterators of URIs for
+//! a multipart upload type with [`ObjectUriIter`].
+//!
+//! The only thing required to create a new upload is the URI of the object to be
+//! uploaded, so given an iterator of `ObjectUri`s, this defines a sequence of
+//! multipart uploads that can be created as the previous one is completed by
+//! calling `next` on the iterator.  [`OneTimeUse`], an iterator that only
+//! produces one `ObjectUri`, is capable of serving a single multipart upload.
+//!
+//! # Key templates
+//!
+//! A [`KeyTemplate`] renders keys from placeholders for the date and time, a
+//! sequence number, random IDs, and the file extension of the encoder, and
+//! turns into an infinite `ObjectUriIter`.
+//!
+//! ```rust
+//! use aws_multipart_upload::uri::KeyTemplate;
+//!
+//! let template = KeyTemplate::parse("logs/{yyyy}/{MM}/{dd}/{HH}/{seq:06}-{uuid}.jsonl").unwrap();
+//! let mut iter = template.into_uri_iter("my-bucket");
+//! let new_uri = iter.next().unwrap();
+//! assert!(new_uri.key.starts_with("logs/"));
+//! assert!(new_uri.key.ends_with(".jsonl"));
+//!
+//! println!("{new_uri}");
+//! // "s3://my-bucket/logs/2025/11/11/11/000000-0b7e6a1c-7d27-4f7a-9a8e-2d0fd0a6b0c4.jsonl"
+//! ```
+//!
+//! # Example
+//!
+//! Keys can also be built with a closure.  This is an iterator of `ObjectUri`s that writes to a prefix based on the
+//! current date and time.
+//!
+//! ```rust
+//! use aws_multipart_upload::uri::{KeyPrefix, ObjectUriIter, ObjectUriIterExt};
+//!
+//! const BUCKET: &str = "my-bucket";
+//! const PREFIX: &str = "static/object/prefix";
+//!
+//! let iter_pfx = std::iter::repeat_with(|| KeyPrefix::from(PREFIX));
+//! let iter = iter_pfx.map_key(BUCKET, |prefix| {
+//!     let now = chrono::Utc::now();
+//!     let now_str = now.format("%Y/%m/%d/%H").to_string();
+//!     let us = now.timestamp_micros();
+//!     let root = format!("{now_str}/{us}.csv");
+//!     prefix.to_key(&root)
+//! });
+//!
+//! let mut uri = ObjectUriIter::new(iter);
+//! let new_uri = uri.next().unwrap();
+//!
+//! println!("{new_uri}");
+//! // "s3://my-bucket/static/object/prefix/2025/11/11/11/01/1763683634194850.csv"
+//! ```
+//!
+//! # Parsing
+//!
+//! An `ObjectUri` can also be parsed from a string, which is either an S3 URI
+//! like `s3://my-bucket/path/to/key.csv`, or the HTTPS URL of the object in
+//! virtual-hosted or path style.
+//!
+//! ```rust
+//! use aws_multipart_upload::ObjectUri;
+//!
+//! let uri: ObjectUri = "https://my-bucket.s3.us-east-2.amazonaws.com/a/b%20c.csv"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(uri, ObjectUri::from(("my-bucket", "a/b c.csv")));
+//! assert_eq!(uri.to_string(), "s3://my-bucket/a/b c.csv");
+//! ```
+//! [`ObjectUriIter`]: super::ObjectUriIter
+use crate::client::request::CreateRequest;
+use crate::client::{ErasedClient, ObjectAttributes};
+use crate::error::{Error, ErrorRepr, Result};
+
+use std::borrow::Cow;
+use std::fmt::{self, Formatter, Write as _};
+use std::net::Ipv4Addr;
+use std::ops::Deref;
+use std::str::FromStr;
+
+mod template;
+pub use self::template::KeyTemplate;
+
+// https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-keys.html
+const MAX_KEY_BYTES: usize = 1024;
+
+/// The address of an uploaded object in S3.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ObjectUri {
+    /// The S3 bucket for the object.
+    ///
+    /// This should be the plain bucket name, e.g., "my-s3-bucket".
+    pub bucket: Bucket,
+    /// The full key of this object within the bucket.
+    pub key: Key,
+}
+
+impl ObjectUri {
+    /// Create a new `ObjectUri` from bucket and object key.
+    pub fn new(bucket: Bucket, key: Key) -> Self {
+        Self { bucket, key }
+    }
+
+    /// Returns the HTTPS URL of the object in the AWS region `region`.
+    ///
+    /// The URL is virtual-hosted style, unless the bucket name has a `'.'`,
+    /// which the certificate of the virtual host does not cover, in which case
+    /// it is path style.
+    pub fn to_https_url(&self, region: &str) -> String {
+        let domain = match region.starts_with("cn-") {
+            true => "amazonaws.com.cn",
+            false => "amazonaws.com",
+        };
+        let key = encode_key(&self.key);
+        match self.bucket.contains('.') {
+            true => format!("https://s3.{region}.{domain}/{}/{key}", self.bucket),
+            false => format!("https://{}.s3.{region}.{domain}/{key}", self.bucket),
+        }
+    }
+
+    /// Returns the path-style URL of the object at `endpoint`, such as the URL
+    /// of an S3-compatible service, e.g., `"https://localhost:9000"`.
+    pub fn to_endpoint_url(&self, endpoint: &str) -> String {
+        let endpoint = endpoint.trim_end_matches('/');
+        let key = encode_key(&self.key);
+        format!("{endpoint}/{}/{key}", self.bucket)
+    }
+
+    /// Check that the bucket name follows the naming rules of S3 and that the
+    /// key is not empty or longer than S3 allows.
+    pub fn validate(&self) -> Result<()> {
+        self.bucket.validate()?;
+        self.key.validate()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bucket.is_empty() || self.key.is_empty()
+    }
+
+    /// Parse the path of a URL where the bucket is the first segment.
+    fn from_path_style(path: &str) -> Result<Self, String> {
+        let (bucket, key) = path
+            .split_once('/')
+            .ok_or_else(|| "missing object key".to_string())?;
+        Ok(Self::new(Bucket::from(bucket), Key::from(url_decode(key)?)))
+    }
+
+    fn parse_url(url: &str) -> Result<Self, String> {
+        if let Some(rest) = url.strip_prefix("s3://") {
+            let (bucket, key) = rest
+                .split_once('/')
+                .ok_or_else(|| "missing object key".to_string())?;
+            return Ok(Self::new(Bucket::from(bucket), Key::from(key)));
+        }
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .ok_or_else(|| "expected an s3:// or https:// URL".to_string())?;
+        // Neither the query, e.g., a version ID, nor the fragment are part of
+        // the object's address.
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (authority, path) = rest
+            .split_once('/')
+            .ok_or_else(|| "missing bucket and object key".to_string())?;
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default();
+
+        // Only the hosts of S3 itself put the bucket in the host name, and
+        // then the bucket is everything before the endpoint, which starts with
+        // the last label that is "s3" or starts with "s3-".  Labels before it
+        // can look the same, since they are part of the bucket name.
+        let labels = host.split('.').collect::<Vec<_>>();
+        let is_aws = host.ends_with(".amazonaws.com") || host.ends_with(".amazonaws.com.cn");
+        let s3_label = labels
+            .iter()
+            .rposition(|l| *l == "s3" || l.starts_with("s3-"));
+        match s3_label {
+            Some(n) if is_aws && n > 0 => {
+                let bucket = labels[..n].join(".");
+                Ok(Self::new(
+                    Bucket::from(bucket),
+                    Key::from(url_decode(path)?),
+                ))
+            }
+            _ => Self::from_path_style(path),
+        }
+    }
+}
+
+impl fmt::Display for ObjectUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "s3://{}/{}", &self.bucket, &self.key)
+    }
+}
+
+impl FromStr for ObjectUri {
+    type Err = Error;
+
+    /// Parse an S3 URI, `s3://bucket/key`, or the HTTPS URL of an object in
+    /// either virtual-hosted or path style.
+    ///
+    /// The key in an HTTPS URL is percent-decoded, while the key in an S3 URI
+    /// is taken as it is.  This fails if the string is neither, or if the
+    /// `ObjectUri` is not [valid].
+    ///
+    /// [valid]: ObjectUri::validate
+    fn from_str(s: &str) -> Result<Self> {
+        let uri = Self::parse_url(s.trim())
+            .map_err(|msg| ErrorRepr::Invalid(format!("invalid object uri {s:?}: {msg}")))?;
+        uri.validate()?;
+        Ok(uri)
+    }
+}
+
+impl TryFrom<&str> for ObjectUri {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl<T: Into<Bucket>, U: Into<Key>> From<(T, U)> for ObjectUri {
+    fn from((b, k): (T, U)) -> Self {
+        ObjectUri::new(b.into(), k.into())
+    }
+}
+
+/// The destination bucket for this upload when it is complete.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bucket(Cow<'static, str>);
+
+impl Bucket {
+    /// Create a new `Bucket`.
+    pub fn new<T: Into<Cow<'static, str>>>(bucket: T) -> Self {
+        let bucket: Cow<'static, str> = bucket.into();
+        match bucket.strip_suffix("/") {
+            Some(v) => v.into(),
+            _ => Self(bucket),
+        }
+    }
+
+    /// Check that this follows the naming rules of S3 for general purpose
+    /// buckets.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| {
+            let msg = format!("invalid bucket name {:?}: {msg}", &self.0);
+            Err(ErrorRepr::Invalid(msg).into())
+        };
+        let name = &*self.0;
+        if !(3..=63).contains(&name.len()) {
+            return invalid("must be between 3 and 63 characters long");
+        }
+        let allowed =
+            |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-';
+        if !name.chars().all(allowed) {
+            return invalid("must have only lowercase letters, numbers, '.' and '-'");
+        }
+        let alphanumeric = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+        if !alphanumeric(name.chars().next()) || !alphanumeric(name.chars().last()) {
+            return invalid("must begin and end with a letter or number");
+        }
+        if name.contains("..") {
+            return invalid("must not have two adjacent periods");
+        }
+        if name.parse::<Ipv4Addr>().is_ok() {
+            return invalid("must not be formatted as an IP address");
+        }
+        if ["xn--", "sthree-"].iter().any(|p| name.starts_with(p)) {
+            return invalid("must not begin with a reserved prefix");
+        }
+        if ["-s3alias", "--ol-s3"].iter().any(|p| name.ends_with(p)) {
+            return invalid("must not end with a reserved suffix");
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Deref for Bucket {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Bucket {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<&str> for Bucket {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl From<String> for Bucket {
+    fn from(value: String) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+/// The key within the associated bucket for this object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Key(Cow<'static, str>);
+
+impl Key {
+    /// Create a new object `Key`.
+    pub fn new<T: Into<Cow<'static, str>>>(key: T) -> Self {
+        Self(key.into())
+    }
+
+    /// Check that this is not empty and not longer than the 1024 bytes S3
+    /// allows.
+    pub fn validate(&self) -> Result<()> {
+        if self.0.is_empty() {
+            return Err(ErrorRepr::Invalid("object key is empty".into()).into());
+        }
+        if self.0.len() > MAX_KEY_BYTES {
+            let msg = format!(
+                "object key is {} bytes, more than the limit of {MAX_KEY_BYTES}",
+                self.0.len()
+            );
+            return Err(ErrorRepr::Invalid(msg).into());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Deref for Key {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Key {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<&str> for Key {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl From<String> for Key {
+    fn from(value: String) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+/// A prefix of S3 object keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeyPrefix(Cow<'static, str>);
+
+impl KeyPrefix {
+    /// Create a new object key prefix.
+    ///
+    /// Normalized to end with a single `'/'` and have no leading `'/'`.
+    pub fn new<T: Into<Cow<'static, str>>>(prefix: T) -> Self {
+        let raw: Cow<'static, str> = prefix.into();
+        let trimmed = raw.trim_matches('/');
+        Self(format!("{trimmed}/").into())
+    }
+
+    /// Extend this prefix by another.
+    pub fn append(&self, other: &KeyPrefix) -> Self {
+        format!("{self}{other}").into()
+    }
+
+    /// Create an object [`Key`] with this prefix and the given suffix.
+    pub fn to_key(&self, suffix: &str) -> Key {
+        format!("{self}{suffix}").into()
+    }
+}
+
+impl Deref for KeyPrefix {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for KeyPrefix {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for KeyPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<&str> for KeyPrefix {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl From<String> for KeyPrefix {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Produce an `ObjectUri` for a new upload from an iterator.
+///
+/// Each `ObjectUri` can come with the [`ObjectAttributes`] of the object, in
+/// which case the iterator is made with [`with_attributes`].
+///
+/// The iterator is required to be `Send` so that the upload it belongs to can
+/// be moved between threads.
+///
+/// [`with_attributes`]: ObjectUriIter::with_attributes
+pub struct ObjectUriIter {
+    inner: Box<dyn Iterator<Item = (ObjectUri, ObjectAttributes)> + Send>,
+}
+
+impl ObjectUriIter {
+    /// Create a new `ObjectUriIter` from an arbitrary iterator of `ObjectUri`.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = ObjectUri> + 'static,
+        I::IntoIter: Send,
+    {
+        let iter = iter
+            .into_iter()
+            .map(|uri| (uri, ObjectAttributes::default()));
+        Self {
+            inner: Box::new(iter),
+        }
+    }
+
+    /// Create a new `ObjectUriIter` from an iterator of `ObjectUri` paired
+    /// with the attributes of the object.
+    pub fn with_attributes<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (ObjectUri, ObjectAttributes)> + 'static,
+        I::IntoIter: Send,
+    {
+        Self {
+            inner: Box::new(iter.into_iter()),
+        }
+    }
+
+    /// Returns the next `ObjectUri` along with the attributes of the object.
+    pub fn next_object(&mut self) -> Option<(ObjectUri, ObjectAttributes)> {
+        self.inner.next()
+    }
+
+    /// Construct the request future to create a new multipart upload using the
+    /// next `ObjectUri` produced by this `ObjectUriIter` value.
+    pub fn next_upload<C: ErasedClient>(&mut self, client: &C) -> Option<C::CreateUpload> {
+        let (uri, attributes) = self.next_object()?;
+        let req = CreateRequest::new(uri).with_attributes(attributes);
+        let fut = client.create_upload(req);
+        Some(fut)
+    }
+}
+
+impl Default for ObjectUriIter {
+    fn default() -> Self {
+        Self::new(EmptyUri)
+    }
+}
+
+impl Iterator for ObjectUriIter {
+    type Item = ObjectUri;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_object().map(|(uri, _)| uri)
+    }
+}
+
+impl fmt::Debug for ObjectUriIter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectUriIter")
+            .field("inner", &"Iterator<Item = (ObjectUri, ObjectAttributes)>")
+            .finish()
+    }
+}
+
+/// Adds the method `map_key` to iterators over `KeyPrefix`.
+pub trait ObjectUriIterExt: Iterator {
+    /// Returns an iterator of `ObjectUri` by applying the function `F` to each
+    /// `KeyPrefix` to produce the object `Key`.
+    fn map_key<B, F>(self, bucket: B, f: F) -> MapKey<Self, F>
+    where
+        Self: Iterator<Item = KeyPrefix> + Sized,
+        F: FnMut(KeyPrefix) -> Key,
+        B: Into<Bucket>,
+    {
+        MapKey::new(self, bucket, f)
+    }
+}
+
+impl<I: Iterator> ObjectUriIterExt for I {}
+
+/// Iterator for [`map_key`](ObjectUriIterExt::map_key).
+pub struct MapKey<I, F> {
+    bucket: Bucket,
+    inner: I,
+    f: F,
+}
+
+impl<I, F> MapKey<I, F> {
+    fn new<B: Into<Bucket>>(inner: I, bucket: B, f: F) -> Self {
+        Self {
+            inner,
+            bucket: bucket.into(),
+            f,
+        }
+    }
+}
+
+impl<I, F> Iterator for MapKey<I, F>
+where
+    I: Iterator<Item = KeyPrefix>,
+    F: FnMut(KeyPrefix) -> Key,
+{
+    type Item = ObjectUri;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = self.inner.next()?;
+        let key = (self.f)(prefix);
+        let uri = ObjectUri::new(self.bucket.clone(), key);
+        Some(uri)
+    }
+}
+
+/// An empty iterator of `ObjectUri`s.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyUri;
+impl IntoIterator for EmptyUri {
+    type IntoIter = std::iter::Empty<ObjectUri>;
+    type Item = ObjectUri;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::empty()
+    }
+}
+
+/// Iterator that is exhausted after one `ObjectUri`.
+#[derive(Debug, Clone, Default)]
+pub struct OneTimeUse(Option<ObjectUri>);
+
+impl OneTimeUse {
+    /// Use the given `uri` as the one produced.
+    pub fn new(uri: ObjectUri) -> Self {
+        Self(Some(uri))
+    }
+}
+
+impl Iterator for OneTimeUse {
+    type Item = ObjectUri;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.take()
+    }
+}
+
+/// Percent-encode everything but the unreserved characters of RFC 3986.
+pub(crate) fn url_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            _ => {
+                let _ = write!(out, "%{b:02X}");
+            }
+        }
+    }
+    out
+}
+
+/// Percent-encode the segments of an object key for the path of a URL.
+pub(crate) fn encode_key(key: &str) -> String {
+    key.split('/').map(url_encode).collect::<Vec<_>>().join("/")
+}
+
+/// Decode the percent-encoded bytes in the path of a URL.
+fn url_decode(value: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(b) = iter.next() {
+        if b != b'%' {
+            bytes.push(b);
+            continue;
+        }
+        let hex = [iter.next(), iter.next()];
+        let decoded = match hex {
+            [Some(hi), Some(lo)] => std::str::from_utf8(&[hi, lo])
+                .ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok()),
+            _ => None,
+        };
+        bytes.push(decoded.ok_or_else(|| "invalid percent-encoding".to_string())?);
+    }
+    String::from_utf8(bytes).map_err(|_| "key is not UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> ObjectUri {
+        url.parse().unwrap_or_else(|e| panic!("{url}: {e}"))
+    }
+
+    #[test]
+    fn parse_s3_uri() {
+        let uri = parse("s3://my-bucket/a/b c.csv");
+        assert_eq!(uri, ObjectUri::from(("my-bucket", "a/b c.csv")));
+    }
+
+    #[test]
+    fn parse_virtual_hosted() {
+        let uri = parse("https://my-bucket.s3.us-east-2.amazonaws.com/a/b%20c.csv?versionId=1");
+        assert_eq!(uri, ObjectUri::from(("my-bucket", "a/b c.csv")));
+        let uri = parse("https://my-bucket.s3-us-west-2.amazonaws.com/k");
+        assert_eq!(uri, ObjectUri::from(("my-bucket", "k")));
+        let uri = parse("https://bkt.s3.cn-north-1.amazonaws.com.cn/k");
+        assert_eq!(uri, ObjectUri::from(("bkt", "k")));
+    }
+
+    #[test]
+    fn parse_path_style() {
+        let uri = parse("https://s3.us-east-2.amazonaws.com/my-bucket/x/y%2Bz");
+        assert_eq!(uri, ObjectUri::from(("my-bucket", "x/y+z")));
+        let uri = parse("http://localhost:9000/my-bucket/x/y");
+        assert_eq!(uri, ObjectUri::from(("my-bucket", "x/y")));
+    }
+
+    #[test]
+    fn parse_dotted_bucket() {
+        let uri = parse("https://my.dotted.bucket.s3.amazonaws.com/k");
+        assert_eq!(uri, ObjectUri::from(("my.dotted.bucket", "k")));
+        let uri = parse("https://my.s3.data.s3.us-east-2.amazonaws.com/k");
+        assert_eq!(uri, ObjectUri::from(("my.s3.data", "k")));
+    }
+
+    #[test]
+    fn parse_s3_prefixed_bucket() {
+        let uri = parse("https://s3-logs.s3.us-east-2.amazonaws.com/k/v");
+        assert_eq!(uri, ObjectUri::from(("s3-logs", "k/v")));
+        let uri = parse("https://s3.us-east-2.amazonaws.com/s3-logs/k/v");
+        assert_eq!(uri, ObjectUri::from(("s3-logs", "k/v")));
+    }
+
+    #[test]
+    fn https_url_round_trip() {
+        for uri in [
+            ObjectUri::from(("my-bucket", "a/b c+d.csv")),
+            ObjectUri::from(("s3-logs", "k/v")),
+            ObjectUri::from(("my.s3.data", "k")),
+        ] {
+            assert_eq!(parse(&uri.to_https_url("us-east-2")), uri);
+            assert_eq!(parse(&uri.to_endpoint_url("http://localhost:9000")), uri);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for url in [
+            "s3://my-bucket",
+            "ftp://b/k",
+            "s3://My-Bucket/k",
+            "https://s3.amazonaws.com/bucket",
+            "https://bkt.s3.amazonaws.com/%zz",
+        ] {
+            assert!(url.parse::<ObjectUri>().is_err(), "{url}");
+        }
+    }
+}
@@ -0,0 +1,457 @@
+use super::{Bucket, Key, ObjectUri, ObjectUriIter};
+use crate::codec::EncoderInfo;
+use crate::error::{Error, ErrorRepr, Result};
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The sequence number of the next key rendered in this process.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Default number of hex digits of the `{rand}` placeholder.
+const DEFAULT_RAND_DIGITS: usize = 16;
+
+/// A template for object keys.
+///
+/// The template is literal text with placeholders in braces, which are
+/// replaced every time a key is rendered.  A literal brace is written twice,
+/// as `{{` or `}}`.
+///
+/// | Placeholder    | Replaced with                                            |
+/// |----------------|----------------------------------------------------------|
+/// | `{yyyy}`       | The year, in four digits                                 |
+/// | `{MM}`         | The month, `01` to `12`                                  |
+/// | `{dd}`         | The day of the month, `01` to `31`                       |
+/// | `{HH}`         | The hour, `00` to `23`                                   |
+/// | `{mm}`         | The minute, `00` to `59`                                 |
+/// | `{ss}`         | The second, `00` to `59`                                 |
+/// | `{epoch}`      | Seconds since the Unix epoch                             |
+/// | `{epoch_ms}`   | Milliseconds since the Unix epoch                        |
+/// | `{seq}`        | The sequence number of the key in this process           |
+/// | `{seq:06}`     | The sequence number, zero-padded to 6 digits             |
+/// | `{uuid}`       | A random UUID                                            |
+/// | `{rand}`       | 16 random hex digits, or `N` of them with `{rand:N}`     |
+/// | `{hostname}`   | The name of the host                                     |
+/// | `{env:NAME}`   | The value of the environment variable `NAME`             |
+/// | `{ext}`        | A `'.'` and the file extension, if there is one          |
+///
+/// The date and time are in UTC unless the template has another offset.  The
+/// sequence number counts every key rendered from a template with `{seq}`
+/// in the process, starting at 0, so keys from different templates do not
+/// repeat a number.  The hostname and environment variables are read when
+/// the template is parsed.
+///
+/// The extension is the one set on the template, or otherwise the one of the
+/// encoder of the upload, see [`UploadBuilder::with_key_template`].
+///
+/// # Example
+///
+/// ```rust
+/// use aws_multipart_upload::uri::KeyTemplate;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let template = "logs/{yyyy}/{MM}/{dd}/{HH}/part-{seq:06}-{uuid}{ext}"
+///     .parse::<KeyTemplate>()
+///     .unwrap()
+///     .with_extension("jsonl.gz");
+///
+/// // 2024-02-29T12:34:56Z
+/// let key = template.render_at(UNIX_EPOCH + Duration::from_secs(1_709_210_096));
+/// assert!(key.starts_with("logs/2024/02/29/12/part-"));
+/// assert!(key.ends_with(".jsonl.gz"));
+///
+/// let mut iter = template.into_uri_iter("my-bucket");
+/// let uri = iter.next().unwrap();
+/// assert_eq!(&*uri.bucket, "my-bucket");
+///
+/// println!("{uri}");
+/// // "s3://my-bucket/logs/2025/11/11/11/part-000001-0b7e6a1c-7d27-4f7a-9a8e-2d0fd0a6b0c4.jsonl.gz"
+/// ```
+///
+/// [`UploadBuilder::with_key_template`]: crate::UploadBuilder::with_key_template
+#[derive(Debug, Clone)]
+pub struct KeyTemplate {
+    segments: Vec<Segment>,
+    offset: i64,
+    extension: Option<String>,
+    encoder_info: Option<Arc<EncoderInfo>>,
+}
+
+impl KeyTemplate {
+    /// Parse the template `template`.
+    ///
+    /// This fails if a placeholder is unknown or not closed, or if the value
+    /// of the hostname or an environment variable in the template can't be
+    /// found.
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = |msg: String| -> Error {
+            ErrorRepr::Invalid(format!("invalid key template {template:?}: {msg}")).into()
+        };
+        if template.is_empty() {
+            return Err(invalid("template is empty".into()));
+        }
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(i) = rest.find(['{', '}']) {
+            literal.push_str(&rest[..i]);
+            let brace = &rest[i..=i];
+            let after = &rest[i + 1..];
+            if let Some(after) = after.strip_prefix(brace) {
+                literal.push_str(brace);
+                rest = after;
+                continue;
+            }
+            if brace == "}" {
+                return Err(invalid("unmatched `}`".into()));
+            }
+            let end = after
+                .find('}')
+                .ok_or_else(|| invalid("unclosed `{`".into()))?;
+            match Segment::parse(&after[..end]).map_err(invalid)? {
+                Segment::Literal(value) => literal.push_str(&value),
+                segment => {
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(segment);
+                }
+            }
+            rest = &after[end + 1..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self {
+            segments,
+            offset: 0,
+            extension: None,
+            encoder_info: None,
+        })
+    }
+
+    /// Set the offset from UTC of the date and time in rendered keys, in
+    /// seconds east of UTC.
+    ///
+    /// For example, `5 * 3600 + 1800` is the offset of India, `+05:30`.
+    pub fn with_utc_offset(self, seconds: i32) -> Self {
+        Self {
+            offset: i64::from(seconds),
+            ..self
+        }
+    }
+
+    /// Set the file extension for the `{ext}` placeholder.
+    ///
+    /// This takes precedence over the extension of the encoder.  A leading
+    /// `'.'` is not part of the extension.
+    pub fn with_extension<T: Into<String>>(self, extension: T) -> Self {
+        let extension = extension.into();
+        let extension = extension.trim_start_matches('.').to_string();
+        Self {
+            extension: Some(extension),
+            ..self
+        }
+    }
+
+    /// Render the key for the current time.
+    pub fn render(&self) -> Key {
+        self.render_at(SystemTime::now())
+    }
+
+    /// Render the key for the time `time`.
+    pub fn render_at(&self, time: SystemTime) -> Key {
+        let millis = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_millis() as i64,
+            Err(e) => -(e.duration().as_millis() as i64),
+        };
+        let local = DateTime::from_unix(millis.div_euclid(1000) + self.offset);
+        let seq = self
+            .segments
+            .iter()
+            .any(|s| matches!(s, Segment::Seq(_)))
+            .then(|| SEQUENCE.fetch_add(1, Ordering::Relaxed));
+
+        let mut key = String::new();
+        for segment in &self.segments {
+            // Writing to a `String` does not fail.
+            let _ = match segment {
+                Segment::Literal(value) => key.write_str(value),
+                Segment::Year => write!(key, "{:04}", local.year),
+                Segment::Month => write!(key, "{:02}", local.month),
+                Segment::Day => write!(key, "{:02}", local.day),
+                Segment::Hour => write!(key, "{:02}", local.hour),
+                Segment::Minute => write!(key, "{:02}", local.minute),
+                Segment::Second => write!(key, "{:02}", local.second),
+                Segment::Epoch => write!(key, "{}", millis.div_euclid(1000)),
+                Segment::EpochMillis => write!(key, "{millis}"),
+                Segment::Seq(width) => write!(key, "{:0width$}", seq.unwrap_or_default()),
+                Segment::Uuid => write_uuid(&mut key),
+                Segment::Rand(digits) => write_rand(&mut key, *digits),
+                Segment::Ext => match self.extension() {
+                    Some(ext) if !ext.is_empty() => write!(key, ".{ext}"),
+                    _ => Ok(()),
+                },
+            };
+        }
+        Key::from(key)
+    }
+
+    /// Returns an infinite `ObjectUriIter` of objects in `bucket` with keys
+    /// rendered from this template.
+    pub fn into_uri_iter<B: Into<Bucket>>(self, bucket: B) -> ObjectUriIter {
+        let bucket = bucket.into();
+        let iter = std::iter::repeat_with(move || ObjectUri::new(bucket.clone(), self.render()));
+        ObjectUriIter::new(iter)
+    }
+
+    /// Take the extension from what the encoder records in `info` when the
+    /// template has none.
+    pub(crate) fn encoder_info(self, info: Arc<EncoderInfo>) -> Self {
+        Self {
+            encoder_info: Some(info),
+            ..self
+        }
+    }
+
+    fn extension(&self) -> Option<&str> {
+        self.extension
+            .as_deref()
+            .or_else(|| self.encoder_info.as_deref()?.extension())
+    }
+}
+
+impl FromStr for KeyTemplate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for KeyTemplate {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+/// A part of a key template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Epoch,
+    EpochMillis,
+    Seq(usize),
+    Uuid,
+    Rand(usize),
+    Ext,
+}
+
+impl Segment {
+    /// Parse the placeholder `spec`, which is what is between the braces.
+    ///
+    /// Placeholders with a value that is known now become literals.
+    fn parse(spec: &str) -> Result<Self, String> {
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (spec, None),
+        };
+        let digits = |arg: &str| {
+            arg.parse::<usize>()
+                .map_err(|_| format!("`{{{spec}}}` needs a number after `:`"))
+        };
+        let segment = match (name, arg) {
+            ("yyyy", None) => Self::Year,
+            ("MM", None) => Self::Month,
+            ("dd", None) => Self::Day,
+            ("HH", None) => Self::Hour,
+            ("mm", None) => Self::Minute,
+            ("ss", None) => Self::Second,
+            ("epoch", None) => Self::Epoch,
+            ("epoch_ms", None) => Self::EpochMillis,
+            ("seq", None) => Self::Seq(0),
+            ("seq", Some(arg)) => Self::Seq(digits(arg)?),
+            ("uuid", None) => Self::Uuid,
+            ("rand", None) => Self::Rand(DEFAULT_RAND_DIGITS),
+            ("rand", Some(arg)) => match digits(arg)? {
+                0 => return Err(format!("`{{{spec}}}` has no digits")),
+                n => Self::Rand(n),
+            },
+            ("ext", None) => Self::Ext,
+            ("hostname", None) => Self::Literal(hostname()?),
+            ("env", Some(var)) => match std::env::var(var) {
+                Ok(value) => Self::Literal(value),
+                Err(e) => return Err(format!("environment variable {var:?}: {e}")),
+            },
+            _ => return Err(format!("unknown placeholder `{{{spec}}}`")),
+        };
+        Ok(segment)
+    }
+}
+
+/// The name of this host, from `HOSTNAME` or else `/etc/hostname`.
+fn hostname() -> Result<String, String> {
+    if let Ok(name) = std::env::var("HOSTNAME")
+        && !name.is_empty()
+    {
+        return Ok(name);
+    }
+    std::fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| "hostname not found, set `HOSTNAME`".into())
+}
+
+/// Write a random version 4 UUID.
+fn write_uuid(out: &mut String) -> std::fmt::Result {
+    let mut n = fastrand::u128(..);
+    n = (n & !(0xf << 76)) | (0x4 << 76);
+    n = (n & !(0x3 << 62)) | (0x2 << 62);
+    let hex = format!("{n:032x}");
+    write!(
+        out,
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Write `digits` random hex digits.
+fn write_rand(out: &mut String, digits: usize) -> std::fmt::Result {
+    for _ in 0..digits {
+        out.push(char::from_digit(fastrand::u32(..16), 16).unwrap_or('0'));
+    }
+    Ok(())
+}
+
+/// The calendar date and time of a timestamp.
+struct DateTime {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+}
+
+impl DateTime {
+    /// The date and time `secs` seconds after the Unix epoch.
+    fn from_unix(secs: i64) -> Self {
+        let (days, time) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    use std::time::Duration;
+
+    const DATE_TIME: &str = "{yyyy}-{MM}-{dd}T{HH}:{mm}:{ss}";
+
+    fn at(secs: i64) -> SystemTime {
+        match secs {
+            0.. => UNIX_EPOCH + Duration::from_secs(secs as u64),
+            _ => UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()),
+        }
+    }
+
+    fn render(template: &str, secs: i64) -> String {
+        KeyTemplate::parse(template)
+            .unwrap()
+            .render_at(at(secs))
+            .to_string()
+    }
+
+    #[test]
+    fn render_epoch() {
+        assert_eq!(render(DATE_TIME, 0), "1970-01-01T00:00:00");
+        assert_eq!(render("{epoch}/{epoch_ms}", 0), "0/0");
+    }
+
+    #[test]
+    fn render_leap_day() {
+        assert_eq!(render(DATE_TIME, 1_709_210_096), "2024-02-29T12:34:56");
+        assert_eq!(render(DATE_TIME, 1_709_251_199), "2024-02-29T23:59:59");
+        assert_eq!(render(DATE_TIME, 1_709_251_200), "2024-03-01T00:00:00");
+        assert_eq!(render(DATE_TIME, 951_782_400), "2000-02-29T00:00:00");
+    }
+
+    #[test]
+    fn render_before_1970() {
+        assert_eq!(render(DATE_TIME, -1), "1969-12-31T23:59:59");
+        assert_eq!(render(DATE_TIME, -2_203_891_200), "1900-03-01T00:00:00");
+        assert_eq!(render("{epoch}", -1), "-1");
+    }
+
+    #[test]
+    fn render_with_utc_offset() {
+        let template = KeyTemplate::parse(DATE_TIME).unwrap();
+        let india = template.clone().with_utc_offset(5 * 3600 + 1800);
+        let key = india.render_at(at(1_709_236_800));
+        assert_eq!(key.to_string(), "2024-03-01T01:30:00");
+        let west = template.with_utc_offset(-3600);
+        assert_eq!(west.render_at(at(0)).to_string(), "1969-12-31T23:00:00");
+    }
+
+    #[test]
+    fn render_escaped_braces() {
+        assert_eq!(render("{{a}}/{{{yyyy}}}", 0), "{a}/{1970}");
+        assert_eq!(render("}}{{", 0), "}{");
+    }
+
+    #[test]
+    fn render_extension() {
+        let template = KeyTemplate::parse("k{ext}").unwrap();
+        assert_eq!(template.render().to_string(), "k");
+        let template = template.with_extension(".csv");
+        assert_eq!(template.render().to_string(), "k.csv");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for template in ["", "{yyyy", "a}", "{rand:0}", "{seq:x}", "{nope}"] {
+            let e = KeyTemplate::parse(template).unwrap_err();
+            assert!(matches!(e.kind(), ErrorKind::Config), "{template}: {e}");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::error::Result;
+
+use futures::future::FusedFuture;
+use futures::ready;
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer for a multipart upload that is able to abort the upload.
+///
+/// Aborting cancels part upload requests that are still in progress, discards
+/// the parts that were uploaded, and sends the request to abort the upload so
+/// that it does not linger in the bucket.
+pub trait MultipartAbort {
+    /// Abort the active upload, if there is one.
+    fn poll_abort(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+}
+
+/// Future for [`abort`](super::EncodedUpload::abort).
+#[must_use = "futures do nothing unless polled"]
+pub struct Abort<'a, Wr: ?Sized> {
+    writer: &'a mut Wr,
+    is_terminated: bool,
+}
+
+impl<Wr: ?Sized + Unpin> Unpin for Abort<'_, Wr> {}
+
+impl<'a, Wr: ?Sized + MultipartAbort + Unpin> Abort<'a, Wr> {
+    pub(crate) fn new(writer: &'a mut Wr) -> Self {
+        Self {
+            writer,
+            is_terminated: false,
+        }
+    }
+}
+
+impl<Wr: ?Sized + MultipartAbort + Unpin> FusedFuture for Abort<'_, Wr> {
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl<Wr: ?Sized + MultipartAbort + Unpin> Future for Abort<'_, Wr> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let out = ready!(Pin::new(&mut *self.writer).poll_abort(cx));
+        self.is_terminated = true;
+        Poll::Ready(out)
+    }
+}
+
+impl<Wr: ?Sized + Debug> Debug for Abort<'_, Wr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Abort")
+            .field("writer", &self.writer)
+            .field("is_terminated", &self.is_terminated)
+            .finish()
+    }
+}
@@ -0,0 +1,190 @@
+use super::{Abort, MultipartAbort, PartSize, UploadSent};
+use crate::client::part::PartBody;
+use crate::client::request::CompletedUpload;
+use crate::error::{Error as UploadError, Result};
+
+use futures::ready;
+use multipart_write::MultipartWrite;
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+
+/// An [`AsyncWrite`] for a single multipart upload.
+///
+/// Bytes written are added to the body of the current part, which is sent to
+/// the uploader `U` when it reaches the target part size.  Shutting down the
+/// writer sends the last part and completes the upload, after which the
+/// [`CompletedUpload`] is available from [`completed`].
+///
+/// Flushing waits for the part upload requests in flight to finish, but the
+/// bytes of a part that has not reached the target size are kept until more
+/// bytes are written or the writer is shut down, because only the last part of
+/// an upload can be smaller than the minimum part size.
+///
+/// Errors from the uploader are returned as an [`io::Error`] wrapping the
+/// [`Error`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::{ByteSize, SdkClient, UploadBuilder};
+/// use tokio::io::AsyncWriteExt as _;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = SdkClient::defaults().await;
+/// let mut writer = UploadBuilder::new(client)
+///     .part_size(ByteSize::mib(8))
+///     .with_uri(("my-bucket", "my/key.tar"))
+///     .build_writer();
+///
+/// let mut file = tokio::fs::File::open("archive.tar").await?;
+/// tokio::io::copy(&mut file, &mut writer).await?;
+/// writer.shutdown().await?;
+///
+/// println!("uploaded {:?}", writer.completed());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`completed`]: UploadWriter::completed
+/// [`Error`]: crate::error::Error
+#[must_use = "writers do nothing unless written to"]
+#[pin_project::pin_project]
+pub struct UploadWriter<U> {
+    #[pin]
+    uploader: U,
+    part_size: PartSize,
+    body: PartBody,
+    parts: u64,
+    completed: Option<CompletedUpload>,
+}
+
+impl<U> UploadWriter<U> {
+    pub(crate) fn new(uploader: U, part_size: PartSize) -> Self {
+        Self {
+            uploader,
+            part_size,
+            body: PartBody::default(),
+            parts: 0,
+            completed: None,
+        }
+    }
+
+    /// Returns the completed upload once the writer has been shut down.
+    pub fn completed(&self) -> Option<&CompletedUpload> {
+        self.completed.as_ref()
+    }
+
+    /// Consume the writer, returning the completed upload if it was shut down.
+    pub fn into_completed(self) -> Option<CompletedUpload> {
+        self.completed
+    }
+
+    /// Abort the upload in progress.
+    ///
+    /// This cancels any part upload requests that are in flight and discards
+    /// the bytes written since the upload started.
+    pub fn abort(&mut self) -> Abort<'_, Self>
+    where
+        U: MultipartAbort,
+        Self: Unpin,
+    {
+        Abort::new(self)
+    }
+
+    fn target(&self) -> usize {
+        self.part_size.target(self.parts).as_u64() as usize
+    }
+}
+
+impl<U> UploadWriter<U>
+where
+    U: MultipartWrite<PartBody, Ret = UploadSent, Error = UploadError, Output = CompletedUpload>,
+{
+    /// Send the body of the current part to the uploader.
+    fn poll_send_body(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut this = self.project();
+        ready!(this.uploader.as_mut().poll_ready(cx)).map_err(io::Error::other)?;
+        let body = std::mem::take(this.body);
+        this.uploader.start_send(body).map_err(io::Error::other)?;
+        *this.parts += 1;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<U> AsyncWrite for UploadWriter<U>
+where
+    U: MultipartWrite<PartBody, Ret = UploadSent, Error = UploadError, Output = CompletedUpload>,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.completed.is_some() {
+            return Poll::Ready(Err(io::Error::other("upload already completed")));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if self.body.size() >= self.target() {
+            ready!(self.as_mut().poll_send_body(cx))?;
+        }
+        // Only take what fits in the part so that it is the target size.
+        let n = buf.len().min(self.target() - self.body.size());
+        self.as_mut().project().body.extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.completed.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+        if self.body.size() >= self.target() {
+            ready!(self.as_mut().poll_send_body(cx))?;
+        }
+        self.project()
+            .uploader
+            .poll_flush(cx)
+            .map_err(io::Error::other)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.completed.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+        // An upload needs at least one part, even if it is empty.
+        if !self.body.is_empty() || self.parts == 0 {
+            ready!(self.as_mut().poll_send_body(cx))?;
+        }
+        let this = self.project();
+        let completed = ready!(this.uploader.poll_complete(cx)).map_err(io::Error::other)?;
+        trace!(uri = ?completed.uri, "completed upload from writer");
+        *this.completed = Some(completed);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<U: MultipartAbort> MultipartAbort for UploadWriter<U> {
+    fn poll_abort(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.project();
+        let out = ready!(this.uploader.poll_abort(cx));
+        *this.body = PartBody::default();
+        *this.parts = 0;
+        Poll::Ready(out)
+    }
+}
+
+impl<U: Debug> Debug for UploadWriter<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadWriter")
+            .field("uploader", &self.uploader)
+            .field("part_size", &self.part_size)
+            .field("body", &self.body)
+            .field("parts", &self.parts)
+            .field("completed", &self.completed)
+            .finish()
+    }
+}
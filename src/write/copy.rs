@@ -0,0 +1,72 @@
+use super::UploadSent;
+use crate::client::request::CopySource;
+use crate::error::Result;
+
+use futures::future::FusedFuture;
+use futures::ready;
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer for a multipart upload that is able to add a part copied from an
+/// existing object.
+///
+/// The copy is made by S3 with an `UploadPartCopy` request, so the data is not
+/// downloaded.  A copied part takes the next part number like any other part,
+/// so it is in the object after the parts sent before it.
+pub trait MultipartCopy {
+    /// Attempt to prepare the writer to copy a part.
+    ///
+    /// This must return `Poll::Ready(Ok(()))` before each call to `start_copy`.
+    fn poll_copy_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+
+    /// Begin sending the request to copy the part from `source`.
+    fn start_copy(self: Pin<&mut Self>, source: CopySource) -> Result<UploadSent>;
+}
+
+/// Future for [`copy_part`](super::EncodedUpload::copy_part).
+#[must_use = "futures do nothing unless polled"]
+pub struct CopyPart<'a, Wr: ?Sized> {
+    writer: &'a mut Wr,
+    source: Option<CopySource>,
+}
+
+impl<Wr: ?Sized + Unpin> Unpin for CopyPart<'_, Wr> {}
+
+impl<'a, Wr: ?Sized + MultipartCopy + Unpin> CopyPart<'a, Wr> {
+    pub(crate) fn new(writer: &'a mut Wr, source: CopySource) -> Self {
+        Self {
+            writer,
+            source: Some(source),
+        }
+    }
+}
+
+impl<Wr: ?Sized + MultipartCopy + Unpin> FusedFuture for CopyPart<'_, Wr> {
+    fn is_terminated(&self) -> bool {
+        self.source.is_none()
+    }
+}
+
+impl<Wr: ?Sized + MultipartCopy + Unpin> Future for CopyPart<'_, Wr> {
+    type Output = Result<UploadSent>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        ready!(Pin::new(&mut *this.writer).poll_copy_ready(cx))?;
+        let source = this
+            .source
+            .take()
+            .expect("polled CopyPart after completion");
+        Poll::Ready(Pin::new(&mut *this.writer).start_copy(source))
+    }
+}
+
+impl<Wr: ?Sized + Debug> Debug for CopyPart<'_, Wr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CopyPart")
+            .field("writer", &self.writer)
+            .field("source", &self.source)
+            .finish()
+    }
+}
@@ -1,16 +1,23 @@
-use super::UploadSent;
+use super::{
+    Abort, CopyPart, MultipartAbort, MultipartCopy, MultipartExpire, PartSize, UploadSent,
+};
+use crate::AWS_MAX_PART_COUNT;
 use crate::client::UploadId;
 use crate::client::part::{PartBody, PartNumber};
-use crate::codec::PartEncoder;
-use crate::error::{Error as UploadError, Result};
+use crate::client::request::CopySource;
+use crate::codec::{EncoderInfo, PartEncoder};
+use crate::error::{Error as UploadError, ErrorRepr, Result};
+use crate::instrument;
 use crate::request::CompletedUpload;
 
 use futures::ready;
 use multipart_write::{FusedMultipartWrite, MultipartWrite};
 use std::fmt::{self, Debug, Formatter};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
+use tokio::time::Sleep;
 
 /// Value returned by the `EncodedUpload` writer.
 #[derive(Debug, Clone)]
@@ -44,34 +51,79 @@ struct UploadState {
     total_bytes: u64,
     total_items: u64,
     total_parts: u64,
+    part_started: Option<Instant>,
+    upload_started: Option<Instant>,
 }
 
 impl UploadState {
-    fn to_status(&self, max_bytes: u64, max_part_bytes: u64, start: Instant) -> Status {
+    fn to_status(&self, max_bytes: u64, part_size: &PartSize, start: Instant) -> Status {
+        let should_upload = self.should_upload(part_size);
         Status {
             id: self.id.clone(),
             part: self.part,
             elapsed: start.elapsed(),
             items: self.total_items,
             bytes: self.total_bytes,
-            should_complete: self.total_bytes >= max_bytes,
+            // The last part allowed has to be the last part of the upload.
+            should_complete: self.total_bytes >= max_bytes
+                || (should_upload && self.at_part_limit()),
             parts: self.total_parts,
             part_bytes: self.part_bytes,
-            should_upload: self.part_bytes >= max_part_bytes,
+            should_upload,
         }
     }
 
+    /// The number of parts in the upload so far, which is greater than the
+    /// number sent by this writer if the upload was resumed.
+    fn parts_sent(&self) -> u64 {
+        self.part.map_or(0, |part| *part as u64)
+    }
+
+    fn should_upload(&self, part_size: &PartSize) -> bool {
+        self.part_bytes >= part_size.target(self.parts_sent()).as_u64()
+    }
+
+    /// Whether the current part is the last one the upload can have.
+    fn at_part_limit(&self) -> bool {
+        self.parts_sent() + 1 >= AWS_MAX_PART_COUNT
+    }
+
+    /// Returns when the upload expires given the maximum age of a part and of
+    /// an upload, which are measured from the first item written to each.
+    fn deadline(
+        &self,
+        part_age: Option<Duration>,
+        upload_age: Option<Duration>,
+    ) -> Option<Instant> {
+        let part = self.part_started.zip(part_age).map(|(t, age)| t + age);
+        let upload = self.upload_started.zip(upload_age).map(|(t, age)| t + age);
+        part.into_iter().chain(upload).min()
+    }
+
     fn update_encode(&mut self, bytes: usize) {
+        let now = Instant::now();
+        self.part_started.get_or_insert(now);
+        self.upload_started.get_or_insert(now);
         let n = bytes as u64;
         self.total_bytes += n;
         self.part_bytes += n;
         self.total_items += 1;
     }
 
+    /// Count a copied part, which doesn't change the part being encoded.
+    fn update_copied(&mut self, sent: UploadSent) {
+        self.upload_started.get_or_insert_with(Instant::now);
+        self.id = Some(sent.id);
+        self.part = Some(sent.part);
+        self.total_bytes += sent.bytes;
+        self.total_parts += 1;
+    }
+
     fn update_sent(&mut self, sent: UploadSent) {
         self.id = Some(sent.id);
         self.part = Some(sent.part);
         self.part_bytes = 0;
+        self.part_started = None;
         self.total_parts += 1;
     }
 }
@@ -83,9 +135,29 @@ impl UploadState {
 /// uploaded according the target part size this value is configured with.
 ///
 /// This writer itself is reusable, i.e., one can continue writing `Item`s after
-/// completing an upload, if and only if `U` is.
+/// completing an upload, if and only if `U` is.  The same is true of aborting
+/// an upload, which discards whatever was encoded for it.
+///
+/// Before the first item of an upload is encoded, the encoder writes the start
+/// of the object with [`begin`], and the last part of the upload is the body
+/// from [`finish`], so formats with a header or a trailer make a valid object.
+/// Completing an upload that nothing was written to uploads the object of no
+/// items, which is the start and end of the object.
+///
+/// The writer can also be configured with a maximum age for a part and for the
+/// upload.  It expires once the first item written to the current part or to
+/// the upload is older than that, which completes the upload when the writer
+/// is used by the stream [`into_upload`], even if no more items arrive.
 ///
+/// The target part size follows a [`PartSize`], which can grow as more parts
+/// are uploaded.  Because an upload can have at most 10,000 parts, the status
+/// indicates the upload should be completed once the last part it can have is
+/// full, and writing more to it is an error.
+///
+/// [`into_upload`]: super::UploadStreamExt::into_upload
 /// [`PartEncoder`]: crate::codec::PartEncoder
+/// [`begin`]: crate::codec::PartEncoder::begin
+/// [`finish`]: crate::codec::PartEncoder::finish
 #[must_use = "futures do nothing unless polled"]
 #[pin_project::pin_project]
 pub struct EncodedUpload<E, U> {
@@ -93,26 +165,156 @@ pub struct EncodedUpload<E, U> {
     uploader: U,
     encoder: E,
     max_bytes: u64,
-    max_part_bytes: u64,
+    part_size: PartSize,
+    max_part_age: Option<Duration>,
+    max_upload_age: Option<Duration>,
+    timer: Option<Pin<Box<Sleep>>>,
+    encoder_info: Option<Arc<EncoderInfo>>,
     start: Instant,
     state: UploadState,
+    error: Option<UploadError>,
     empty: bool,
+    begun: bool,
+    stale: bool,
+    finished: bool,
 }
 
 impl<E, U> EncodedUpload<E, U> {
-    pub(crate) fn new(uploader: U, encoder: E, bytes: u64, part_bytes: u64) -> Self {
+    pub(crate) fn new(uploader: U, encoder: E, bytes: u64, part_size: PartSize) -> Self {
         Self {
             uploader,
             encoder,
             max_bytes: bytes,
-            max_part_bytes: part_bytes,
+            part_size,
+            max_part_age: None,
+            max_upload_age: None,
+            timer: None,
+            encoder_info: None,
             start: Instant::now(),
             state: UploadState::default(),
+            error: None,
             empty: true,
+            begun: false,
+            stale: false,
+            finished: false,
+        }
+    }
+
+    /// Set the maximum time the first item written to a part can wait before
+    /// the upload expires.
+    pub fn max_part_age(self, age: Option<Duration>) -> Self {
+        Self {
+            max_part_age: age,
+            ..self
+        }
+    }
+
+    /// Set the maximum time the first item written to an upload can wait
+    /// before the upload expires.
+    pub fn max_upload_age(self, age: Option<Duration>) -> Self {
+        Self {
+            max_upload_age: age,
+            ..self
+        }
+    }
+
+    /// Report the content type and extension of the encoder in `info`, which
+    /// the upload and its key template read.
+    pub(crate) fn encoder_info(self, info: Arc<EncoderInfo>) -> Self {
+        Self {
+            encoder_info: Some(info),
+            ..self
+        }
+    }
+
+    /// Returns the time at which the upload in progress expires, if the writer
+    /// has a maximum age and something was written to the upload.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.state.deadline(self.max_part_age, self.max_upload_age)
+    }
+
+    /// Abort the upload in progress.
+    ///
+    /// This cancels any part upload requests that are in flight and discards
+    /// the items written since the upload started.
+    pub fn abort(&mut self) -> Abort<'_, Self>
+    where
+        U: MultipartAbort,
+        Self: Unpin,
+    {
+        Abort::new(self)
+    }
+
+    /// Add a part to the upload that is copied from an existing object.
+    ///
+    /// The copy is the next part of the upload, so it comes after the parts
+    /// that were already sent and before the items written since then, which
+    /// are sent in a later part.  To copy it after those items instead, flush
+    /// the writer first.  The size of the copy only counts towards the upload
+    /// size if `source` has a byte range.
+    pub fn copy_part(&mut self, source: CopySource) -> CopyPart<'_, Self>
+    where
+        U: MultipartCopy,
+        Self: Unpin,
+    {
+        CopyPart::new(self, source)
+    }
+
+    /// Forget the upload, restoring the encoder before it is used next.
+    fn discard(self: Pin<&mut Self>) {
+        let this = self.project();
+        *this.state = UploadState::default();
+        *this.start = Instant::now();
+        *this.error = None;
+        *this.empty = true;
+        *this.begun = false;
+        *this.stale = true;
+        *this.finished = false;
+    }
+
+    /// Discard the upload if the error caused it to be aborted.
+    fn check<T>(self: Pin<&mut Self>, res: Result<T>) -> Result<T> {
+        if let Err(e) = &res
+            && e.aborted().is_some()
+        {
+            self.discard();
         }
+        res
     }
 
-    fn poll_send_body<Item>(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>
+    /// Poll the uploader for readiness without sending the part in progress,
+    /// which makes progress on the part upload requests in flight.
+    pub(crate) fn poll_uploader(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>
+    where
+        U: MultipartWrite<PartBody, Error = UploadError>,
+    {
+        let res = ready!(self.as_mut().project().uploader.poll_ready(cx));
+        Poll::Ready(self.check(res))
+    }
+
+    /// Write the start of the object if it has not been written yet,
+    /// returning the number of bytes the encoder wrote.
+    fn begin<Item>(self: Pin<&mut Self>) -> Result<usize>
+    where
+        E: PartEncoder<Item>,
+    {
+        let this = self.project();
+        if *this.begun {
+            return Ok(0);
+        }
+        if let Some(info) = this.encoder_info {
+            info.record::<Item, _>(this.encoder);
+        }
+        let header = this.encoder.begin()?;
+        *this.begun = true;
+        Ok(header)
+    }
+
+    fn poll_send_body<Item>(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        last: bool,
+    ) -> Poll<Result<()>>
     where
         E: PartEncoder<Item>,
         U: MultipartWrite<
@@ -122,22 +324,28 @@ impl<E, U> EncodedUpload<E, U> {
                 Output = CompletedUpload,
             >,
     {
-        let mut this = self.project();
+        let res = ready!(self.as_mut().project().uploader.poll_ready(cx));
+        self.as_mut().check(res)?;
 
-        match this.uploader.as_mut().poll_ready(cx)? {
-            Poll::Ready(()) => {
-                this.encoder.flush()?;
-                let new_encoder = this.encoder.clear()?;
-                let encoder = std::mem::replace(this.encoder, new_encoder);
-                let body = encoder.into_body()?;
-                let ret = this.uploader.as_mut().start_send(body)?;
-                this.state.update_sent(ret);
-                *this.empty = true;
-
-                Poll::Ready(Ok(()))
-            }
-            Poll::Pending => Poll::Pending,
+        let mut this = self.project();
+        this.encoder.flush()?;
+        let new_encoder = this.encoder.clear()?;
+        let encoder = std::mem::replace(this.encoder, new_encoder);
+        let body = if last {
+            encoder.finish()?
+        } else {
+            encoder.into_body()?
+        };
+        *this.empty = true;
+        *this.finished = last;
+        // The end of an object written in parts that were already sent can be
+        // empty, but S3 does not accept an empty part otherwise.
+        if !body.is_empty() || !last || this.state.total_parts == 0 {
+            let ret = this.uploader.as_mut().start_send(body)?;
+            this.state.update_sent(ret);
         }
+
+        Poll::Ready(Ok(()))
     }
 }
 
@@ -166,45 +374,144 @@ where
     type Output = CompletedUpload;
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-        if self.state.part_bytes >= self.max_part_bytes {
-            ready!(self.as_mut().poll_send_body(cx))?;
+        if let Some(e) = self.as_mut().project().error.take() {
+            return Poll::Ready(Err(e));
+        }
+        if self.stale {
+            let this = self.as_mut().project();
+            *this.encoder = this.encoder.restore()?;
+            *this.stale = false;
+        }
+        if self.state.should_upload(&self.part_size) {
+            if self.state.at_part_limit() {
+                return Poll::Ready(Err(ErrorRepr::TooManyParts.into()));
+            }
+            ready!(self.as_mut().poll_send_body(cx, false))?;
         }
         Poll::Ready(Ok(()))
     }
 
-    fn start_send(self: Pin<&mut Self>, part: Item) -> Result<Self::Ret> {
+    fn start_send(mut self: Pin<&mut Self>, part: Item) -> Result<Self::Ret> {
+        let header = self.as_mut().begin::<Item>()?;
         let this = self.project();
-        let bytes = this.encoder.encode(part)?;
+        let res = this.encoder.encode(part);
+        instrument::encoded(res.is_ok());
+        let bytes = header + res?;
         this.state.update_encode(bytes);
         *this.empty = false;
-        let status = this
+        let mut status = this
+            .state
+            .to_status(*this.max_bytes, this.part_size, *this.start);
+        let deadline = this
             .state
-            .to_status(*this.max_bytes, *this.max_part_bytes, *this.start);
+            .deadline(*this.max_part_age, *this.max_upload_age);
+        status.should_complete |= deadline.is_some_and(|t| t <= Instant::now());
         Ok(status)
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if let Some(e) = self.as_mut().project().error.take() {
+            return Poll::Ready(Err(e));
+        }
         if !self.empty {
-            ready!(self.as_mut().poll_send_body(cx))?;
+            ready!(self.as_mut().poll_send_body(cx, false))?;
         }
-        ready!(self.project().uploader.poll_flush(cx))?;
-        Poll::Ready(Ok(()))
+        let res = ready!(self.as_mut().project().uploader.poll_flush(cx));
+        Poll::Ready(self.check(res))
     }
 
     fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Self::Output>> {
-        if !self.empty {
-            ready!(self.as_mut().poll_send_body(cx))?;
+        if let Some(e) = self.as_mut().project().error.take() {
+            return Poll::Ready(Err(e));
         }
-        let mut this = self.project();
-        let out = ready!(this.uploader.as_mut().poll_complete(cx))?;
+        // If nothing was encoded, the parts sent were copied and there is no end
+        // of an object to write, unless there were none and the object is the
+        // one of no items.
+        let trailer = self.begun && self.state.total_parts > 0;
+        let nothing = !self.begun && self.state.total_parts == 0;
+        if !self.finished && (!self.empty || trailer || nothing) {
+            if nothing {
+                if self.stale {
+                    let this = self.as_mut().project();
+                    *this.encoder = this.encoder.restore()?;
+                    *this.stale = false;
+                }
+                let header = self.as_mut().begin::<Item>()?;
+                let this = self.as_mut().project();
+                this.state.update_encode(header);
+                *this.empty = false;
+            }
+            ready!(self.as_mut().poll_send_body(cx, true))?;
+        }
+        let res = ready!(self.as_mut().project().uploader.poll_complete(cx));
+        let out = self.as_mut().check(res)?;
+        let this = self.project();
         let new_encoder = this.encoder.restore()?;
         *this.encoder = new_encoder;
+        *this.begun = false;
+        *this.stale = false;
+        *this.finished = false;
         *this.state = UploadState::default();
         *this.start = Instant::now();
         Poll::Ready(Ok(out))
     }
 }
 
+impl<E, U: MultipartAbort> MultipartAbort for EncodedUpload<E, U> {
+    fn poll_abort(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let out = ready!(self.as_mut().project().uploader.poll_abort(cx));
+        self.discard();
+        Poll::Ready(out)
+    }
+}
+
+impl<E, U: MultipartCopy> MultipartCopy for EncodedUpload<E, U> {
+    fn poll_copy_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let res = ready!(self.as_mut().project().uploader.poll_copy_ready(cx));
+        Poll::Ready(self.check(res))
+    }
+
+    fn start_copy(self: Pin<&mut Self>, source: CopySource) -> Result<UploadSent> {
+        let this = self.project();
+        let sent = this.uploader.start_copy(source)?;
+        this.state.update_copied(sent.clone());
+        Ok(sent)
+    }
+}
+
+impl<E, U> MultipartExpire for EncodedUpload<E, U>
+where
+    U: MultipartWrite<PartBody, Error = UploadError>,
+{
+    fn poll_expired(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Waiting for the next item is also when the requests in flight need to
+        // make progress, so that they finish and release what they hold.  A
+        // request that fails expires the upload, which returns the error when
+        // it is completed.
+        if self.state.total_parts > 0
+            && let Poll::Ready(Err(e)) = self.as_mut().poll_uploader(cx)
+        {
+            *self.as_mut().project().error = Some(e);
+            return Poll::Ready(());
+        }
+        let Some(deadline) = self.deadline() else {
+            return Poll::Pending;
+        };
+        let deadline = tokio::time::Instant::from_std(deadline);
+        let this = self.project();
+        match this.timer {
+            Some(timer) if timer.deadline() == deadline => {}
+            Some(timer) => timer.as_mut().reset(deadline),
+            None => *this.timer = Some(Box::pin(tokio::time::sleep_until(deadline))),
+        }
+        this.timer
+            .as_mut()
+            .expect("timer was set")
+            .as_mut()
+            .poll(cx)
+    }
+}
+
 impl<E, U> Debug for EncodedUpload<E, U>
 where
     E: Debug,
@@ -215,10 +522,18 @@ where
             .field("uploader", &self.uploader)
             .field("encoder", &self.encoder)
             .field("max_bytes", &self.max_bytes)
-            .field("max_part_bytes", &self.max_part_bytes)
+            .field("part_size", &self.part_size)
+            .field("max_part_age", &self.max_part_age)
+            .field("max_upload_age", &self.max_upload_age)
+            .field("timer", &self.timer)
+            .field("encoder_info", &self.encoder_info)
             .field("start", &self.start)
             .field("state", &self.state)
+            .field("error", &self.error)
             .field("empty", &self.empty)
+            .field("begun", &self.begun)
+            .field("stale", &self.stale)
+            .field("finished", &self.finished)
             .finish()
     }
 }
@@ -0,0 +1,25 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer for a multipart upload that can expire, meaning the data written
+/// to it should be uploaded even though no more items have arrived.
+///
+/// Streams created by [`into_upload`] and [`into_upload_when`] poll this when
+/// they are waiting for the next item, and complete the upload when it is
+/// ready.
+///
+/// A writer that never expires can implement this with an empty `impl` block.
+///
+/// [`into_upload`]: super::UploadStreamExt::into_upload
+/// [`into_upload_when`]: super::UploadStreamExt::into_upload_when
+pub trait MultipartExpire {
+    /// Poll for the upload in progress to expire.
+    ///
+    /// This is pending as long as there is no deadline, and otherwise ready
+    /// once the deadline has passed.
+    ///
+    /// The default is always pending, i.e., the upload never expires.
+    fn poll_expired(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Pending
+    }
+}
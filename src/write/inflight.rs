@@ -0,0 +1,152 @@
+use bytesize::ByteSize;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+/// A budget for the bytes of parts that are being uploaded.
+///
+/// The bytes of a part are counted against the budget from when its upload
+/// request is made until the request finishes, whether it succeeded or not,
+/// or until the part is written to disk if parts are staged there.  A
+/// [`PartBuffer`] with a budget waits to accept a new part while the budget
+/// is used up, so that the memory held by part upload requests in flight stays
+/// bounded by the budget plus the size of one part.
+///
+/// Cloning an `InflightBytes` shares the budget, so one budget can limit the
+/// memory of several uploads at once.  A part upload request only makes
+/// progress while its upload is polled, which the streams made by
+/// [`into_upload`] also do while they wait for the next item, so the bytes of
+/// an upload that is idle are released as its requests finish.  An upload that
+/// is not polled at all, like a `MultipartUpload` nothing is being written to,
+/// keeps the bytes of its requests in the budget until it is polled again or
+/// dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::write::InflightBytes;
+/// use aws_multipart_upload::{ByteSize, SdkClient, UploadBuilder};
+///
+/// # async fn example() -> aws_multipart_upload::error::Result<()> {
+/// let client = SdkClient::defaults().await;
+/// let budget = InflightBytes::new(ByteSize::gib(1));
+///
+/// let first = UploadBuilder::new(client.clone())
+///     .inflight_bytes(budget.clone())
+///     .with_uri(("my-bucket", "first/key"))
+///     .build();
+/// let second = UploadBuilder::new(client)
+///     .inflight_bytes(budget)
+///     .with_uri(("my-bucket", "second/key"))
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`PartBuffer`]: super::PartBuffer
+/// [`into_upload`]: super::UploadStreamExt::into_upload
+#[derive(Clone)]
+pub struct InflightBytes(Arc<Budget>);
+
+struct Budget {
+    limit: u64,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    used: u64,
+    waiters: Vec<Waker>,
+}
+
+impl InflightBytes {
+    /// Create a new `InflightBytes` with a budget of `limit` bytes.
+    pub fn new(limit: ByteSize) -> Self {
+        Self(Arc::new(Budget {
+            limit: limit.as_u64(),
+            state: Mutex::default(),
+        }))
+    }
+
+    /// Returns the number of bytes in the budget.
+    pub fn limit(&self) -> ByteSize {
+        ByteSize::b(self.0.limit)
+    }
+
+    /// Returns the number of bytes of the part uploads in flight.
+    pub fn used(&self) -> ByteSize {
+        ByteSize::b(self.state().used)
+    }
+
+    /// Returns `Ready` if the budget is not used up yet, otherwise arranging
+    /// for the current task to be woken when bytes are released.
+    pub(crate) fn poll_available(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state();
+        if state.used < self.0.limit {
+            return Poll::Ready(());
+        }
+        if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    /// Count `bytes` against the budget until the reservation is dropped.
+    pub(crate) fn reserve(&self, bytes: usize) -> Reservation {
+        let bytes = bytes as u64;
+        self.state().used += bytes;
+        Reservation {
+            budget: self.clone(),
+            bytes,
+        }
+    }
+
+    fn release(&self, bytes: u64) {
+        let waiters = {
+            let mut state = self.state();
+            state.used = state.used.saturating_sub(bytes);
+            std::mem::take(&mut state.waiters)
+        };
+        waiters.into_iter().for_each(Waker::wake);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.0.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<ByteSize> for InflightBytes {
+    fn from(limit: ByteSize) -> Self {
+        Self::new(limit)
+    }
+}
+
+impl Debug for InflightBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InflightBytes")
+            .field("limit", &self.limit())
+            .field("used", &self.used())
+            .finish()
+    }
+}
+
+/// Bytes counted against an [`InflightBytes`] budget, which are released when
+/// this is dropped.
+pub(crate) struct Reservation {
+    budget: InflightBytes,
+    bytes: u64,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        self.budget.release(self.bytes);
+    }
+}
+
+impl Debug for Reservation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reservation")
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
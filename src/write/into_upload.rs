@@ -0,0 +1,140 @@
+use super::MultipartExpire;
+
+use futures::ready;
+use futures::stream::{FusedStream, Stream};
+use multipart_write::FusedMultipartWrite;
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Stream of results from sending an input stream to a multipart upload.
+///
+/// An upload is completed, and its result is the next item in the stream, when
+/// the closure returns true for the value returned by writing an item, when
+/// the writer expires while waiting for the next item, and when the input
+/// stream is exhausted.
+#[must_use = "streams do nothing unless polled"]
+#[pin_project::pin_project]
+pub struct IntoUpload<St: Stream, U, F> {
+    #[pin]
+    stream: St,
+    #[pin]
+    writer: U,
+    buffered: Option<St::Item>,
+    f: F,
+    state: State,
+    empty: bool,
+    is_terminated: bool,
+}
+
+impl<St: Stream, U, F> IntoUpload<St, U, F> {
+    pub(crate) fn new(stream: St, writer: U, f: F) -> Self {
+        Self {
+            stream,
+            writer,
+            buffered: None,
+            f,
+            state: State::PollNext,
+            empty: true,
+            is_terminated: false,
+        }
+    }
+}
+
+impl<St, U, F> FusedStream for IntoUpload<St, U, F>
+where
+    St: Stream,
+    U: FusedMultipartWrite<St::Item> + MultipartExpire,
+    F: FnMut(&U::Ret) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl<St, U, F> Stream for IntoUpload<St, U, F>
+where
+    St: Stream,
+    U: FusedMultipartWrite<St::Item> + MultipartExpire,
+    F: FnMut(&U::Ret) -> bool,
+{
+    type Item = Result<U::Output, U::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if this.buffered.is_some() {
+                ready!(this.writer.as_mut().poll_ready(cx))?;
+                let it = this.buffered.take().expect("checked above");
+                let ret = this.writer.as_mut().start_send(it)?;
+                *this.empty = false;
+                *this.state = if (this.f)(&ret) {
+                    State::PollComplete(false)
+                } else {
+                    State::PollNext
+                };
+            }
+
+            match *this.state {
+                State::PollNext => match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(it)) => *this.buffered = Some(it),
+                    Poll::Ready(None) if *this.empty => {
+                        *this.is_terminated = true;
+                        return Poll::Ready(None);
+                    }
+                    Poll::Ready(None) => *this.state = State::PollComplete(true),
+                    Poll::Pending => {
+                        // Nothing to upload, so nothing to expire.
+                        if *this.empty {
+                            return Poll::Pending;
+                        }
+                        ready!(this.writer.as_mut().poll_expired(cx));
+                        trace!("upload expired waiting for the next item");
+                        *this.state = State::PollComplete(false);
+                    }
+                },
+                State::PollComplete(last) => {
+                    let out = ready!(this.writer.as_mut().poll_complete(cx));
+                    if last || this.writer.is_terminated() {
+                        *this.state = State::Terminated;
+                    } else {
+                        *this.empty = true;
+                        *this.state = State::PollNext;
+                    }
+                    return Poll::Ready(Some(out));
+                }
+                State::Terminated => {
+                    *this.is_terminated = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+impl<St, U, F> Debug for IntoUpload<St, U, F>
+where
+    St: Stream + Debug,
+    St::Item: Debug,
+    U: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoUpload")
+            .field("stream", &self.stream)
+            .field("writer", &self.writer)
+            .field("buffered", &self.buffered)
+            .field("f", &"FnMut(&U::Ret) -> bool")
+            .field("state", &self.state)
+            .field("empty", &self.empty)
+            .field("is_terminated", &self.is_terminated)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    PollNext,
+    PollComplete(bool),
+    Terminated,
+}
@@ -4,46 +4,95 @@
 //! components for building multipart writers like them, and extension traits
 //! for `MultipartWrite` and `Stream` providing useful combinator methods
 //! supporting multipart uploads.
-use crate::client::UploadClient;
 use crate::client::part::{CompletedParts, PartBody};
-use crate::client::request::{CompletedUpload, SendUploadPart};
+use crate::client::request::CompletedUpload;
+use crate::client::{ErasedClient, LocalUploadClient, UploadClient};
 use crate::codec::PartEncoder;
 use crate::error::Error as UploadError;
-use crate::uri::ObjectUriIter;
+use crate::uri::{ObjectUri, ObjectUriIter};
 
 use bytesize::ByteSize;
 use futures::Stream;
-use multipart_write::stream::{Assemble, Assembled};
+use multipart_write::stream::Assemble;
 use multipart_write::{FusedMultipartWrite, MultipartStreamExt as _, MultipartWrite};
+use std::hash::Hash;
+
+mod abort;
+pub use self::abort::{Abort, MultipartAbort};
+
+mod async_write;
+pub use self::async_write::UploadWriter;
+
+mod copy;
+pub use self::copy::{CopyPart, MultipartCopy};
 
 mod encoded;
 pub use self::encoded::{EncodedUpload, Status};
 
+mod expire;
+pub use self::expire::MultipartExpire;
+
+mod inflight;
+pub use self::inflight::InflightBytes;
+pub(crate) use self::inflight::Reservation;
+
+mod into_upload;
+pub use self::into_upload::IntoUpload;
+
 mod part_buffer;
 pub use self::part_buffer::PartBuffer;
 
+mod part_size;
+pub use self::part_size::PartSize;
+
+mod partitioned;
+pub use self::partitioned::{IntoPartitionedUpload, PartitionedUpload};
+
+mod source;
+pub(crate) use self::source::{send_copies, send_file, send_reader};
+
 mod upload;
 pub use self::upload::{Upload, UploadSent};
 
 /// A type for creating, building, and completing a multipart upload.
-pub type MultipartUpload<E> = EncodedUpload<E, Upload<PartBuffer>>;
+///
+/// With the default client [`UploadClient`] this is `Send` as long as the
+/// encoder `E` is, so it can be driven from a spawned task on a multi-threaded
+/// runtime.
+pub type MultipartUpload<E, C = UploadClient> =
+    EncodedUpload<E, Upload<PartBuffer<<C as ErasedClient>::UploadPart>, C>>;
+
+/// `MultipartUpload` without the `Send` requirement.
+pub type LocalMultipartUpload<E> = MultipartUpload<E, LocalUploadClient>;
+
+/// A type for making a multipart upload of the bytes written with
+/// `AsyncWrite`.
+pub type MultipartUploadWriter<C = UploadClient> =
+    UploadWriter<Upload<PartBuffer<<C as ErasedClient>::UploadPart>, C>>;
 
 /// Trait alias for a general form of `MultipartUpload`.
 pub trait AwsMultipartUpload<Item>
 where
-    Self: FusedMultipartWrite<Item, Ret = Status, Error = UploadError, Output = CompletedUpload>,
+    Self: FusedMultipartWrite<Item, Ret = Status, Error = UploadError, Output = CompletedUpload>
+        + MultipartExpire,
 {
 }
 
-impl<Item, E: PartEncoder<Item>> AwsMultipartUpload<Item> for MultipartUpload<E> {}
+impl<Item, E, C> AwsMultipartUpload<Item> for MultipartUpload<E, C>
+where
+    E: PartEncoder<Item>,
+    C: ErasedClient,
+{
+}
 
 /// Extension trait for `MultipartWrite` adding specializations for S3 uploads.
 pub trait UploadWriteExt<Part>: MultipartWrite<Part> {
     /// Returns a new `MultipartWrite` that uploads to a multipart upload, using
     /// this writer as a buffer for request futures.
-    fn upload(self, client: &UploadClient, iter: ObjectUriIter) -> Upload<Self>
+    fn upload<C>(self, client: &C, iter: ObjectUriIter) -> Upload<Self, C>
     where
-        Self: MultipartWrite<SendUploadPart, Error = UploadError, Output = CompletedParts> + Sized,
+        Self: MultipartWrite<C::UploadPart, Error = UploadError, Output = CompletedParts> + Sized,
+        C: ErasedClient,
     {
         Upload::new(self, client, iter)
     }
@@ -53,13 +102,29 @@ pub trait UploadWriteExt<Part>: MultipartWrite<Part> {
     /// is capable of writing.
     ///
     /// [`PartEncoder`]: crate::codec::PartEncoder
-    fn encoded_upload<E>(
+    fn encoded_upload<E, P>(
         self,
         encoder: E,
         bytes: ByteSize,
-        part_bytes: ByteSize,
+        part_size: P,
     ) -> EncodedUpload<E, Self>
     where
+        P: Into<PartSize>,
+        Self: MultipartWrite<
+                PartBody,
+                Ret = UploadSent,
+                Error = UploadError,
+                Output = CompletedUpload,
+            > + Sized,
+    {
+        EncodedUpload::new(self, encoder, bytes.as_u64(), part_size.into())
+    }
+
+    /// Transform this `MultipartWrite` into an `AsyncWrite` that builds parts
+    /// of the target size from the bytes written to it.
+    fn into_async_write<P>(self, part_size: P) -> UploadWriter<Self>
+    where
+        P: Into<PartSize>,
         Self: MultipartWrite<
                 PartBody,
                 Ret = UploadSent,
@@ -67,7 +132,7 @@ pub trait UploadWriteExt<Part>: MultipartWrite<Part> {
                 Output = CompletedUpload,
             > + Sized,
     {
-        EncodedUpload::new(self, encoder, bytes.as_u64(), part_bytes.as_u64())
+        UploadWriter::new(self, part_size.into())
     }
 }
 
@@ -76,9 +141,6 @@ impl<Part, Wr: MultipartWrite<Part>> UploadWriteExt<Part> for Wr {}
 /// Future for the result of collecting a stream into a multipart upload.
 pub type CollectUpload<St, U> = Assemble<St, U>;
 
-/// Stream of results from sending an input stream to a multipart upload.
-pub type IntoUpload<St, U, F> = Assembled<St, U, F>;
-
 /// Extension of `Stream` by methods for uploading it.
 pub trait UploadStreamExt: Stream {
     /// Collect this stream into a multipart upload, returning the result of
@@ -95,6 +157,9 @@ pub trait UploadStreamExt: Stream {
     /// producing the next item in the stream by completing the upload when the
     /// status indicates the upload is complete.
     ///
+    /// The upload is also completed when the uploader expires while waiting
+    /// for the next item, see [`MultipartExpire`].
+    ///
     /// The resulting stream ends when either the input stream is exhausted or
     /// the uploader is unable to start the next upload after producing an item.
     fn into_upload<U>(self, uploader: U) -> IntoUpload<Self, U, fn(&Status) -> bool>
@@ -105,21 +170,45 @@ pub trait UploadStreamExt: Stream {
                 Ret = Status,
                 Error = UploadError,
                 Output = CompletedUpload,
-            >,
+            > + MultipartExpire,
     {
-        self.assembled(uploader, |status| status.should_complete)
+        IntoUpload::new(self, uploader, |status| status.should_complete)
     }
 
     /// Transform the input stream by writing its items to the uploader `U`,
     /// producing the next item in the stream by completing the upload when the
-    /// given closure returns true.
+    /// given closure returns true or when the uploader expires.
     fn into_upload_when<U, F>(self, uploader: U, f: F) -> IntoUpload<Self, U, F>
     where
         Self: Sized,
-        U: FusedMultipartWrite<Self::Item, Error = UploadError, Output = CompletedUpload>,
+        U: FusedMultipartWrite<Self::Item, Error = UploadError, Output = CompletedUpload>
+            + MultipartExpire,
         F: FnMut(&U::Ret) -> bool,
     {
-        self.assembled(uploader, f)
+        IntoUpload::new(self, uploader, f)
+    }
+
+    /// Transform the input stream by writing each item to the upload of its
+    /// key in the [`PartitionedUpload`], producing the result of every upload
+    /// that is completed.
+    ///
+    /// An upload is completed when its status indicates it should be, when it
+    /// expires while waiting for the next item, when it is the least recently
+    /// used one and another has to be opened, and when the input stream is
+    /// exhausted.
+    fn into_partitioned_upload<K, E, F, M, C>(
+        self,
+        upload: PartitionedUpload<K, E, F, M, C>,
+    ) -> IntoPartitionedUpload<Self, K, E, F, M, C>
+    where
+        Self: Sized,
+        K: Eq + Hash + Clone,
+        E: PartEncoder<Self::Item>,
+        F: FnMut(&Self::Item) -> K,
+        M: FnMut(&K) -> ObjectUri,
+        C: ErasedClient,
+    {
+        IntoPartitionedUpload::new(self, upload)
     }
 }
 
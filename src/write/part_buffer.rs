@@ -1,4 +1,5 @@
-use crate::client::part::CompletedParts;
+use super::{InflightBytes, MultipartAbort};
+use crate::client::part::{CompletedPart, CompletedParts};
 use crate::client::request::SendUploadPart;
 use crate::error::{Error as UploadError, Result};
 
@@ -11,28 +12,36 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// Utility `MultipartWrite` for buffering upload request futures.
+///
+/// The type parameter is the part upload request future, which by default is
+/// the `Send` future [`SendUploadPart`].
 #[must_use = "futures do nothing unless polled"]
 #[pin_project::pin_project]
-pub struct PartBuffer {
+pub struct PartBuffer<F = SendUploadPart> {
     #[pin]
-    pending: FuturesUnordered<SendUploadPart>,
+    pending: FuturesUnordered<F>,
     completed: CompletedParts,
     capacity: Option<NonZeroUsize>,
+    inflight: Option<InflightBytes>,
     flushing: bool,
 }
 
-impl PartBuffer {
-    pub(crate) fn new(capacity: Option<usize>) -> Self {
+impl<F> PartBuffer<F> {
+    pub(crate) fn new(capacity: Option<usize>, inflight: Option<InflightBytes>) -> Self {
         Self {
             pending: FuturesUnordered::new(),
             completed: CompletedParts::default(),
             capacity: capacity.and_then(NonZeroUsize::new),
+            inflight,
             flushing: false,
         }
     }
 }
 
-impl MultipartWrite<SendUploadPart> for PartBuffer {
+impl<F> MultipartWrite<F> for PartBuffer<F>
+where
+    F: Future<Output = Result<CompletedPart>>,
+{
     type Ret = ();
     type Output = CompletedParts;
     type Error = UploadError;
@@ -60,14 +69,18 @@ impl MultipartWrite<SendUploadPart> for PartBuffer {
                 Err(e) => return Poll::Ready(Err(e)),
             }
         }
-        if this.capacity.is_none_or(|n| this.pending.len() < n.get()) {
-            Poll::Ready(Ok(()))
-        } else {
-            Poll::Pending
+        if this.capacity.is_some_and(|n| this.pending.len() >= n.get()) {
+            return Poll::Pending;
+        }
+        // The budget is released when requests finish, whether they are ours
+        // or those of another upload sharing it, so this is woken either way.
+        if let Some(inflight) = this.inflight {
+            ready!(inflight.poll_available(cx));
         }
+        Poll::Ready(Ok(()))
     }
 
-    fn start_send(mut self: Pin<&mut Self>, part: SendUploadPart) -> Result<Self::Ret> {
+    fn start_send(mut self: Pin<&mut Self>, part: F) -> Result<Self::Ret> {
         self.as_mut().pending.push(part);
         Ok(())
     }
@@ -104,12 +117,24 @@ impl MultipartWrite<SendUploadPart> for PartBuffer {
     }
 }
 
-impl Debug for PartBuffer {
+impl<F> MultipartAbort for PartBuffer<F> {
+    fn poll_abort(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.project();
+        // Dropping the futures cancels the requests that are still in flight.
+        this.pending.get_mut().clear();
+        *this.completed = CompletedParts::default();
+        *this.flushing = false;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<F> Debug for PartBuffer<F> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("PartBuffer")
             .field("pending", &self.pending)
             .field("completed", &self.completed)
             .field("capacity", &self.capacity)
+            .field("inflight", &self.inflight)
             .field("flushing", &self.flushing)
             .finish()
     }
@@ -0,0 +1,94 @@
+use crate::{AWS_MAX_PART_COUNT, AWS_MAX_PART_SIZE, AWS_MIN_PART_SIZE};
+
+use bytesize::ByteSize;
+
+/// The target size of the parts of an upload.
+///
+/// A part is uploaded once it reaches the target size.  The size can be fixed,
+/// or it can grow by a factor every so many parts, which allows an upload to be
+/// larger than the maximum number of parts of the initial size would allow.
+/// For instance, 10,000 parts of 5 MiB make an object of about 48 GiB, but when
+/// the part size doubles every 500 parts they make one of more than 5 TiB.
+///
+/// The size is between the minimum and maximum part size that S3 allows.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_multipart_upload::ByteSize;
+/// use aws_multipart_upload::write::PartSize;
+///
+/// let size = PartSize::new(ByteSize::mib(5)).grow(2, 500);
+/// assert_eq!(size.target(499), ByteSize::mib(5));
+/// assert_eq!(size.target(500), ByteSize::mib(10));
+/// assert!(size.max_object_size() >= ByteSize::tib(5));
+///
+/// // A factor of 0 keeps the size fixed.
+/// let fixed = PartSize::new(ByteSize::mib(5)).grow(0, 500);
+/// assert_eq!(fixed.target(500), ByteSize::mib(5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartSize {
+    initial: u64,
+    factor: u64,
+    every: u64,
+}
+
+impl PartSize {
+    /// Create a fixed target part size.
+    pub fn new(size: ByteSize) -> Self {
+        Self {
+            initial: clamp(size.as_u64()),
+            factor: 1,
+            every: 1,
+        }
+    }
+
+    /// Multiply the target size by `factor` after every `every` parts.
+    ///
+    /// Neither can be zero: a `factor` of 0 is taken to be 1, which keeps the
+    /// size fixed, and an `every` of 0 is taken to be 1, which grows the size
+    /// after every part.
+    pub fn grow(self, factor: u64, every: u64) -> Self {
+        Self {
+            factor: factor.max(1),
+            every: every.max(1),
+            ..self
+        }
+    }
+
+    /// Returns the target size of the part after `parts` parts have been
+    /// uploaded.
+    pub fn target(&self, parts: u64) -> ByteSize {
+        let steps = u32::try_from(parts / self.every).unwrap_or(u32::MAX);
+        let scale = self.factor.saturating_pow(steps);
+        ByteSize::b(clamp(self.initial.saturating_mul(scale)))
+    }
+
+    /// Returns the size of the largest object that can be uploaded in parts of
+    /// this size without exceeding the maximum number of parts.
+    pub fn max_object_size(&self) -> ByteSize {
+        let bytes = (0..AWS_MAX_PART_COUNT)
+            .fold(0u64, |acc, n| acc.saturating_add(self.target(n).as_u64()));
+        ByteSize::b(bytes)
+    }
+}
+
+impl Default for PartSize {
+    fn default() -> Self {
+        Self::new(crate::DEFAULT_MAX_PART_SIZE)
+    }
+}
+
+impl From<ByteSize> for PartSize {
+    fn from(value: ByteSize) -> Self {
+        Self::new(value)
+    }
+}
+
+// Clamp to AWS_MIN <= size <= min(AWS_MAX, usize::MAX).
+fn clamp(size: u64) -> u64 {
+    size.max(AWS_MIN_PART_SIZE.as_u64())
+        .min(AWS_MAX_PART_SIZE.as_u64())
+        .min(usize::MAX as u64)
+}
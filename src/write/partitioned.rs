@@ -0,0 +1,375 @@
+use super::{MultipartExpire, MultipartUpload};
+use crate::UploadBuilder;
+use crate::client::{ErasedClient, UploadClient};
+use crate::codec::PartEncoder;
+use crate::error::{Error as UploadError, Result};
+use crate::request::CompletedUpload;
+use crate::uri::ObjectUri;
+
+use futures::ready;
+use futures::stream::{FusedStream, Stream};
+use multipart_write::MultipartWrite;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::Hash;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Default maximum number of uploads a `PartitionedUpload` has open at once.
+const DEFAULT_MAX_OPEN: usize = 16;
+
+/// A set of multipart uploads that items are routed to by key.
+///
+/// Every item written goes to the upload of its key, which is computed from
+/// the item by the key function.  The first item of a key that has no upload
+/// open starts one, to the object URI the key maps to, and it stays open until
+/// its status says it should be completed, it expires, or the input ends.  All
+/// of the uploads are made with the client and configuration of the
+/// [`UploadBuilder`] this was built from, so they share the client and the
+/// budget of bytes in flight, which the builder is required to have.
+///
+/// At most [`max_open`] uploads are open at one time.  When a new key needs an
+/// upload and the limit is reached, the upload that was written to least
+/// recently is completed to make room.  A key that comes back after its upload
+/// was completed starts a new upload to the URI it maps to at that time, so the
+/// URI should usually be unique, e.g. with a timestamp in the key.
+///
+/// The uploads are driven by the stream from [`into_partitioned_upload`],
+/// which produces the result of every upload that is completed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use aws_multipart_upload::codec::JsonLinesEncoder;
+/// use aws_multipart_upload::write::UploadStreamExt as _;
+/// use aws_multipart_upload::{ByteSize, ObjectUri, SdkClient, UploadBuilder};
+/// use futures::{StreamExt as _, stream};
+/// use serde_json::{Value, json};
+///
+/// # async fn example() -> aws_multipart_upload::error::Result<()> {
+/// let client = SdkClient::defaults().await;
+/// let upload = UploadBuilder::new(client)
+///     .max_inflight_bytes(ByteSize::gib(1))
+///     .with_encoder(JsonLinesEncoder::new())
+///     .build_partitioned(
+///         |item: &Value| item["tenant"].as_str().unwrap_or_default().to_string(),
+///         |tenant: &String| {
+///             let ts = std::time::UNIX_EPOCH.elapsed().unwrap_or_default().as_millis();
+///             ObjectUri::from(("my-bucket", format!("tenant={tenant}/{ts}.jsonl")))
+///         },
+///     )?
+///     .max_open(100);
+///
+/// let items = stream::iter([json!({"tenant": "a"}), json!({"tenant": "b"})]);
+/// let mut completed = items.into_partitioned_upload(upload);
+/// while let Some(res) = completed.next().await {
+///     println!("uploaded {}", res?.uri);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`max_open`]: PartitionedUpload::max_open
+/// [`into_partitioned_upload`]: super::UploadStreamExt::into_partitioned_upload
+pub struct PartitionedUpload<K, E, F, M, C: ErasedClient = UploadClient> {
+    builder: UploadBuilder<E, C>,
+    key: F,
+    uri: M,
+    open: HashMap<K, OpenUpload<E, C>>,
+    closing: Vec<Pin<Box<MultipartUpload<E, C>>>>,
+    max_open: usize,
+    used: u64,
+}
+
+/// An upload that is open for writing.
+struct OpenUpload<E, C: ErasedClient> {
+    writer: Pin<Box<MultipartUpload<E, C>>>,
+    used: u64,
+}
+
+impl<K, E, F, M, C: ErasedClient> PartitionedUpload<K, E, F, M, C> {
+    pub(crate) fn new(builder: UploadBuilder<E, C>, key: F, uri: M) -> Self {
+        Self {
+            builder,
+            key,
+            uri,
+            open: HashMap::new(),
+            closing: Vec::new(),
+            max_open: DEFAULT_MAX_OPEN,
+            used: 0,
+        }
+    }
+
+    /// Set the maximum number of uploads that are open at one time.
+    ///
+    /// The default is 16.
+    pub fn max_open(self, limit: usize) -> Self {
+        Self {
+            max_open: limit.max(1),
+            ..self
+        }
+    }
+
+    /// Returns the number of uploads that are open for writing.
+    pub fn open_uploads(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Poll the uploads being completed, returning the first result.
+    fn poll_closing<Item>(&mut self, cx: &mut Context<'_>) -> Poll<Result<CompletedUpload>>
+    where
+        E: PartEncoder<Item>,
+    {
+        for i in 0..self.closing.len() {
+            let writer = self.closing[i].as_mut();
+            if let Poll::Ready(res) = MultipartWrite::<Item>::poll_complete(writer, cx) {
+                drop(self.closing.swap_remove(i));
+                return Poll::Ready(res);
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Start completing every open upload.
+    fn close_all(&mut self) {
+        let open = self.open.drain().map(|(_, open)| open.writer);
+        self.closing.extend(open);
+    }
+}
+
+impl<K, E, F, M, C> PartitionedUpload<K, E, F, M, C>
+where
+    K: Eq + Hash + Clone,
+    C: ErasedClient,
+{
+    /// Start completing the upload of `key`.
+    fn close(&mut self, key: &K) {
+        if let Some(open) = self.open.remove(key) {
+            self.closing.push(open.writer);
+        }
+    }
+
+    /// Poll the open uploads so that their part upload requests make progress,
+    /// returning the first error and dropping the upload it came from.
+    fn poll_open(&mut self, cx: &mut Context<'_>) -> Option<UploadError> {
+        let (key, e) = self.open.iter_mut().find_map(|(key, open)| {
+            match open.writer.as_mut().poll_uploader(cx) {
+                Poll::Ready(Err(e)) => Some((key.clone(), e)),
+                _ => None,
+            }
+        })?;
+        self.open.remove(&key);
+        Some(e)
+    }
+
+    /// Start completing the uploads that expired.
+    fn close_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        let expired = self
+            .open
+            .iter_mut()
+            .filter_map(|(key, open)| {
+                open.writer
+                    .as_mut()
+                    .poll_expired(cx)
+                    .is_ready()
+                    .then_some(key)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        expired.iter().for_each(|key| self.close(key));
+        !expired.is_empty()
+    }
+
+    /// Returns the upload for `key`, starting a new one if it is not open.
+    fn writer<Item>(&mut self, key: &K) -> Result<Pin<&mut MultipartUpload<E, C>>>
+    where
+        E: PartEncoder<Item>,
+        M: FnMut(&K) -> ObjectUri,
+    {
+        self.used += 1;
+        if !self.open.contains_key(key) {
+            if self.open.len() >= self.max_open
+                && let Some(lru) = self
+                    .open
+                    .iter()
+                    .min_by_key(|(_, open)| open.used)
+                    .map(|(key, _)| key.clone())
+            {
+                trace!("completing least recently used upload");
+                self.close(&lru);
+            }
+            let encoder = self.builder.encoder.restore()?;
+            let uri = (self.uri)(key);
+            trace!(?uri, "opening partition upload");
+            let writer = Box::pin(self.builder.object_upload(encoder, uri));
+            self.open
+                .insert(key.clone(), OpenUpload { writer, used: 0 });
+        }
+        let open = self.open.get_mut(key).expect("inserted above");
+        open.used = self.used;
+        Ok(open.writer.as_mut())
+    }
+}
+
+impl<K, E, F, M, C> Debug for PartitionedUpload<K, E, F, M, C>
+where
+    K: Debug,
+    E: Debug,
+    C: ErasedClient,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartitionedUpload")
+            .field("builder", &self.builder)
+            .field("key", &"FnMut(&Item) -> K")
+            .field("uri", &"FnMut(&K) -> ObjectUri")
+            .field("open", &self.open.keys().collect::<Vec<_>>())
+            .field("closing", &self.closing.len())
+            .field("max_open", &self.max_open)
+            .field("used", &self.used)
+            .finish()
+    }
+}
+
+/// Stream of the uploads completed by writing an input stream to a
+/// [`PartitionedUpload`].
+///
+/// The stream ends when the input stream is exhausted and all of the uploads
+/// that were open are completed.  Errors are produced as items of the stream.
+/// An error encoding an item does not affect the upload it was written to, but
+/// after any other error the upload it came from is dropped, so the next item
+/// with its key starts a new upload.
+#[must_use = "streams do nothing unless polled"]
+#[pin_project::pin_project]
+pub struct IntoPartitionedUpload<St: Stream, K, E, F, M, C: ErasedClient = UploadClient> {
+    #[pin]
+    stream: St,
+    upload: PartitionedUpload<K, E, F, M, C>,
+    buffered: Option<(K, St::Item)>,
+    exhausted: bool,
+    is_terminated: bool,
+}
+
+impl<St: Stream, K, E, F, M, C: ErasedClient> IntoPartitionedUpload<St, K, E, F, M, C> {
+    pub(crate) fn new(stream: St, upload: PartitionedUpload<K, E, F, M, C>) -> Self {
+        Self {
+            stream,
+            upload,
+            buffered: None,
+            exhausted: false,
+            is_terminated: false,
+        }
+    }
+
+    /// Returns a reference to the `PartitionedUpload`.
+    pub fn get_ref(&self) -> &PartitionedUpload<K, E, F, M, C> {
+        &self.upload
+    }
+}
+
+impl<St, K, E, F, M, C> FusedStream for IntoPartitionedUpload<St, K, E, F, M, C>
+where
+    St: Stream,
+    K: Eq + Hash + Clone,
+    E: PartEncoder<St::Item>,
+    F: FnMut(&St::Item) -> K,
+    M: FnMut(&K) -> ObjectUri,
+    C: ErasedClient,
+{
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl<St, K, E, F, M, C> Stream for IntoPartitionedUpload<St, K, E, F, M, C>
+where
+    St: Stream,
+    K: Eq + Hash + Clone,
+    E: PartEncoder<St::Item>,
+    F: FnMut(&St::Item) -> K,
+    M: FnMut(&K) -> ObjectUri,
+    C: ErasedClient,
+{
+    type Item = Result<CompletedUpload>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let upload = this.upload;
+
+        loop {
+            if let Poll::Ready(res) = upload.poll_closing::<St::Item>(cx) {
+                return Poll::Ready(Some(res));
+            }
+
+            // Requests in flight for any upload hold the shared budget, so all
+            // of them have to make progress for any one of them to.
+            if let Some(e) = upload.poll_open(cx) {
+                return Poll::Ready(Some(Err(e)));
+            }
+
+            if let Some((key, _)) = this.buffered.as_ref() {
+                let key = key.clone();
+                let mut writer = match upload.writer::<St::Item>(&key) {
+                    Ok(writer) => writer,
+                    Err(e) => {
+                        this.buffered.take();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                };
+                // An error here drops the upload but keeps the item for a new
+                // upload of its key.
+                if let Err(e) = ready!(MultipartWrite::<St::Item>::poll_ready(writer.as_mut(), cx))
+                {
+                    upload.open.remove(&key);
+                    return Poll::Ready(Some(Err(e)));
+                }
+                let (_, item) = this.buffered.take().expect("checked above");
+                if writer.start_send(item)?.should_complete {
+                    upload.close(&key);
+                }
+            }
+
+            if *this.exhausted {
+                if upload.closing.is_empty() {
+                    *this.is_terminated = true;
+                    return Poll::Ready(None);
+                }
+                return Poll::Pending;
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let key = (upload.key)(&item);
+                    *this.buffered = Some((key, item));
+                }
+                Poll::Ready(None) => {
+                    *this.exhausted = true;
+                    upload.close_all();
+                }
+                Poll::Pending => {
+                    if !upload.close_expired(cx) {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<St, K, E, F, M, C> Debug for IntoPartitionedUpload<St, K, E, F, M, C>
+where
+    St: Stream + Debug,
+    St::Item: Debug,
+    K: Debug,
+    E: Debug,
+    C: ErasedClient,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoPartitionedUpload")
+            .field("stream", &self.stream)
+            .field("upload", &self.upload)
+            .field("buffered", &self.buffered)
+            .field("exhausted", &self.exhausted)
+            .field("is_terminated", &self.is_terminated)
+            .finish()
+    }
+}
@@ -0,0 +1,188 @@
+use super::{MultipartAbort, MultipartCopy, PartSize, UploadSent};
+use crate::AWS_MAX_PART_COUNT;
+use crate::client::part::PartBody;
+use crate::client::request::{CompletedUpload, CopySource};
+use crate::error::{Error as UploadError, ErrorRepr, FailedUpload, Result};
+
+use bytes::BytesMut;
+use futures::future::poll_fn;
+use futures::stream::{self, Stream, StreamExt as _};
+use multipart_write::MultipartWrite;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::task::Poll;
+use tokio::io::{AsyncRead, AsyncReadExt as _, AsyncSeekExt as _};
+
+/// Upload the file at `path` in parts sized by `part_size`, reading up to
+/// `reads` byte ranges of the file at one time.
+pub(crate) async fn send_file<Wr>(
+    writer: Wr,
+    path: &Path,
+    part_size: PartSize,
+    reads: usize,
+    abort: bool,
+) -> Result<CompletedUpload>
+where
+    Wr: MultipartWrite<PartBody, Ret = UploadSent, Error = UploadError, Output = CompletedUpload>
+        + MultipartAbort,
+{
+    let len = tokio::fs::metadata(path)
+        .await
+        .map_err(ErrorRepr::Read)?
+        .len();
+    let ranges = part_ranges(len, part_size)?;
+    trace!(?path, bytes = len, parts = ranges.len(), "uploading file");
+
+    let path = path.to_path_buf();
+    let parts = stream::iter(ranges)
+        .map(move |(offset, size)| read_range(path.clone(), offset, size))
+        .buffered(reads.max(1));
+    send_parts(writer, parts, abort).await
+}
+
+/// Upload the bytes read from `reader` in parts sized by `part_size`.
+pub(crate) async fn send_reader<Wr, R>(
+    writer: Wr,
+    reader: R,
+    part_size: PartSize,
+    abort: bool,
+) -> Result<CompletedUpload>
+where
+    Wr: MultipartWrite<PartBody, Ret = UploadSent, Error = UploadError, Output = CompletedUpload>
+        + MultipartAbort,
+    R: AsyncRead + Unpin,
+{
+    // The state is the reader, the number of parts read, and whether the end
+    // of the reader was reached.
+    let parts = stream::unfold((reader, 0, false), move |(mut reader, n, eof)| async move {
+        if eof {
+            return None;
+        }
+        let size = part_size.target(n).as_u64();
+        let res = read_part(&mut reader, size).await;
+        match res {
+            // A reader whose size is a multiple of the part size ends with an
+            // empty read, which is only a part if it is the only one.
+            Ok(body) if body.is_empty() && n > 0 => None,
+            Ok(body) => {
+                let eof = (body.size() as u64) < size;
+                Some((Ok(body), (reader, n + 1, eof)))
+            }
+            Err(e) => Some((Err(e), (reader, n + 1, true))),
+        }
+    });
+    send_parts(writer, parts, abort).await
+}
+
+/// Copy the parts of the upload from `sources` and complete it.
+pub(crate) async fn send_copies<Wr, I>(writer: Wr, sources: I) -> Result<CompletedUpload>
+where
+    Wr: MultipartWrite<PartBody, Error = UploadError, Output = CompletedUpload> + MultipartCopy,
+    I: IntoIterator<Item = CopySource>,
+{
+    let mut writer = pin!(writer);
+    for source in sources {
+        poll_fn(|cx| writer.as_mut().poll_copy_ready(cx)).await?;
+        writer.as_mut().start_copy(source)?;
+    }
+    poll_fn(|cx| writer.as_mut().poll_complete(cx)).await
+}
+
+/// Send the parts from the stream to the writer and complete the upload.
+///
+/// The writer is polled while waiting for the next part so that the part
+/// uploads in progress are not held up by reading the source.
+async fn send_parts<Wr, St>(writer: Wr, parts: St, abort: bool) -> Result<CompletedUpload>
+where
+    Wr: MultipartWrite<PartBody, Ret = UploadSent, Error = UploadError, Output = CompletedUpload>
+        + MultipartAbort,
+    St: Stream<Item = Result<PartBody>>,
+{
+    let mut writer = pin!(writer);
+    let mut parts = pin!(parts);
+    let mut buffered = None;
+    let mut last_sent: Option<UploadSent> = None;
+    let mut exhausted = false;
+    let mut read_failed = false;
+
+    let res = poll_fn(|cx| {
+        loop {
+            if exhausted {
+                return writer.as_mut().poll_complete(cx);
+            }
+            if buffered.is_some() {
+                futures::ready!(writer.as_mut().poll_ready(cx))?;
+                let part = buffered.take().expect("checked above");
+                last_sent = Some(writer.as_mut().start_send(part)?);
+            }
+            match parts.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(part))) => buffered = Some(part),
+                Poll::Ready(Some(Err(e))) => {
+                    read_failed = true;
+                    return Poll::Ready(Err(e));
+                }
+                Poll::Ready(None) => exhausted = true,
+                Poll::Pending => {
+                    futures::ready!(writer.as_mut().poll_ready(cx))?;
+                    return Poll::Pending;
+                }
+            }
+        }
+    })
+    .await;
+
+    // An error reading the source leaves the upload in progress, so abort it
+    // if configured to, like the writer does with errors of its own.
+    match res {
+        Err(e) if abort && read_failed => {
+            let aborted = poll_fn(|cx| writer.as_mut().poll_abort(cx)).await;
+            match last_sent {
+                Some(sent) => {
+                    let failed = FailedUpload::new(&sent.id, &sent.uri, sent.part);
+                    Err(ErrorRepr::aborted(failed, e, aborted).into())
+                }
+                None => Err(e),
+            }
+        }
+        res => res,
+    }
+}
+
+/// Split `len` bytes into the offset and size of each part.
+fn part_ranges(len: u64, part_size: PartSize) -> Result<Vec<(u64, u64)>> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    while offset < len {
+        if ranges.len() as u64 == AWS_MAX_PART_COUNT {
+            return Err(ErrorRepr::TooManyParts.into());
+        }
+        let size = part_size.target(ranges.len() as u64).as_u64();
+        let size = size.min(len - offset);
+        ranges.push((offset, size));
+        offset += size;
+    }
+    // An empty file is uploaded as a single empty part.
+    if ranges.is_empty() {
+        ranges.push((0, 0));
+    }
+    Ok(ranges)
+}
+
+async fn read_range(path: PathBuf, offset: u64, size: u64) -> Result<PartBody> {
+    let mut file = tokio::fs::File::open(&path)
+        .await
+        .map_err(ErrorRepr::Read)?;
+    file.seek(SeekFrom::Start(offset))
+        .await
+        .map_err(ErrorRepr::Read)?;
+    read_part(&mut file, size).await
+}
+
+/// Read `size` bytes or up to the end of the reader, whichever is first.
+async fn read_part<R: AsyncRead + Unpin>(reader: &mut R, size: u64) -> Result<PartBody> {
+    let mut body = BytesMut::with_capacity(size as usize);
+    let mut limited = reader.take(size);
+    while limited.read_buf(&mut body).await.map_err(ErrorRepr::Read)? != 0 {}
+    Ok(PartBody::new(body))
+}
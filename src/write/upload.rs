@@ -1,13 +1,22 @@
+use super::{InflightBytes, MultipartAbort, MultipartCopy};
+use crate::AWS_MAX_PART_COUNT;
 use crate::client::part::{CompletedParts, PartBody, PartNumber};
 use crate::client::request::*;
-use crate::client::{UploadClient, UploadData, UploadId};
-use crate::error::{Error as UploadError, Result};
+use crate::client::{
+    BucketAccess, ChecksumAlgorithm, CustomerKey, Encryption, ErasedClient, ObjectAttributes,
+    UploadClient, UploadData, UploadId,
+};
+use crate::codec::EncoderInfo;
+use crate::error::{Error as UploadError, ErrorRepr, FailedUpload, Result};
+use crate::instrument;
 use crate::uri::{ObjectUri, ObjectUriIter};
 
 use futures::ready;
 use multipart_write::{FusedMultipartWrite, MultipartWrite};
 use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// Returned when a part upload request was sent.
@@ -40,45 +49,232 @@ impl UploadSent {
 /// A type to manage the lifecycle of a multipart upload.
 ///
 /// This `MultipartWrite` sends part upload requests from the input [`PartBody`]
-/// and completes the upload when polled for completion.
+/// and completes the upload when polled for completion.  With [`MultipartCopy`]
+/// it also sends requests to copy a part from an existing object, so copied
+/// parts and parts with a body can be mixed in the same upload, in the order
+/// they are sent.
 ///
 /// On completion, a new upload is created using the `ObjectUriIter` it was
 /// configured with, which makes the writer available to continue writing parts
 /// to with a new upload ID.  As long as the iterator `ObjectUriIter` can produce
 /// the next upload, this writer remains active.
 ///
+/// The request futures are made by the client `C`, which by default is the
+/// `Send` client [`UploadClient`].
+///
+/// The active upload can be aborted with [`MultipartAbort`], after which the
+/// writer moves on to the next upload in the same way as on completion.  If
+/// configured with [`abort_on_error`], this also happens when a request fails
+/// and the error is returned.
+///
+/// An upload that was interrupted can be resumed by starting the writer with
+/// its [`UploadData`], in which case the parts already uploaded are listed and
+/// part numbering continues after the last of them.  The `ObjectUriIter` is
+/// used for uploads after that one.
+///
+/// Each upload is created with the [`ObjectAttributes`] that the
+/// `ObjectUriIter` has for the object, filled in from the default attributes
+/// of the writer.
+///
+/// [`abort_on_error`]: Upload::abort_on_error
 /// [`PartBody`]: crate::client::part::PartBody
 /// [`CompletedUpload`]: crate::client::request::CompletedUpload
 #[must_use = "futures do nothing unless polled"]
 #[pin_project::pin_project]
-pub struct Upload<Buf> {
+pub struct Upload<Buf, C: ErasedClient = UploadClient> {
+    #[pin]
+    inner: UploadImpl<Buf, C>,
     #[pin]
-    inner: UploadImpl<Buf>,
+    fut: Option<C::CreateUpload>,
     #[pin]
-    fut: Option<SendCreateUpload>,
-    next_uri: Option<ObjectUri>,
+    list_fut: Option<C::ListParts>,
+    resumed: Option<UploadData>,
+    next_uri: Option<(ObjectUri, ObjectAttributes)>,
     iter: ObjectUriIter,
+    started: bool,
+    attributes: ObjectAttributes,
+    encoder_info: Arc<EncoderInfo>,
 }
 
-impl<Buf> Upload<Buf> {
-    pub(crate) fn new(buf: Buf, client: &UploadClient, mut iter: ObjectUriIter) -> Self {
+impl<Buf, C: ErasedClient> Upload<Buf, C> {
+    pub(crate) fn new(buf: Buf, client: &C, iter: ObjectUriIter) -> Self {
         let inner = UploadImpl::new(buf, client);
-        let fut = iter.next_upload(client);
         Self {
             inner,
-            fut,
+            fut: None,
+            list_fut: None,
+            resumed: None,
             next_uri: None,
             iter,
+            started: false,
+            attributes: ObjectAttributes::default(),
+            encoder_info: Arc::default(),
+        }
+    }
+
+    /// Create the writer starting with the existing upload `data`.
+    pub(crate) fn resume(buf: Buf, client: &C, iter: ObjectUriIter, data: UploadData) -> Self {
+        let inner = UploadImpl::new(buf, client);
+        trace!(id = %data.id, uri = ?data.uri, "resuming upload");
+        Self {
+            inner,
+            fut: None,
+            list_fut: None,
+            resumed: Some(data),
+            next_uri: None,
+            iter,
+            started: true,
+            attributes: ObjectAttributes::default(),
+            encoder_info: Arc::default(),
+        }
+    }
+
+    /// Set whether to abort the active upload when it fails.
+    ///
+    /// The error that caused this reports the outcome of aborting the upload
+    /// in [`Error::aborted`].
+    ///
+    /// [`Error::aborted`]: crate::error::Error::aborted
+    pub fn abort_on_error(self, abort: bool) -> Self {
+        let inner = UploadImpl {
+            abort_on_error: abort,
+            ..self.inner
+        };
+        Self { inner, ..self }
+    }
+
+    /// Set the algorithm to compute part checksums with.
+    ///
+    /// The uploads created by this writer are configured to use the algorithm
+    /// and every part is sent with its checksum.
+    pub fn checksum(self, algorithm: Option<ChecksumAlgorithm>) -> Self {
+        let inner = UploadImpl {
+            checksum: algorithm,
+            ..self.inner
+        };
+        Self { inner, ..self }
+    }
+
+    /// Set the server-side encryption of the uploads created by this writer.
+    ///
+    /// With a key provided by the customer, the key is sent with every part
+    /// and with the request to complete the upload too.
+    pub fn encryption(self, encryption: Option<Encryption>) -> Self {
+        let inner = UploadImpl {
+            encryption,
+            ..self.inner
+        };
+        Self { inner, ..self }
+    }
+
+    /// Set the default attributes of the objects uploaded by this writer.
+    ///
+    /// The attributes that the `ObjectUriIter` has for an object take
+    /// precedence over these.  The bucket access of the defaults is also used
+    /// for an upload that is resumed.
+    pub fn attributes(self, attributes: ObjectAttributes) -> Self {
+        Self { attributes, ..self }
+    }
+
+    /// Set where to find the content type to use when no attributes have one,
+    /// which the encoder writing to this upload records.
+    pub(crate) fn encoder_info(self, info: Arc<EncoderInfo>) -> Self {
+        Self {
+            encoder_info: info,
+            ..self
+        }
+    }
+
+    /// Set the budget that the bytes of the parts being uploaded count against,
+    /// which should be the one the `PartBuffer` waits on.
+    pub(crate) fn inflight_bytes(self, budget: Option<InflightBytes>) -> Self {
+        let inner = UploadImpl {
+            inflight: budget,
+            ..self.inner
+        };
+        Self { inner, ..self }
+    }
+
+    /// Set a directory to stage parts in before they are uploaded.
+    ///
+    /// When this is set, the body of every part is written to a file in the
+    /// directory and the request to upload the part reads it from the file,
+    /// so the part is not held in memory while the request is in flight.  The
+    /// file is deleted when the request has finished.
+    pub fn stage_parts_in(self, dir: Option<PathBuf>) -> Self {
+        let inner = UploadImpl {
+            stage_dir: dir,
+            ..self.inner
+        };
+        Self { inner, ..self }
+    }
+
+    /// If the upload was aborted because of the error, move on to the next.
+    fn on_error(self: Pin<&mut Self>, e: &UploadError) {
+        let this = self.project();
+        if e.aborted().is_some() && this.next_uri.is_none() {
+            *this.started = true;
+            *this.next_uri = this.iter.next_object();
         }
     }
 
     fn poll_new_upload(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
         let mut this = self.project();
 
-        if let Some(uri) = this.next_uri.take() {
+        // The first URI is taken only now so that a key template can use what
+        // the encoder has recorded by the time the first part is ready.
+        if !*this.started {
+            *this.started = true;
+            *this.next_uri = this.iter.next_object();
+        }
+
+        // The parts of a resumed upload are listed with the key and bucket
+        // access that the rest of its requests are sent with.
+        if this.list_fut.is_none()
+            && let Some(data) = this.resumed.as_ref()
+        {
+            let access = this.attributes.bucket_access().clone();
+            let req = ListPartsRequest::new(data)
+                .with_customer_key(customer_key(&this.inner.encryption))
+                .with_bucket_access(access.clone());
+            let fut = this.inner.client.list_parts(req);
+            this.list_fut.set(Some(fut));
+            this.inner.as_mut().set_access(access);
+        }
+
+        if let Some(fut) = this.list_fut.as_mut().as_pin_mut() {
+            let res = ready!(fut.poll(cx));
+            this.list_fut.set(None);
+            let data = this.resumed.take().expect("resumed upload without data");
+            let parts = res?;
+            trace!(
+                id = %data.id,
+                uri = ?data.uri,
+                parts = parts.count(),
+                "resumed upload",
+            );
+            this.inner.as_mut().resume(data, parts);
+        }
+
+        if let Some((uri, attributes)) = this.next_uri.take() {
             trace!(?uri, "starting new upload");
-            let req = CreateRequest::new(uri);
-            let fut = SendCreateUpload::new(&this.inner.client, req);
+            let mut attributes = attributes.or(this.attributes);
+            if attributes.content_type().is_none()
+                && let Some(content_type) = this.encoder_info.content_type()
+            {
+                attributes = attributes.with_content_type(content_type);
+            }
+            this.inner
+                .as_mut()
+                .set_access(attributes.bucket_access().clone());
+            let mut req = CreateRequest::new(uri).with_attributes(attributes);
+            if let Some(algorithm) = this.inner.checksum {
+                req = req.with_checksum(algorithm);
+            }
+            if let Some(encryption) = &this.inner.encryption {
+                req = req.with_encryption(encryption.clone());
+            }
+            let fut = this.inner.client.create_upload(req);
             this.fut.set(Some(fut));
         }
 
@@ -100,20 +296,29 @@ impl<Buf> Upload<Buf> {
     }
 }
 
-impl<Buf> FusedMultipartWrite<PartBody> for Upload<Buf>
+impl<Buf, C> FusedMultipartWrite<PartBody> for Upload<Buf, C>
 where
-    Buf: MultipartWrite<SendUploadPart, Output = CompletedParts, Error = UploadError>,
+    Buf: MultipartWrite<C::UploadPart, Output = CompletedParts, Error = UploadError>
+        + MultipartAbort,
+    C: ErasedClient,
 {
     fn is_terminated(&self) -> bool {
         // If the inner upload is not active, and there is no request for a new
         // upload nor next URI to make the request, we are terminated.
-        self.inner.is_terminated() && self.fut.is_none() && self.next_uri.is_none()
+        self.inner.is_terminated()
+            && self.fut.is_none()
+            && self.list_fut.is_none()
+            && self.resumed.is_none()
+            && self.started
+            && self.next_uri.is_none()
     }
 }
 
-impl<Buf> MultipartWrite<PartBody> for Upload<Buf>
+impl<Buf, C> MultipartWrite<PartBody> for Upload<Buf, C>
 where
-    Buf: MultipartWrite<SendUploadPart, Error = UploadError, Output = CompletedParts>,
+    Buf: MultipartWrite<C::UploadPart, Error = UploadError, Output = CompletedParts>
+        + MultipartAbort,
+    C: ErasedClient,
 {
     type Ret = UploadSent;
     type Error = UploadError;
@@ -121,34 +326,105 @@ where
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
         ready!(self.as_mut().poll_new_upload(cx))?;
-        self.project().inner.poll_ready(cx)
+        let out = ready!(self.as_mut().project().inner.poll_ready(cx));
+        if let Err(e) = &out {
+            self.on_error(e);
+        }
+        Poll::Ready(out)
     }
 
     fn start_send(self: Pin<&mut Self>, part: PartBody) -> Result<Self::Ret> {
         self.project().inner.start_send(part)
     }
 
-    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-        self.project().inner.poll_flush(cx)
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let out = ready!(self.as_mut().project().inner.poll_flush(cx));
+        if let Err(e) = &out {
+            self.on_error(e);
+        }
+        Poll::Ready(out)
     }
 
     fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Self::Output>> {
         let mut this = self.project();
         let out = ready!(this.inner.as_mut().poll_complete(cx));
-        *this.next_uri = this.iter.next();
+        *this.started = true;
+        *this.next_uri = this.iter.next_object();
 
         trace!(next_uri = ?this.next_uri, "completed upload");
         Poll::Ready(out)
     }
 }
 
-impl<Buf: Debug> Debug for Upload<Buf> {
+impl<Buf, C> MultipartCopy for Upload<Buf, C>
+where
+    Buf: MultipartWrite<C::UploadPart, Error = UploadError, Output = CompletedParts>
+        + MultipartAbort,
+    C: ErasedClient,
+{
+    fn poll_copy_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_ready(cx)
+    }
+
+    fn start_copy(self: Pin<&mut Self>, source: CopySource) -> Result<UploadSent> {
+        self.project().inner.start_copy(source)
+    }
+}
+
+impl<Buf, C> MultipartAbort for Upload<Buf, C>
+where
+    Buf: MultipartAbort,
+    C: ErasedClient,
+{
+    fn poll_abort(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut this = self.project();
+
+        // The parts of a resumed upload don't need to be known to abort it.
+        if let Some(data) = this.resumed.take() {
+            this.list_fut.set(None);
+            let access = this.attributes.bucket_access().clone();
+            this.inner.as_mut().set_access(access);
+            this.inner.as_mut().set_upload_data(data);
+        }
+
+        // An upload still being created has to exist before it can be aborted.
+        if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+            let res = ready!(fut.poll(cx));
+            this.fut.set(None);
+            match res {
+                Ok(data) => this.inner.as_mut().set_upload_data(data),
+                Err(e) => {
+                    *this.started = true;
+                    *this.next_uri = this.iter.next_object();
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+
+        let out = ready!(this.inner.poll_abort(cx));
+        // `next_uri` is only set here when there was no active upload.
+        if this.next_uri.is_none() {
+            *this.started = true;
+            *this.next_uri = this.iter.next_object();
+        }
+
+        trace!(next_uri = ?this.next_uri, "aborted upload");
+        Poll::Ready(out)
+    }
+}
+
+impl<Buf: Debug, C: ErasedClient> Debug for Upload<Buf, C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Upload")
             .field("inner", &self.inner)
             .field("fut", &self.fut)
+            .field("list_fut", &self.list_fut)
+            .field("resumed", &self.resumed)
             .field("next_uri", &self.next_uri)
             .field("iter", &self.iter)
+            .field("started", &self.started)
+            .field("attributes", &self.attributes)
+            .field("encoder_info", &self.encoder_info)
             .finish()
     }
 }
@@ -156,64 +432,262 @@ impl<Buf: Debug> Debug for Upload<Buf> {
 /// Responsible for a single upload, which `Upload` orchestrates.
 #[must_use = "futures do nothing unless polled"]
 #[pin_project::pin_project]
-struct UploadImpl<Buf> {
+struct UploadImpl<Buf, C: ErasedClient> {
     #[pin]
     buf: Buf,
     #[pin]
-    fut: Option<SendCompleteUpload>,
+    fut: Option<C::CompleteUpload>,
+    #[pin]
+    abort_fut: Option<C::AbortUpload>,
     data: Option<UploadData>,
-    client: UploadClient,
+    client: C,
     completed: CompletedParts,
     part: PartNumber,
+    checksum: Option<ChecksumAlgorithm>,
+    encryption: Option<Encryption>,
+    access: BucketAccess,
+    inflight: Option<InflightBytes>,
+    stage_dir: Option<PathBuf>,
+    staged: Option<StagePart>,
+    abort_on_error: bool,
+    error: Option<(UploadError, FailedUpload)>,
 }
 
-impl<Buf> UploadImpl<Buf> {
-    fn new(buf: Buf, client: &UploadClient) -> Self {
+/// Writing the body of an upload part request to a file.
+type StagePart = Pin<Box<dyn Future<Output = Result<UploadPartRequest>> + Send>>;
+
+impl<Buf, C: ErasedClient> UploadImpl<Buf, C> {
+    fn new(buf: Buf, client: &C) -> Self {
         Self {
             buf,
             fut: None,
+            abort_fut: None,
             data: None,
             client: client.clone(),
             completed: CompletedParts::default(),
             part: PartNumber::default(),
+            checksum: None,
+            encryption: None,
+            access: BucketAccess::default(),
+            inflight: None,
+            stage_dir: None,
+            staged: None,
+            abort_on_error: false,
+            error: None,
         }
     }
 
     fn set_upload_data(self: Pin<&mut Self>, data: UploadData) {
         *self.project().data = Some(data);
     }
+
+    /// Set the bucket access to send the requests of the next upload with.
+    fn set_access(self: Pin<&mut Self>, access: BucketAccess) {
+        *self.project().access = access;
+    }
+
+    /// Continue the upload `data` having the uploaded `parts`.
+    fn resume(self: Pin<&mut Self>, data: UploadData, parts: CompletedParts) {
+        let this = self.project();
+        *this.part = match parts.is_empty() {
+            true => PartNumber::default(),
+            false => PartNumber::new(*parts.max_part_number() + 1),
+        };
+        *this.completed = parts;
+        *this.data = Some(data);
+    }
+
+    /// Finish staging the last part on disk and send the request to upload it.
+    fn poll_staged(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>
+    where
+        Buf: MultipartWrite<C::UploadPart, Error = UploadError>,
+    {
+        let mut this = self.project();
+        let Some(fut) = this.staged.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+        let res = ready!(fut.as_mut().poll(cx));
+        *this.staged = None;
+        // The buffer was ready when the part was sent to be staged, and nothing
+        // else has been sent to it since.
+        let fut = this.client.upload_part(res?);
+        let _ = this.buf.as_mut().start_send(fut)?;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Send the request to copy a part from `source`, after the buffer was
+    /// polled ready like for `start_send`.
+    fn start_copy(self: Pin<&mut Self>, source: CopySource) -> Result<UploadSent>
+    where
+        Buf: MultipartWrite<C::UploadPart, Error = UploadError>,
+    {
+        let mut this = self.project();
+        let data = this.data.as_ref().expect("polled Upload after completion");
+        if **this.part as u64 > AWS_MAX_PART_COUNT {
+            return Err(ErrorRepr::TooManyParts.into());
+        }
+        let pt_num = this.part.increment();
+
+        // Nothing is held in memory for a copied part, so it doesn't count
+        // against the budget of bytes in flight.
+        let bytes = source.size().unwrap_or_default() as usize;
+        let req = UploadPartCopyRequest::new(data, source, pt_num)
+            .with_customer_key(customer_key(this.encryption))
+            .with_bucket_access(this.access.clone());
+        let fut = this.client.upload_part_copy(req);
+        let _ = this.buf.as_mut().start_send(fut)?;
+        let sent = UploadSent::new(data, pt_num, bytes);
+        trace!(
+            id = %sent.id,
+            uri = %sent.uri,
+            part = %sent.part,
+            bytes = sent.bytes,
+            "part copy initiated",
+        );
+        Ok(sent)
+    }
+
+    /// Start aborting the active upload if configured to on error.
+    fn on_error<T>(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        res: Result<T>,
+    ) -> Poll<Result<T>>
+    where
+        Buf: MultipartAbort,
+    {
+        let e = match res {
+            Err(e) if self.abort_on_error => e,
+            res => return Poll::Ready(res),
+        };
+        let Some(data) = self.data.as_ref() else {
+            return Poll::Ready(Err(e));
+        };
+        let failed = e
+            .failed_upload()
+            .cloned()
+            .unwrap_or_else(|| FailedUpload::new(&data.id, &data.uri, self.part));
+        trace!(error = %e, "aborting failed upload");
+        *self.as_mut().project().error = Some((e, failed));
+        self.poll_aborted(cx).map(Err)
+    }
+
+    /// Finish aborting the upload after an error, returning the error.
+    fn poll_aborted(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<UploadError>
+    where
+        Buf: MultipartAbort,
+    {
+        let res = ready!(self.as_mut().poll_abort(cx));
+        let (e, failed) = self
+            .project()
+            .error
+            .take()
+            .expect("aborted without an error");
+        Poll::Ready(ErrorRepr::aborted(failed, e, res).into())
+    }
+}
+
+impl<Buf, C> MultipartAbort for UploadImpl<Buf, C>
+where
+    Buf: MultipartAbort,
+    C: ErasedClient,
+{
+    fn poll_abort(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut this = self.project();
+
+        if this.abort_fut.is_none() {
+            let Some(data) = this.data.as_ref() else {
+                return Poll::Ready(Ok(()));
+            };
+            ready!(this.buf.as_mut().poll_abort(cx))?;
+            this.fut.set(None);
+            *this.staged = None;
+            trace!(id = %data.id, uri = ?data.uri, "aborting upload");
+            let req = AbortRequest::new(data.get_id(), data.get_uri())
+                .with_bucket_access(this.access.clone());
+            let fut = this.client.abort_upload(req);
+            this.abort_fut.set(Some(fut));
+        }
+
+        let fut = this
+            .abort_fut
+            .as_mut()
+            .as_pin_mut()
+            .expect("abort request not sent");
+        let out = ready!(fut.poll(cx));
+
+        if let Some(data) = this.data.as_ref() {
+            instrument::upload_finished(&data.uri.bucket, "aborted");
+        }
+        this.abort_fut.set(None);
+        *this.data = None;
+        *this.part = PartNumber::default();
+        *this.completed = CompletedParts::default();
+        trace!(result = ?out, "aborted upload");
+
+        Poll::Ready(out)
+    }
 }
 
-impl<Buf> FusedMultipartWrite<PartBody> for UploadImpl<Buf>
+impl<Buf, C> FusedMultipartWrite<PartBody> for UploadImpl<Buf, C>
 where
-    Buf: MultipartWrite<SendUploadPart, Output = CompletedParts, Error = UploadError>,
+    Buf: MultipartWrite<C::UploadPart, Output = CompletedParts, Error = UploadError>
+        + MultipartAbort,
+    C: ErasedClient,
 {
     fn is_terminated(&self) -> bool {
         self.data.is_none()
     }
 }
 
-impl<Buf> MultipartWrite<PartBody> for UploadImpl<Buf>
+impl<Buf, C> MultipartWrite<PartBody> for UploadImpl<Buf, C>
 where
-    Buf: MultipartWrite<SendUploadPart, Error = UploadError, Output = CompletedParts>,
+    Buf: MultipartWrite<C::UploadPart, Error = UploadError, Output = CompletedParts>
+        + MultipartAbort,
+    C: ErasedClient,
 {
     type Ret = UploadSent;
     type Error = UploadError;
     type Output = CompletedUpload;
 
-    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-        self.project().buf.as_mut().poll_ready(cx)
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.error.is_some() {
+            return self.poll_aborted(cx).map(Err);
+        }
+        if let Err(e) = ready!(self.as_mut().poll_staged(cx)) {
+            return self.on_error(cx, Err(e));
+        }
+        let res = ready!(self.as_mut().project().buf.poll_ready(cx));
+        self.on_error(cx, res)
     }
 
     fn start_send(self: Pin<&mut Self>, part: PartBody) -> Result<Self::Ret> {
         let mut this = self.project();
         let bytes = part.size();
         let data = this.data.as_ref().expect("polled Upload after completion");
+        if **this.part as u64 > AWS_MAX_PART_COUNT {
+            return Err(ErrorRepr::TooManyParts.into());
+        }
         let pt_num = this.part.increment();
 
-        let req = UploadPartRequest::new(data, part, pt_num);
-        let fut = SendUploadPart::new(this.client, req);
-        let _ = this.buf.as_mut().start_send(fut)?;
+        let mut req = UploadPartRequest::new(data, part, pt_num);
+        if let Some(algorithm) = this.checksum {
+            req = req.with_checksum(*algorithm);
+        }
+        req = req
+            .with_customer_key(customer_key(this.encryption))
+            .with_bucket_access(this.access.clone());
+        // The request holds on to the reservation until it is dropped, which is
+        // when the request future has finished.
+        req.reservation = this.inflight.as_ref().map(|b| Arc::new(b.reserve(bytes)));
+        match this.stage_dir {
+            Some(dir) => *this.staged = Some(Box::pin(req.stage(dir.clone()))),
+            None => {
+                let fut = this.client.upload_part(req);
+                let _ = this.buf.as_mut().start_send(fut)?;
+            }
+        }
+        instrument::part_sent(&data.uri.bucket, bytes);
         let sent = UploadSent::new(data, pt_num, bytes);
         trace!(
             id = %sent.id,
@@ -225,29 +699,49 @@ where
         Ok(sent)
     }
 
-    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
-        let this = self.project();
-        let parts = ready!(this.buf.poll_complete(cx))?;
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.error.is_some() {
+            return self.poll_aborted(cx).map(Err);
+        }
+        if let Err(e) = ready!(self.as_mut().poll_staged(cx)) {
+            return self.on_error(cx, Err(e));
+        }
+        let this = self.as_mut().project();
+        let parts = match ready!(this.buf.poll_complete(cx)) {
+            Ok(parts) => parts,
+            Err(e) => return self.on_error(cx, Err(e)),
+        };
         this.completed.extend(parts);
         Poll::Ready(Ok(()))
     }
 
-    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Self::Output>> {
-        let mut this = self.project();
+    fn poll_complete(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Self::Output>> {
+        if self.error.is_some() {
+            return self.poll_aborted(cx).map(Err);
+        }
+        if let Err(e) = ready!(self.as_mut().poll_staged(cx)) {
+            return self.on_error(cx, Err(e));
+        }
+        let mut this = self.as_mut().project();
 
         if this.fut.is_none() {
             let data = this.data.as_ref().expect("polled Upload after completion");
-            let parts = ready!(this.buf.poll_complete(cx))?;
+            let parts = match ready!(this.buf.as_mut().poll_complete(cx)) {
+                Ok(parts) => parts,
+                Err(e) => return self.on_error(cx, Err(e)),
+            };
             this.completed.extend(parts);
             let completed = std::mem::take(this.completed);
-            let req = CompleteRequest::new(data, completed);
+            let req = CompleteRequest::new(data, completed)
+                .with_customer_key(customer_key(this.encryption))
+                .with_bucket_access(this.access.clone());
             trace!(
                 id = %req.id(),
                 uri = ?req.uri(),
                 parts = ?req.completed_parts(),
                 "completing upload",
             );
-            let fut = SendCompleteUpload::new(this.client, req);
+            let fut = this.client.complete_upload(req);
             this.fut.set(Some(fut));
         }
 
@@ -257,8 +751,16 @@ where
             .as_pin_mut()
             .expect("polled Upload after completion");
         let out = ready!(fut.poll(cx));
-
         this.fut.set(None);
+
+        if out.is_err() && *this.abort_on_error {
+            return self.on_error(cx, out);
+        }
+
+        if let Some(data) = this.data.as_ref() {
+            let outcome = if out.is_ok() { "completed" } else { "failed" };
+            instrument::upload_finished(&data.uri.bucket, outcome);
+        }
         *this.data = None;
         *this.part = PartNumber::default();
         trace!(result = ?out, "completed upload");
@@ -267,15 +769,35 @@ where
     }
 }
 
-impl<Buf: Debug> Debug for UploadImpl<Buf> {
+impl<Buf: Debug, C: ErasedClient> Debug for UploadImpl<Buf, C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("UploadImpl")
             .field("buf", &self.buf)
             .field("fut", &self.fut)
+            .field("abort_fut", &self.abort_fut)
             .field("data", &self.data)
             .field("client", &self.client)
             .field("completed", &self.completed)
             .field("part", &self.part)
+            .field("checksum", &self.checksum)
+            .field("encryption", &self.encryption)
+            .field("access", &self.access)
+            .field("inflight", &self.inflight)
+            .field("stage_dir", &self.stage_dir)
+            .field(
+                "staged",
+                &self
+                    .staged
+                    .as_ref()
+                    .map(|_| "Future<Output = Result<UploadPartRequest>>"),
+            )
+            .field("abort_on_error", &self.abort_on_error)
+            .field("error", &self.error)
             .finish()
     }
 }
+
+/// The key to send with every request of an upload that has `encryption`.
+fn customer_key(encryption: &Option<Encryption>) -> Option<CustomerKey> {
+    encryption.as_ref()?.customer_key().cloned()
+}
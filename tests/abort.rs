@@ -0,0 +1,97 @@
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use multipart_write::MultipartWriteExt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Sends requests to an `InMemoryClient`, failing part uploads while `fail` is
+/// set.
+#[derive(Clone, Default)]
+struct FailParts {
+    inner: InMemoryClient,
+    fail: Arc<AtomicBool>,
+}
+
+impl FailParts {
+    fn failing() -> Self {
+        let client = Self::default();
+        client.fail.store(true, Ordering::SeqCst);
+        client
+    }
+}
+
+impl SendRequest for FailParts {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(Error::other(ErrorKind::Sdk, "part failed"));
+        }
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+fn uris() -> impl Iterator<Item = ObjectUri> {
+    (0..).map(|n| ObjectUri::from(("bucket", format!("key-{n}"))))
+}
+
+#[tokio::test]
+async fn abort_upload_in_progress() {
+    let client = FailParts::default();
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri_iter(uris())
+        .build();
+    upload.send_part(b"abc".to_vec()).await.unwrap();
+    assert_eq!(client.inner.in_progress().len(), 1);
+
+    upload.abort().await.unwrap();
+    assert!(client.inner.in_progress().is_empty());
+    assert!(client.inner.object_uris().is_empty());
+
+    // The writer goes on to the next object.
+    upload.send_part(b"def".to_vec()).await.unwrap();
+    let out = MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+    assert_eq!(out.uri, ObjectUri::from(("bucket", "key-1")));
+    assert_eq!(client.inner.object(&out.uri).unwrap(), &b"def"[..]);
+}
+
+#[tokio::test]
+async fn abort_on_error() {
+    let client = FailParts::failing();
+    let mut upload = UploadBuilder::new(client.clone())
+        .abort_on_error(true)
+        .with_uri(("bucket", "key"))
+        .build();
+    let e = upload.send_part(b"abc".to_vec()).await.unwrap_err();
+
+    assert_eq!(e.aborted(), Some(true));
+    let failed = e.failed_upload().unwrap();
+    assert_eq!(failed.uri, ObjectUri::from(("bucket", "key")));
+    assert!(client.inner.in_progress().is_empty());
+}
+
+#[tokio::test]
+async fn no_abort_by_default() {
+    let client = FailParts::failing();
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri(("bucket", "key"))
+        .build();
+    let e = upload.send_part(b"abc".to_vec()).await.unwrap_err();
+
+    assert_eq!(e.aborted(), None);
+    assert_eq!(client.inner.in_progress().len(), 1);
+}
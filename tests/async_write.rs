@@ -0,0 +1,95 @@
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use tokio::io::AsyncWriteExt as _;
+
+const MIB: usize = 1024 * 1024;
+
+/// Sends requests to an `InMemoryClient`, failing every part upload.
+#[derive(Clone, Default)]
+struct FailParts(InMemoryClient);
+
+impl SendRequest for FailParts {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.0.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, _: UploadPartRequest) -> Result<CompletedPart> {
+        Err(Error::other(ErrorKind::Sdk, "part failed"))
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.0.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.0.send_abort_upload_request(req).await
+    }
+}
+
+fn bytes(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 253) as u8).collect()
+}
+
+#[tokio::test]
+async fn write_object() {
+    let client = InMemoryClient::new();
+
+    for size in [0, 10, 5 * MIB, 17 * MIB + 5] {
+        let expect = bytes(size);
+        let uri = ObjectUri::from(("bucket", size.to_string()));
+        let mut writer = UploadBuilder::new(client.clone())
+            .part_size(ByteSize::mib(5))
+            .with_uri(uri.clone())
+            .build_writer();
+
+        tokio::io::copy(&mut &expect[..], &mut writer)
+            .await
+            .unwrap();
+        writer.flush().await.unwrap();
+        assert!(writer.completed().is_none());
+        writer.shutdown().await.unwrap();
+
+        assert_eq!(writer.completed().unwrap().uri, uri);
+        assert_eq!(client.object(&uri).unwrap(), expect);
+    }
+}
+
+#[tokio::test]
+async fn no_write_after_shutdown() {
+    let mut writer = UploadBuilder::new(InMemoryClient::new())
+        .with_uri(("bucket", "key"))
+        .build_writer();
+    writer.write_all(b"abc").await.unwrap();
+    writer.shutdown().await.unwrap();
+    assert!(writer.write(b"def").await.is_err());
+}
+
+#[tokio::test]
+async fn abort_writer() {
+    let client = InMemoryClient::new();
+    let mut writer = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_uri(("bucket", "key"))
+        .build_writer();
+    writer.write_all(&bytes(11 * MIB)).await.unwrap();
+    assert_eq!(client.in_progress().len(), 1);
+
+    writer.abort().await.unwrap();
+    assert!(client.in_progress().is_empty());
+    assert!(client.object_uris().is_empty());
+}
+
+#[tokio::test]
+async fn upload_error_is_io_error() {
+    let mut writer = UploadBuilder::new(FailParts::default())
+        .part_size(ByteSize::mib(5))
+        .with_uri(("bucket", "key"))
+        .build_writer();
+    writer.write_all(&bytes(6 * MIB)).await.unwrap();
+
+    let e = writer.shutdown().await.unwrap_err();
+    let e = e.get_ref().and_then(|e| e.downcast_ref::<Error>()).unwrap();
+    assert!(matches!(e.kind(), ErrorKind::Sdk));
+}
@@ -0,0 +1,139 @@
+use aws_multipart_upload::codec::JsonLinesEncoder;
+use aws_multipart_upload::error::Result;
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, SendRequest, UploadBuilder, aws_sdk};
+
+use futures::stream;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+/// Sends requests to an `InMemoryClient`, recording the requests to create an
+/// upload and the bucket access of the part and complete requests.
+#[derive(Clone, Default)]
+struct Recorded {
+    inner: InMemoryClient,
+    creates: Arc<Mutex<Vec<CreateRequest>>>,
+    access: Arc<Mutex<Vec<BucketAccess>>>,
+}
+
+impl SendRequest for Recorded {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.creates.lock().unwrap().push(req.clone());
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        self.access
+            .lock()
+            .unwrap()
+            .push(req.bucket_access().clone());
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.access
+            .lock()
+            .unwrap()
+            .push(req.bucket_access().clone());
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+fn items() -> Vec<Value> {
+    (0..10).map(|i| json!({"i": i})).collect()
+}
+
+/// Upload two objects with `defaults` and the attributes of each object,
+/// returning the requests to create them.
+async fn upload_objects(
+    client: &Recorded,
+    defaults: ObjectAttributes,
+    objects: [ObjectAttributes; 2],
+) -> Vec<CreateRequest> {
+    let objects = objects
+        .into_iter()
+        .enumerate()
+        .map(|(n, attrs)| (ObjectUri::from(("bucket", format!("key-{n}"))), attrs));
+    let mut upload = UploadBuilder::new(client.clone())
+        .attributes(defaults)
+        .with_encoder(JsonLinesEncoder::new())
+        .with_object_iter(objects)
+        .build();
+    for _ in 0..2 {
+        stream::iter(items())
+            .collect_upload(&mut upload)
+            .await
+            .unwrap();
+    }
+    client.creates.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn object_attributes_override_defaults() {
+    let client = Recorded::default();
+    let defaults = ObjectAttributes::new()
+        .with_metadata("x", "0")
+        .with_metadata("y", "2")
+        .with_cache_control("no-cache");
+    let objects = [
+        ObjectAttributes::new().with_metadata("x", "1"),
+        ObjectAttributes::new().with_content_type("text/x-custom"),
+    ];
+    let creates = upload_objects(&client, defaults, objects).await;
+    assert_eq!(creates.len(), 2);
+
+    let first = creates[0].attributes();
+    assert_eq!(first.metadata()["x"], "1");
+    assert_eq!(first.metadata()["y"], "2");
+    assert_eq!(first.cache_control(), Some("no-cache"));
+    let second = creates[1].attributes();
+    assert_eq!(second.metadata()["x"], "0");
+    assert_eq!(second.content_type(), Some("text/x-custom"));
+}
+
+#[tokio::test]
+async fn content_type_from_encoder() {
+    let client = Recorded::default();
+    let objects = [ObjectAttributes::new(), ObjectAttributes::new()];
+    let creates = upload_objects(&client, ObjectAttributes::new(), objects).await;
+
+    for create in creates {
+        let content_type = create.attributes().content_type();
+        assert_eq!(content_type, Some("application/x-ndjson"));
+    }
+}
+
+#[tokio::test]
+async fn requester_pays_for_every_request() {
+    let client = Recorded::default();
+    let defaults = ObjectAttributes::new().with_requester_pays(true);
+    let objects = [ObjectAttributes::new(), ObjectAttributes::new()];
+    upload_objects(&client, defaults, objects).await;
+
+    let access = client.access.lock().unwrap();
+    assert!(!access.is_empty());
+    assert!(access.iter().all(BucketAccess::requester_pays));
+}
+
+#[test]
+fn tags_are_url_encoded() {
+    let attrs = ObjectAttributes::new()
+        .with_tag("team", "a b&c")
+        .with_requester_pays(true);
+    let req = CreateRequest::new(ObjectUri::from(("bucket", "key"))).with_attributes(attrs);
+
+    let conf = aws_sdk::Config::builder()
+        .behavior_version_latest()
+        .region(aws_sdk::config::Region::new("us-east-1"))
+        .build();
+    let client = aws_sdk::Client::from_conf(conf);
+    let builder = req.with_builder(client.create_multipart_upload());
+    let input = builder.as_input();
+    assert_eq!(input.get_tagging().as_deref(), Some("team=a%20b%26c"));
+    assert!(input.get_request_payer().is_some());
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "avro")]
+use aws_multipart_upload::apache_avro::{Codec, DeflateSettings, Reader, Schema, from_value};
+use aws_multipart_upload::codec::AvroEncoder;
+use aws_multipart_upload::error::ErrorKind;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::stream;
+use multipart_write::MultipartWriteExt;
+use serde::{Deserialize, Serialize};
+
+const SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "event",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "name", "type": ["null", "string"]}
+    ]
+}"#;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Event {
+    id: i64,
+    name: Option<String>,
+}
+
+fn events(n: i64) -> Vec<Event> {
+    (0..n)
+        .map(|id| Event {
+            id,
+            name: (id % 3 != 0).then(|| format!("name-{id}-{}", "x".repeat(40))),
+        })
+        .collect()
+}
+
+fn encoder(codec: Codec) -> AvroEncoder {
+    AvroEncoder::new(Schema::parse_str(SCHEMA).unwrap()).with_codec(codec)
+}
+
+fn read(obj: &[u8]) -> Vec<Event> {
+    Reader::new(obj)
+        .unwrap()
+        .map(|value| from_value(&value.unwrap()).unwrap())
+        .collect()
+}
+
+/// Upload `events` encoded with `codec`, returning the object.
+async fn upload(codec: Codec, events: Vec<Event>) -> Vec<u8> {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key.avro"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(encoder(codec).with_block_size(ByteSize::kib(16)))
+        .with_uri(uri.clone())
+        .build();
+    stream::iter(events).collect_upload(upload).await.unwrap();
+    client.object(&uri).unwrap().to_vec()
+}
+
+#[tokio::test]
+async fn object_of_many_parts() {
+    let expect = events(400_000);
+    let obj = upload(Codec::Null, expect.clone()).await;
+    assert!(obj.len() > 10 * 1024 * 1024);
+    assert_eq!(read(&obj), expect);
+}
+
+#[tokio::test]
+async fn compressed_blocks() {
+    for codec in [Codec::Deflate(DeflateSettings::default()), Codec::Snappy] {
+        let expect = events(1000);
+        let obj = upload(codec, expect.clone()).await;
+        assert_eq!(read(&obj), expect);
+    }
+}
+
+#[tokio::test]
+async fn every_object_has_a_header() {
+    let client = InMemoryClient::new();
+    let uris = (0..).map(|n| ObjectUri::from(("bucket", format!("key-{n}.avro"))));
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(encoder(Codec::Snappy))
+        .with_uri_iter(uris)
+        .build();
+
+    let expect = events(20);
+    for chunk in expect.chunks(10) {
+        for event in chunk {
+            upload.feed(event.clone()).await.unwrap();
+        }
+        let out = MultipartWriteExt::<Event>::complete(&mut upload)
+            .await
+            .unwrap();
+        assert_eq!(read(&client.object(&out.uri).unwrap()), chunk);
+    }
+}
+
+#[tokio::test]
+async fn item_not_matching_schema() {
+    let mut upload = UploadBuilder::new(InMemoryClient::new())
+        .with_encoder(encoder(Codec::Null))
+        .with_uri(("bucket", "key.avro"))
+        .build();
+    let e = upload
+        .send_part(serde_json::json!({"other": 1}))
+        .await
+        .unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Encoding));
+}
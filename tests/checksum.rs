@@ -0,0 +1,59 @@
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, UploadBuilder};
+
+use aws_smithy_types::base64;
+use multipart_write::MultipartWriteExt;
+
+const MIN_PART: usize = 5 * 1024 * 1024;
+
+/// Uploads a 5 MiB part of ones followed by `"tail"`, returning the object and
+/// what the upload completed with.
+async fn upload(algorithm: Option<ChecksumAlgorithm>) -> (Vec<u8>, CompletedUpload) {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let mut builder = UploadBuilder::new(client.clone()).with_uri(uri.clone());
+    if let Some(algorithm) = algorithm {
+        builder = builder.checksum(algorithm);
+    }
+    let mut upload = builder.build();
+    upload.send_part(vec![1u8; MIN_PART]).await.unwrap();
+    upload.send_part(b"tail".to_vec()).await.unwrap();
+    let out = MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+    (client.object(&uri).unwrap().to_vec(), out)
+}
+
+#[test]
+fn checksum_of_known_value() {
+    let crc32c = ChecksumAlgorithm::Crc32c.checksum(b"123456789");
+    assert_eq!(crc32c.value, "4waSgw==");
+}
+
+#[tokio::test]
+async fn composite_checksum() {
+    for algorithm in [ChecksumAlgorithm::Crc32c, ChecksumAlgorithm::Sha256] {
+        let (obj, out) = upload(Some(algorithm)).await;
+
+        let mut parts = Vec::new();
+        for part in [&obj[..MIN_PART], &obj[MIN_PART..]] {
+            let value = algorithm.checksum(part).value;
+            parts.extend(base64::decode(value).unwrap());
+        }
+        let expect = format!("{}-2", algorithm.checksum(&parts).value);
+        assert_eq!(out.checksum, Some(Checksum::new(algorithm, expect)));
+    }
+}
+
+#[tokio::test]
+async fn full_object_checksum() {
+    let algorithm = ChecksumAlgorithm::Crc64Nvme;
+    let (obj, out) = upload(Some(algorithm)).await;
+    assert_eq!(out.checksum, Some(algorithm.checksum(&obj)));
+}
+
+#[tokio::test]
+async fn no_checksum_by_default() {
+    let (_, out) = upload(None).await;
+    assert_eq!(out.checksum, None);
+}
@@ -0,0 +1,141 @@
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{AbortIncomplete, InMemoryClient, ObjectUri, SendRequest, UploadClient};
+
+use std::time::Duration;
+
+/// Sends requests to an `InMemoryClient`, failing every request to abort.
+#[derive(Clone, Default)]
+struct FailAborts(InMemoryClient);
+
+impl SendRequest for FailAborts {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.0.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        self.0.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.0.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, _: AbortRequest) -> Result<()> {
+        Err(Error::other(ErrorKind::Sdk, "access denied"))
+    }
+
+    async fn send_list_uploads_request(
+        &self,
+        req: ListUploadsRequest,
+    ) -> Result<Vec<IncompleteUpload>> {
+        self.0.send_list_uploads_request(req).await
+    }
+}
+
+/// Returns a client with uploads in progress to `p/1`, `p/2` and `q/1` in
+/// `bucket`, and to `p/1` in `other`.
+async fn client() -> InMemoryClient {
+    let client = InMemoryClient::new();
+    for uri in [
+        ("bucket", "p/1"),
+        ("bucket", "p/2"),
+        ("bucket", "q/1"),
+        ("other", "p/1"),
+    ] {
+        let req = CreateRequest::new(ObjectUri::from(uri));
+        client.send_create_upload_request(req).await.unwrap();
+    }
+    client
+}
+
+fn keys(uploads: &[IncompleteUpload]) -> Vec<String> {
+    let mut keys: Vec<_> = uploads.iter().map(|u| u.data.uri.key.to_string()).collect();
+    keys.sort();
+    keys
+}
+
+#[tokio::test]
+async fn list_uploads_under_prefix() {
+    let client = client().await;
+    let all = client
+        .send_list_uploads_request(ListUploadsRequest::new("bucket"))
+        .await
+        .unwrap();
+    assert_eq!(keys(&all), ["p/1", "p/2", "q/1"]);
+
+    let req = ListUploadsRequest::new("bucket").with_prefix("p/");
+    let under_p = client.send_list_uploads_request(req).await.unwrap();
+    assert_eq!(keys(&under_p), ["p/1", "p/2"]);
+}
+
+#[tokio::test]
+async fn abort_incomplete_uploads() {
+    let client = client().await;
+    let req = ListUploadsRequest::new("bucket").with_prefix("p/");
+
+    let summary = AbortIncomplete::new(req.clone(), Duration::ZERO)
+        .send(&client)
+        .await
+        .unwrap();
+    assert!(summary.is_success());
+    assert_eq!(keys(&summary.aborted), ["p/1", "p/2"]);
+    assert_eq!(summary.listed(), 2);
+    assert_eq!(client.in_progress().len(), 2);
+}
+
+#[tokio::test]
+async fn dry_run_aborts_nothing() {
+    let client = client().await;
+    let req = ListUploadsRequest::new("bucket");
+
+    let summary = AbortIncomplete::new(req, Duration::ZERO)
+        .dry_run(true)
+        .send(&client)
+        .await
+        .unwrap();
+    assert!(summary.dry_run);
+    assert_eq!(keys(&summary.aborted), ["p/1", "p/2", "q/1"]);
+    assert_eq!(client.in_progress().len(), 4);
+}
+
+#[tokio::test]
+async fn recent_uploads_are_skipped() {
+    let client = client().await;
+    let req = ListUploadsRequest::new("bucket");
+
+    let summary = AbortIncomplete::new(req, Duration::from_secs(60 * 60))
+        .send(&client)
+        .await
+        .unwrap();
+    assert!(summary.aborted.is_empty());
+    assert_eq!(summary.skipped, 3);
+    assert_eq!(client.in_progress().len(), 4);
+}
+
+#[tokio::test]
+async fn failed_aborts_are_reported() {
+    let client = FailAborts(client().await);
+    let req = ListUploadsRequest::new("other");
+
+    let summary = AbortIncomplete::new(req, Duration::ZERO)
+        .send(&client)
+        .await
+        .unwrap();
+    assert!(!summary.is_success());
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(client.0.in_progress().len(), 4);
+}
+
+#[tokio::test]
+async fn abort_through_upload_client() {
+    let client = client().await;
+    let req = ListUploadsRequest::new("other");
+
+    let summary = AbortIncomplete::new(req, Duration::ZERO)
+        .send(&UploadClient::new(client.clone()))
+        .await
+        .unwrap();
+    assert_eq!(keys(&summary.aborted), ["p/1"]);
+    assert_eq!(client.in_progress().len(), 3);
+}
@@ -0,0 +1,145 @@
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+use aws_multipart_upload::codec::{
+    Compressed, Compression, JsonArrayEncoder, LinesEncoder, PartEncoder,
+};
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::stream;
+
+/// Upload the items of `items` compressed with `codec`, returning the object.
+async fn upload<C>(codec: C, items: Vec<u32>) -> Vec<u8>
+where
+    C: Compression + Clone + Send + Sync + 'static,
+    C::Writer: Send,
+{
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key.json"));
+    let upload = UploadBuilder::new(client.clone())
+        .with_encoder(Compressed::new(JsonArrayEncoder::new(), codec))
+        .with_uri(uri.clone())
+        .build();
+    stream::iter(items).collect_upload(upload).await.unwrap();
+    client.object(&uri).unwrap().to_vec()
+}
+
+/// Upload random lines compressed with `codec` in 5 MiB parts, returning the
+/// lines, the object, and the number of parts.
+async fn upload_lines<C>(codec: C) -> (Vec<String>, Vec<u8>, usize)
+where
+    C: Compression + Clone + Send + Sync + 'static,
+    C::Writer: Send,
+{
+    let mut rng = fastrand::Rng::with_seed(7);
+    let lines: Vec<String> = (0..200_000)
+        .map(|_| (0..40).map(|_| rng.alphanumeric()).collect())
+        .collect();
+
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key.jsonl"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(Compressed::new(LinesEncoder::default(), codec))
+        .with_uri(uri.clone())
+        .build();
+    let out = stream::iter(lines.clone())
+        .collect_upload(upload)
+        .await
+        .unwrap();
+    let etag = out.etag.to_string();
+    let parts = etag.trim_matches('"').rsplit('-').next().unwrap();
+    let obj = client.object(&uri).unwrap().to_vec();
+    (lines, obj, parts.parse().unwrap())
+}
+
+fn joined(lines: Vec<String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Returns the bytes `encode` reports for the items after a flush.
+fn reported_after_flush<C: Compression + Clone>(codec: C) -> (usize, usize) {
+    let mut encoder = Compressed::new(JsonArrayEncoder::new(), codec);
+    let mut reported = PartEncoder::<u32>::begin(&mut encoder).unwrap();
+    reported += encoder.encode(1).unwrap();
+    PartEncoder::<u32>::flush(&mut encoder).unwrap();
+    reported += encoder.encode(2).unwrap();
+    let flushed = PartEncoder::<u32>::into_body(encoder).unwrap().len();
+    (reported, flushed)
+}
+
+#[cfg(feature = "gzip")]
+mod gzip_stream {
+    use super::*;
+    use aws_multipart_upload::codec::Gzip;
+    use std::io::Read as _;
+
+    fn decode(obj: &[u8]) -> String {
+        let mut out = String::new();
+        flate2::read::GzDecoder::new(obj)
+            .read_to_string(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[tokio::test]
+    async fn object_of_items() {
+        let obj = upload(Gzip::default(), vec![1, 2, 3]).await;
+        assert_eq!(decode(&obj), "[1,2,3]");
+    }
+
+    #[tokio::test]
+    async fn object_of_no_items() {
+        let obj = upload(Gzip::default(), Vec::new()).await;
+        assert_eq!(decode(&obj), "[]");
+    }
+
+    #[tokio::test]
+    async fn object_of_many_parts() {
+        let (lines, obj, parts) = upload_lines(Gzip::default()).await;
+        assert!(parts >= 2);
+        assert_eq!(decode(&obj), joined(lines));
+    }
+
+    #[test]
+    fn flushed_bytes_are_reported() {
+        let (reported, flushed) = reported_after_flush(Gzip::default());
+        assert!(reported > 0);
+        assert!(reported <= flushed);
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd_stream {
+    use super::*;
+    use aws_multipart_upload::codec::Zstd;
+
+    fn decode(obj: &[u8]) -> String {
+        String::from_utf8(::zstd::decode_all(obj).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn object_of_items() {
+        let obj = upload(Zstd::default(), vec![1, 2, 3]).await;
+        assert_eq!(decode(&obj), "[1,2,3]");
+    }
+
+    #[tokio::test]
+    async fn object_of_no_items() {
+        let obj = upload(Zstd::default(), Vec::new()).await;
+        assert_eq!(decode(&obj), "[]");
+    }
+
+    #[tokio::test]
+    async fn object_of_many_parts() {
+        let (lines, obj, parts) = upload_lines(Zstd::default()).await;
+        assert!(parts >= 2);
+        assert_eq!(decode(&obj), joined(lines));
+    }
+
+    #[test]
+    fn flushed_bytes_are_reported() {
+        let (reported, flushed) = reported_after_flush(Zstd::default());
+        assert!(reported > 0);
+        assert!(reported <= flushed);
+    }
+}
@@ -0,0 +1,135 @@
+use aws_multipart_upload::codec::{JsonArrayEncoder, LinesEncoder};
+use aws_multipart_upload::error::{ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use multipart_write::MultipartWriteExt;
+
+const MIB: usize = 1024 * 1024;
+
+/// A client that does not support copying parts.
+struct NoCopy(InMemoryClient);
+
+impl SendRequest for NoCopy {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.0.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        self.0.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.0.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.0.send_abort_upload_request(req).await
+    }
+}
+
+/// Upload an object of `size` bytes of `byte` to `key`.
+async fn put(client: &InMemoryClient, key: &str, byte: u8, size: usize) -> ObjectUri {
+    let uri = ObjectUri::from(("bucket", key));
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri(uri.clone())
+        .build();
+    upload.send_part(vec![byte; size]).await.unwrap();
+    MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+    uri
+}
+
+#[tokio::test]
+async fn concatenate_objects() {
+    let client = InMemoryClient::new();
+    let a = put(&client, "a", b'a', 6 * MIB).await;
+    let b = put(&client, "b", b'b', 100).await;
+
+    let out = UploadBuilder::new(client.clone())
+        .checksum(ChecksumAlgorithm::Crc32c)
+        .upload_copy([CopySource::new(a), CopySource::new(b)], ("bucket", "ab"))
+        .await
+        .unwrap();
+
+    let obj = client.object(&out.uri).unwrap();
+    assert_eq!(obj.len(), 6 * MIB + 100);
+    assert!(obj[..6 * MIB].iter().all(|b| *b == b'a'));
+    assert!(obj[6 * MIB..].iter().all(|b| *b == b'b'));
+}
+
+#[tokio::test]
+async fn copy_object_in_ranges() {
+    let client = InMemoryClient::new();
+    let a = put(&client, "a", b'a', 6 * MIB + 1).await;
+
+    let parts = CopySource::parts(a.clone(), 6 * MIB as u64 + 1, ByteSize::mib(5));
+    assert_eq!(parts.len(), 2);
+    let out = UploadBuilder::new(client.clone())
+        .upload_copy(parts, ("bucket", "copy"))
+        .await
+        .unwrap();
+    assert_eq!(client.object(&out.uri), client.object(&a));
+}
+
+#[tokio::test]
+async fn copied_part_before_items() {
+    let client = InMemoryClient::new();
+    let a = put(&client, "a", b'a', 6 * MIB).await;
+
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default())
+        .with_uri(("bucket", "out"))
+        .build();
+    let sent = upload.copy_part(CopySource::new(a.clone())).await.unwrap();
+    assert_eq!(*sent.part, 1);
+    upload.feed("hello").await.unwrap();
+    upload.feed("world").await.unwrap();
+    let out = MultipartWriteExt::<&str>::complete(&mut upload)
+        .await
+        .unwrap();
+
+    let obj = client.object(&out.uri).unwrap();
+    assert_eq!(obj[..6 * MIB], client.object(&a).unwrap());
+    assert_eq!(&obj[6 * MIB..], b"hello\nworld\n");
+}
+
+#[tokio::test]
+async fn object_of_only_copied_parts() {
+    let client = InMemoryClient::new();
+    let a = put(&client, "a", b'a', 100).await;
+
+    // Nothing is encoded, so the encoder doesn't write its brackets.
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(JsonArrayEncoder::new())
+        .with_uri(("bucket", "out"))
+        .build();
+    upload
+        .copy_part(CopySource::new(a).with_range(0..10))
+        .await
+        .unwrap();
+    let out = MultipartWriteExt::<i32>::complete(&mut upload)
+        .await
+        .unwrap();
+    assert_eq!(client.object(&out.uri).unwrap(), vec![b'a'; 10]);
+}
+
+#[tokio::test]
+async fn missing_source() {
+    let client = InMemoryClient::new();
+    let e = UploadBuilder::new(client)
+        .upload_copy([CopySource::new(("bucket", "missing"))], ("bucket", "out"))
+        .await
+        .unwrap_err();
+    assert!(e.to_string().contains("NoSuchKey"));
+}
+
+#[tokio::test]
+async fn copy_not_supported() {
+    let e = UploadBuilder::new(NoCopy(InMemoryClient::new()))
+        .upload_copy([CopySource::new(("bucket", "a"))], ("bucket", "out"))
+        .await
+        .unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Config));
+}
@@ -0,0 +1,93 @@
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, SendRequest, UploadBuilder, aws_sdk};
+
+use aws_smithy_types::base64;
+use multipart_write::MultipartWriteExt;
+
+const MIB: usize = 1024 * 1024;
+
+fn sdk_client() -> aws_sdk::Client {
+    let conf = aws_sdk::Config::builder()
+        .behavior_version_latest()
+        .region(aws_sdk::config::Region::new("us-east-1"))
+        .build();
+    aws_sdk::Client::from_conf(conf)
+}
+
+#[tokio::test]
+async fn upload_with_customer_key() {
+    let key = CustomerKey::new([7; 32]);
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let mut upload = UploadBuilder::new(client.clone())
+        .encryption(Encryption::customer(key.clone()))
+        .with_uri(uri.clone())
+        .build();
+
+    // The key is not in the debug output.
+    let debug = format!("{upload:?}");
+    assert!(!debug.contains(&base64::encode([7; 32])));
+
+    upload.send_part(vec![1; 5 * MIB]).await.unwrap();
+    upload.send_part(b"end".to_vec()).await.unwrap();
+    MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+    assert_eq!(client.object(&uri).unwrap().len(), 5 * MIB + 3);
+
+    // Copying from the object needs the key, and so does the new object.
+    let source = CopySource::new(uri).with_customer_key(key.clone());
+    let out = UploadBuilder::new(client.clone())
+        .encryption(Encryption::customer(key))
+        .upload_copy([source], ("bucket", "copy"))
+        .await
+        .unwrap();
+    assert_eq!(client.object(&out.uri).unwrap().len(), 5 * MIB + 3);
+}
+
+#[tokio::test]
+async fn part_without_customer_key() {
+    let client = InMemoryClient::new();
+    let req = CreateRequest::new(ObjectUri::from(("bucket", "key")))
+        .with_encryption(Encryption::customer(CustomerKey::new([7; 32])));
+    let data = client.send_create_upload_request(req).await.unwrap();
+
+    let req = UploadPartRequest::new(&data, PartBody::default(), PartNumber::new(1));
+    let e = client.send_new_part_upload_request(req).await.unwrap_err();
+    assert!(e.to_string().contains("InvalidRequest"));
+}
+
+#[test]
+fn customer_key_from_base64() {
+    let key = CustomerKey::from_base64(&base64::encode([1; 32])).unwrap();
+    assert_eq!(key, CustomerKey::new([1; 32]));
+    assert!(CustomerKey::from_base64("AAAA").is_err());
+    assert!(CustomerKey::from_base64("not base64").is_err());
+}
+
+#[test]
+fn customer_key_is_sent_with_part() {
+    let key = CustomerKey::new([1; 32]);
+    let data = UploadData::new("id", ("bucket", "key"));
+    let req = UploadPartRequest::new(&data, PartBody::default(), PartNumber::new(1))
+        .with_customer_key(Some(key.clone()));
+
+    let builder = req.with_builder(sdk_client().upload_part());
+    let input = builder.as_input();
+    let algorithm = input.get_sse_customer_algorithm().as_deref();
+    assert_eq!(algorithm, Some("AES256"));
+    let key_md5 = input.get_sse_customer_key_md5().as_deref();
+    assert_eq!(key_md5, Some(key.key_md5()));
+}
+
+#[test]
+fn kms_key_is_sent_with_create() {
+    let encryption = Encryption::kms("arn:key").with_context("a", "b");
+    let req = CreateRequest::new(ObjectUri::from(("bucket", "key"))).with_encryption(encryption);
+
+    let builder = req.with_builder(sdk_client().create_multipart_upload());
+    let input = builder.as_input();
+    assert_eq!(input.get_ssekms_key_id().as_deref(), Some("arn:key"));
+    let context = input.get_ssekms_encryption_context().clone().unwrap();
+    assert_eq!(base64::decode(context).unwrap(), br#"{"a":"b"}"#);
+}
@@ -0,0 +1,73 @@
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::stream::{self, StreamExt as _};
+use std::time::{Duration, Instant};
+use tokio::time;
+
+fn uris() -> impl Iterator<Item = ObjectUri> {
+    (0..).map(|n| ObjectUri::from(("bucket", format!("key-{n}"))))
+}
+
+#[tokio::test]
+async fn idle_upload_completes_when_part_expires() {
+    let client = InMemoryClient::new();
+    let upload = UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default())
+        .with_uri_iter(uris())
+        .max_part_age(Duration::from_millis(200))
+        .build();
+    let items = stream::iter(["a", "b", "c"]).chain(stream::pending());
+    let mut completed = items.into_upload(upload);
+
+    let start = Instant::now();
+    let out = time::timeout(Duration::from_secs(5), completed.next())
+        .await
+        .expect("part did not expire")
+        .unwrap()
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(client.object(&out.uri).unwrap(), &b"a\nb\nc\n"[..]);
+
+    // Nothing is written while the stream is idle, so nothing expires.
+    let idle = time::timeout(Duration::from_millis(400), completed.next()).await;
+    assert!(idle.is_err());
+}
+
+#[tokio::test]
+async fn idle_upload_is_not_completed_by_default() {
+    let client = InMemoryClient::new();
+    let upload = UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default())
+        .with_uri_iter(uris())
+        .build();
+    let items = stream::iter(["a", "b", "c"]).chain(stream::pending());
+    let mut completed = items.into_upload(upload);
+
+    let idle = time::timeout(Duration::from_millis(400), completed.next()).await;
+    assert!(idle.is_err());
+    assert!(client.object_uris().is_empty());
+}
+
+#[tokio::test]
+async fn upload_expires_while_items_arrive() {
+    let client = InMemoryClient::new();
+    let upload = UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default())
+        .with_uri_iter(uris())
+        .max_upload_age(Duration::from_millis(300))
+        .build();
+    let items = stream::iter(0..6).then(|n| async move {
+        time::sleep(Duration::from_millis(100)).await;
+        n.to_string()
+    });
+    let completed: Vec<_> = items.into_upload(upload).collect().await;
+
+    assert!(completed.len() >= 2);
+    let mut obj = Vec::new();
+    for out in completed {
+        obj.extend(client.object(&out.unwrap().uri).unwrap());
+    }
+    assert_eq!(obj, b"0\n1\n2\n3\n4\n5\n");
+}
@@ -0,0 +1,135 @@
+use aws_multipart_upload::codec::{LinesEncoder, PartEncoder};
+use aws_multipart_upload::request::PartBody;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::stream;
+use multipart_write::MultipartWriteExt;
+use std::convert::Infallible;
+
+/// Writes lines between a `BEGIN` and an `END` line.
+#[derive(Default)]
+struct Framed(PartBody);
+
+impl PartEncoder<String> for Framed {
+    type Error = Infallible;
+
+    fn restore(&self) -> Result<Self, Self::Error> {
+        Ok(Self::default())
+    }
+
+    fn begin(&mut self) -> Result<usize, Self::Error> {
+        self.0.extend_from_slice(b"BEGIN\n");
+        Ok(6)
+    }
+
+    fn encode(&mut self, item: String) -> Result<usize, Self::Error> {
+        self.0.extend_from_slice(item.as_bytes());
+        self.0.extend_from_slice(b"\n");
+        Ok(item.len() + 1)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn into_body(self) -> Result<PartBody, Self::Error> {
+        Ok(self.0)
+    }
+
+    fn finish(mut self) -> Result<PartBody, Self::Error> {
+        self.0.extend_from_slice(b"END\n");
+        Ok(self.0)
+    }
+}
+
+fn uris() -> impl Iterator<Item = ObjectUri> {
+    (0..).map(|n| ObjectUri::from(("bucket", format!("key-{n}"))))
+}
+
+fn lines(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("{i:01023}")).collect()
+}
+
+#[tokio::test]
+async fn header_and_footer_of_object_in_many_parts() {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(Framed::default())
+        .with_uri(uri.clone())
+        .build();
+    let items = lines(12 * 1024);
+    stream::iter(items.clone())
+        .collect_upload(upload)
+        .await
+        .unwrap();
+
+    let expect: String = items.iter().map(|line| format!("{line}\n")).collect();
+    let expect = format!("BEGIN\n{expect}END\n");
+    assert_eq!(client.object(&uri).unwrap(), expect.as_bytes());
+}
+
+#[tokio::test]
+async fn header_and_footer_of_every_object() {
+    let client = InMemoryClient::new();
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(Framed::default())
+        .with_uri_iter(uris())
+        .build();
+
+    for item in ["a", "b"] {
+        upload.feed(item.to_string()).await.unwrap();
+        let out = MultipartWriteExt::<String>::complete(&mut upload)
+            .await
+            .unwrap();
+        let expect = format!("BEGIN\n{item}\nEND\n");
+        assert_eq!(client.object(&out.uri).unwrap(), expect.as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn lines_header_of_every_object() {
+    let client = InMemoryClient::new();
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default().with_header("header"))
+        .with_uri_iter(uris())
+        .build();
+
+    for _ in 0..2 {
+        upload.feed("a").await.unwrap();
+        upload.feed("b").await.unwrap();
+        let out = MultipartWriteExt::<&str>::complete(&mut upload)
+            .await
+            .unwrap();
+        assert_eq!(client.object(&out.uri).unwrap(), &b"header\na\nb\n"[..]);
+    }
+}
+
+#[cfg(feature = "csv")]
+#[tokio::test]
+async fn csv_header_of_every_object() {
+    use aws_multipart_upload::codec::CsvEncoder;
+
+    #[derive(serde::Serialize)]
+    struct Row {
+        a: i32,
+        b: &'static str,
+    }
+
+    let client = InMemoryClient::new();
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(CsvEncoder::default().with_header())
+        .with_uri_iter(uris())
+        .build();
+
+    for _ in 0..2 {
+        upload.feed(Row { a: 1, b: "x" }).await.unwrap();
+        upload.feed(Row { a: 2, b: "y" }).await.unwrap();
+        let out = MultipartWriteExt::<Row>::complete(&mut upload)
+            .await
+            .unwrap();
+        assert_eq!(client.object(&out.uri).unwrap(), &b"a,b\n1,x\n2,y\n"[..]);
+    }
+}
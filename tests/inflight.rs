@@ -0,0 +1,138 @@
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::{InflightBytes, UploadStreamExt as _};
+use aws_multipart_upload::{
+    ByteSize, InMemoryClient, MultipartUpload, ObjectUri, SendRequest, UploadBuilder,
+};
+
+use futures::{StreamExt as _, stream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Sends requests to an `InMemoryClient`, taking a while to upload a part and
+/// recording the most bytes of parts that were in flight at once.  Part uploads
+/// fail after the wait if `fail` is set.
+#[derive(Clone, Default)]
+struct Slow {
+    inner: InMemoryClient,
+    fail: bool,
+    inflight: Arc<AtomicUsize>,
+    max_inflight: Arc<AtomicUsize>,
+}
+
+impl Slow {
+    fn max_inflight(&self) -> ByteSize {
+        ByteSize::b(self.max_inflight.load(Ordering::SeqCst) as u64)
+    }
+}
+
+impl SendRequest for Slow {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        let bytes = req.body().len();
+        let inflight = self.inflight.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        self.max_inflight.fetch_max(inflight, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.inflight.fetch_sub(bytes, Ordering::SeqCst);
+        if self.fail {
+            return Err(Error::other(ErrorKind::Sdk, "part failed"));
+        }
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+fn upload(
+    client: &InMemoryClient,
+    budget: &InflightBytes,
+    key: &str,
+) -> MultipartUpload<LinesEncoder> {
+    UploadBuilder::new(client.clone())
+        .with_encoder(LinesEncoder::default())
+        .part_size(ByteSize::mib(5))
+        .upload_size(ByteSize::mib(100))
+        .inflight_bytes(budget.clone())
+        .with_uri(("bucket", key.to_string()))
+        .build()
+}
+
+fn lines(n: usize) -> Vec<String> {
+    vec!["x".repeat(1023); n]
+}
+
+#[tokio::test]
+async fn idle_upload_releases_shared_budget() {
+    let client = InMemoryClient::new();
+    let budget = InflightBytes::new(ByteSize::mib(5));
+
+    // One part is sent, then the stream waits for an item that never comes.
+    let idle = stream::iter(lines(5 * 1024 + 10)).chain(stream::pending());
+    let mut idle = idle.into_upload(upload(&client, &budget, "idle"));
+    let res = tokio::time::timeout(Duration::from_millis(200), idle.next()).await;
+    assert!(res.is_err());
+    assert_eq!(budget.used(), ByteSize::b(0));
+
+    // Another upload sharing the budget is not blocked by the idle one.
+    let other = stream::iter(lines(12 * 1024)).collect_upload(upload(&client, &budget, "other"));
+    let res = tokio::time::timeout(Duration::from_secs(5), other).await;
+    res.expect("upload blocked on the shared budget").unwrap();
+    assert_eq!(budget.used(), ByteSize::b(0));
+}
+
+#[tokio::test]
+async fn budget_bounds_bytes_in_flight() {
+    let client = Slow::default();
+    let budget = InflightBytes::new(ByteSize::mib(12));
+    let bytes = vec![1u8; 50 * 1024 * 1024];
+
+    let upload = |key: &'static str| {
+        UploadBuilder::new(client.clone())
+            .part_size(ByteSize::mib(5))
+            .inflight_bytes(budget.clone())
+            .upload_reader(&bytes[..], ObjectUri::from(("bucket", key)))
+    };
+    let (first, second) = tokio::join!(upload("first"), upload("second"));
+    first.unwrap();
+    second.unwrap();
+
+    // The budget is exceeded by at most the size of one part.
+    assert!(client.max_inflight() > ByteSize::mib(5));
+    assert!(client.max_inflight() <= ByteSize::mib(12 + 5));
+    assert_eq!(budget.used(), ByteSize::b(0));
+    for key in ["first", "second"] {
+        let obj = client.inner.object(&ObjectUri::from(("bucket", key)));
+        assert_eq!(obj.unwrap().len(), bytes.len());
+    }
+}
+
+#[tokio::test]
+async fn part_failing_while_idle_fails_stream() {
+    let client = Slow {
+        fail: true,
+        ..Default::default()
+    };
+    let upload = UploadBuilder::new(client)
+        .with_encoder(LinesEncoder::default())
+        .part_size(ByteSize::mib(5))
+        .max_inflight_bytes(ByteSize::mib(100))
+        .with_uri(("bucket", "key"))
+        .build();
+
+    let items = stream::iter(lines(5 * 1024 + 10)).chain(stream::pending());
+    let mut completed = items.into_upload(upload);
+    let res = tokio::time::timeout(Duration::from_secs(5), completed.next()).await;
+    let e = res.expect("part error not returned").unwrap().unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Sdk));
+}
@@ -0,0 +1,70 @@
+use aws_multipart_upload::codec::JsonArrayEncoder;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::stream;
+use multipart_write::MultipartWriteExt;
+use serde_json::{Value, json};
+
+fn items(n: i64) -> Vec<Value> {
+    (0..n)
+        .map(|i| json!({"i": i, "s": "x".repeat(100), "n": [1, {"a": "b\nc"}]}))
+        .collect()
+}
+
+/// Upload `items` with `encoder`, returning the object parsed as JSON.
+async fn upload(encoder: JsonArrayEncoder, items: Vec<Value>) -> (usize, Value) {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key.json"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(encoder)
+        .with_uri(uri.clone())
+        .build();
+    stream::iter(items).collect_upload(upload).await.unwrap();
+    let obj = client.object(&uri).unwrap();
+    (obj.len(), serde_json::from_slice(&obj).unwrap())
+}
+
+#[tokio::test]
+async fn object_of_many_parts_is_one_array() {
+    for encoder in [JsonArrayEncoder::new(), JsonArrayEncoder::new().pretty()] {
+        let expect = items(100_000);
+        let (size, obj) = upload(encoder, expect.clone()).await;
+        assert!(size > 10 * 1024 * 1024);
+        assert_eq!(obj, Value::Array(expect));
+    }
+}
+
+#[tokio::test]
+async fn object_of_one_item() {
+    let expect = items(1);
+    let (_, obj) = upload(JsonArrayEncoder::new(), expect.clone()).await;
+    assert_eq!(obj, Value::Array(expect));
+}
+
+#[tokio::test]
+async fn object_of_no_items() {
+    let (_, obj) = upload(JsonArrayEncoder::new(), Vec::new()).await;
+    assert_eq!(obj, json!([]));
+}
+
+#[tokio::test]
+async fn every_object_is_an_array() {
+    let client = InMemoryClient::new();
+    let uris = (0..).map(|n| ObjectUri::from(("bucket", format!("key-{n}.json"))));
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_encoder(JsonArrayEncoder::new())
+        .with_uri_iter(uris)
+        .build();
+
+    for n in [1, 2] {
+        upload.feed(json!(n)).await.unwrap();
+        upload.feed(json!(n * 10)).await.unwrap();
+        let out = MultipartWriteExt::<Value>::complete(&mut upload)
+            .await
+            .unwrap();
+        let obj = client.object(&out.uri).unwrap();
+        assert_eq!(obj, format!("[{n},{}]", n * 10).as_bytes());
+    }
+}
@@ -0,0 +1,71 @@
+use aws_multipart_upload::error::{Error, InMemoryError};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{ErasedClient, InMemoryClient, ObjectUri, UploadBuilder, UploadClient};
+
+use multipart_write::MultipartWriteExt;
+
+const MIN_PART: usize = 5 * 1024 * 1024;
+
+/// Returns whether `e` is the error `InMemoryClient` fails a request with when
+/// it breaks the rule of `code`.
+fn is(e: &Error, code: InMemoryError) -> bool {
+    e.to_string().contains(&code.to_string())
+}
+
+#[tokio::test]
+async fn object_is_the_parts_in_order() {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri(uri.clone())
+        .build();
+    upload.send_part(vec![1u8; MIN_PART]).await.unwrap();
+    upload.send_part(vec![2u8; MIN_PART]).await.unwrap();
+    upload.send_part(b"end".to_vec()).await.unwrap();
+    assert_eq!(client.in_progress().len(), 1);
+    assert!(client.object(&uri).is_none());
+
+    MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+    let obj = client.object(&uri).unwrap();
+    assert_eq!(obj.len(), 2 * MIN_PART + 3);
+    assert!(obj[..MIN_PART].iter().all(|b| *b == 1));
+    assert!(obj[MIN_PART..2 * MIN_PART].iter().all(|b| *b == 2));
+    assert_eq!(&obj[2 * MIN_PART..], b"end");
+    assert_eq!(client.object_uris(), vec![uri]);
+    assert!(client.in_progress().is_empty());
+}
+
+#[tokio::test]
+async fn part_too_small() {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri(uri.clone())
+        .build();
+    upload.send_part(b"a".to_vec()).await.unwrap();
+    upload.send_part(b"b".to_vec()).await.unwrap();
+
+    let e = MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap_err();
+    assert!(is(&e, InMemoryError::EntityTooSmall));
+    assert!(client.object(&uri).is_none());
+    assert_eq!(client.in_progress().len(), 1);
+}
+
+#[tokio::test]
+async fn no_upload_after_abort() {
+    let client = InMemoryClient::new();
+    let uploads = UploadClient::new(client.clone());
+    let req = CreateRequest::new(ObjectUri::from(("bucket", "key")));
+    let data = uploads.create_upload(req).await.unwrap();
+    assert_eq!(client.in_progress(), vec![data.clone()]);
+
+    let abort = || AbortRequest::new(data.get_id(), data.get_uri());
+    uploads.abort_upload(abort()).await.unwrap();
+    assert!(client.in_progress().is_empty());
+    let e = uploads.abort_upload(abort()).await.unwrap_err();
+    assert!(is(&e, InMemoryError::NoSuchUpload));
+}
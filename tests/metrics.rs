@@ -0,0 +1,181 @@
+#![cfg(feature = "metrics")]
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, SendRequest, UploadBuilder};
+
+use futures::stream;
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, Key, KeyName, Metadata, Recorder, SharedString,
+    Unit,
+};
+use multipart_write::MultipartWriteExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Values = Arc<Mutex<HashMap<String, f64>>>;
+
+/// Records the value of counters and gauges by name and labels, such as
+/// `"aws_multipart_upload_active_tasks,bucket=bucket"`.
+#[derive(Clone, Default)]
+struct TestRecorder(Values);
+
+impl TestRecorder {
+    fn get(&self, key: &str) -> f64 {
+        self.0.lock().unwrap().get(key).copied().unwrap_or_default()
+    }
+
+    fn handle(&self, key: &Key) -> Handle {
+        let mut name = key.name().to_string();
+        for label in key.labels() {
+            name += &format!(",{}={}", label.key(), label.value());
+        }
+        Handle(name, Arc::clone(&self.0))
+    }
+}
+
+struct Handle(String, Values);
+
+impl Handle {
+    fn add(&self, value: f64) {
+        *self.1.lock().unwrap().entry(self.0.clone()).or_default() += value;
+    }
+}
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        self.add(value as f64);
+    }
+
+    fn absolute(&self, _: u64) {}
+}
+
+impl GaugeFn for Handle {
+    fn increment(&self, value: f64) {
+        self.add(value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.add(-value);
+    }
+
+    fn set(&self, _: f64) {}
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(Arc::new(self.handle(key)))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(Arc::new(self.handle(key)))
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+/// Sends requests to an `InMemoryClient`, failing the first part upload.
+#[derive(Clone, Default)]
+struct FailFirstPart {
+    inner: InMemoryClient,
+    failed: Arc<AtomicBool>,
+}
+
+impl SendRequest for FailFirstPart {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        if !self.failed.swap(true, Ordering::SeqCst) {
+            return Err(Error::other(ErrorKind::Sdk, "service unavailable"));
+        }
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+#[tokio::test]
+async fn completed_upload() {
+    let recorder = TestRecorder::default();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+
+    let upload = UploadBuilder::new(InMemoryClient::new())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(LinesEncoder::default())
+        .with_uri(("bucket", "key"))
+        .build();
+    let lines = (0..6 * 1024).map(|n| format!("{n:01023}"));
+    stream::iter(lines).collect_upload(upload).await.unwrap();
+
+    let bytes = ByteSize::mib(6).as_u64() as f64;
+    assert_eq!(
+        recorder.get("aws_multipart_upload_encoded_items_total"),
+        6144.0
+    );
+    assert_eq!(
+        recorder.get("aws_multipart_upload_bytes_total,bucket=bucket"),
+        bytes
+    );
+    assert_eq!(
+        recorder.get("aws_multipart_upload_active_tasks,bucket=bucket"),
+        0.0
+    );
+    let completed = "aws_multipart_upload_uploads_total,bucket=bucket,outcome=completed";
+    assert_eq!(recorder.get(completed), 1.0);
+}
+
+#[tokio::test]
+async fn aborted_upload() {
+    let recorder = TestRecorder::default();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+
+    let mut upload = UploadBuilder::new(InMemoryClient::new())
+        .with_uri(("bucket", "key"))
+        .build();
+    upload.send_part(b"abc".to_vec()).await.unwrap();
+    upload.abort().await.unwrap();
+
+    let aborted = "aws_multipart_upload_uploads_total,bucket=bucket,outcome=aborted";
+    assert_eq!(recorder.get(aborted), 1.0);
+    let completed = "aws_multipart_upload_uploads_total,bucket=bucket,outcome=completed";
+    assert_eq!(recorder.get(completed), 0.0);
+}
+
+#[tokio::test]
+async fn retried_request() {
+    let recorder = TestRecorder::default();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+
+    let policy = RetryPolicy::default()
+        .max_attempts(2)
+        .initial_backoff(Duration::from_millis(1))
+        .retry_if(|_| true);
+    let mut upload = UploadBuilder::new(FailFirstPart::default())
+        .retry_policy(policy)
+        .with_uri(("bucket", "key"))
+        .build();
+    upload.send_part(b"abc".to_vec()).await.unwrap();
+    MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+
+    let retries = "aws_multipart_upload_retries_total,operation=upload_part,bucket=bucket";
+    assert_eq!(recorder.get(retries), 1.0);
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "parquet")]
+use aws_multipart_upload::arrow_schema::{DataType, Field, Schema};
+use aws_multipart_upload::codec::ParquetEncoder;
+use aws_multipart_upload::error::Result;
+use aws_multipart_upload::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use aws_multipart_upload::parquet::file::statistics::Statistics;
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use futures::stream;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// Sends requests to an `InMemoryClient`, recording the size of each part.
+#[derive(Clone, Default)]
+struct PartSizes {
+    inner: InMemoryClient,
+    sizes: Arc<Mutex<Vec<(PartNumber, usize)>>>,
+}
+
+impl PartSizes {
+    /// Returns the size of the parts in part number order.
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes = self.sizes.lock().unwrap().clone();
+        sizes.sort();
+        sizes.into_iter().map(|(_, size)| size).collect()
+    }
+}
+
+impl SendRequest for PartSizes {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        let part = (req.part_number(), req.body().len());
+        self.sizes.lock().unwrap().push(part);
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+#[derive(Serialize)]
+struct Row {
+    id: i64,
+    name: String,
+}
+
+#[tokio::test]
+async fn row_groups_are_aligned_to_parts() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    let encoder = ParquetEncoder::try_new(Arc::new(schema))
+        .unwrap()
+        .with_row_group_size(ByteSize::mib(1));
+
+    let client = PartSizes::default();
+    let uri = ObjectUri::from(("bucket", "key.parquet"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(encoder)
+        .with_uri(uri.clone())
+        .build();
+
+    let mut rng = fastrand::Rng::with_seed(3);
+    let rows = (0..400_000).map(|id| Row {
+        id,
+        name: (0..24).map(|_| rng.alphanumeric()).collect(),
+    });
+    stream::iter(rows).collect_upload(upload).await.unwrap();
+
+    let obj = client.inner.object(&uri).unwrap();
+    let sizes = client.sizes();
+    assert!(sizes.len() >= 2);
+    assert_eq!(sizes.iter().sum::<usize>(), obj.len());
+
+    // Every part boundary is where a row group starts.
+    let reader = ParquetRecordBatchReaderBuilder::try_new(obj).unwrap();
+    let starts: Vec<usize> = reader
+        .metadata()
+        .row_groups()
+        .iter()
+        .map(|rg| rg.column(0).byte_range().0 as usize)
+        .collect();
+    let mut boundary = 0;
+    for size in &sizes[..sizes.len() - 1] {
+        boundary += size;
+        assert!(starts.contains(&boundary), "part ends at {boundary}");
+    }
+
+    // The row groups have the ids in order.
+    let mut next = 0;
+    for rg in reader.metadata().row_groups() {
+        let Some(Statistics::Int64(ids)) = rg.column(0).statistics() else {
+            panic!("no statistics for ids");
+        };
+        assert_eq!(ids.min_opt(), Some(&next));
+        next += rg.num_rows();
+        assert_eq!(ids.max_opt(), Some(&(next - 1)));
+    }
+    assert_eq!(next, 400_000);
+    let rows: usize = reader.build().unwrap().map(|b| b.unwrap().num_rows()).sum();
+    assert_eq!(rows, 400_000);
+}
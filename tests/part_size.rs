@@ -0,0 +1,93 @@
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::error::{ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::{PartSize, UploadStreamExt as _};
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use futures::stream;
+use std::sync::{Arc, Mutex};
+
+/// Sends requests to an `InMemoryClient`, recording the size of each part.
+#[derive(Clone, Default)]
+struct PartSizes {
+    inner: InMemoryClient,
+    sizes: Arc<Mutex<Vec<(PartNumber, usize)>>>,
+}
+
+impl PartSizes {
+    /// Returns the size of the parts in part number order.
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes = self.sizes.lock().unwrap().clone();
+        sizes.sort();
+        sizes.into_iter().map(|(_, size)| size).collect()
+    }
+}
+
+impl SendRequest for PartSizes {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        let part = (req.part_number(), req.body().len());
+        self.sizes.lock().unwrap().push(part);
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+#[test]
+fn target_part_size() {
+    let size = PartSize::new(ByteSize::mib(5)).grow(2, 3);
+    assert_eq!(size.target(2), ByteSize::mib(5));
+    assert_eq!(size.target(3), ByteSize::mib(10));
+    assert_eq!(size.target(6), ByteSize::mib(20));
+    // The size doesn't grow beyond the largest part allowed.
+    assert_eq!(size.target(100), ByteSize::gib(5));
+    // Nor is it less than the smallest part allowed.
+    assert_eq!(PartSize::new(ByteSize::kib(1)).target(0), ByteSize::mib(5));
+}
+
+#[test]
+fn upload_too_large_for_part_size() {
+    let builder = || {
+        UploadBuilder::new(InMemoryClient::new())
+            .with_uri(("bucket", "key"))
+            .upload_size(ByteSize::tib(5))
+            .part_size(ByteSize::mib(5))
+    };
+    let e = builder().try_build().err().unwrap();
+    assert!(matches!(e.kind(), ErrorKind::Config));
+
+    assert!(builder().grow_part_size(2, 500).try_build().is_ok());
+}
+
+#[tokio::test]
+async fn part_size_grows() {
+    let client = PartSizes::default();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .grow_part_size(2, 1)
+        .with_encoder(LinesEncoder::default())
+        .with_uri(uri.clone())
+        .build();
+
+    // Lines of 1 KiB, with the newline, for an object of 40 MiB.
+    let lines = (0..40 * 1024).map(|n| format!("{n:01023}"));
+    stream::iter(lines).collect_upload(upload).await.unwrap();
+
+    let sizes = client.sizes();
+    let mib = ByteSize::mib(1).as_u64() as usize;
+    assert_eq!(sizes.len(), 4);
+    assert_eq!(sizes[..3], [5 * mib, 10 * mib, 20 * mib]);
+    assert_eq!(sizes[3], 5 * mib);
+    assert_eq!(client.inner.object(&uri).unwrap().len(), 40 * mib);
+}
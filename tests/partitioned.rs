@@ -0,0 +1,128 @@
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::error::ErrorKind;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use futures::channel::mpsc;
+use futures::{StreamExt as _, stream};
+use std::time::Duration;
+
+/// The key of a line is the text before the first `:`.
+fn key(line: &str) -> String {
+    line.split(':').next().unwrap().to_string()
+}
+
+fn builder(client: &InMemoryClient) -> UploadBuilder<LinesEncoder> {
+    UploadBuilder::new(client.clone())
+        .max_inflight_bytes(ByteSize::mib(20))
+        .with_encoder(LinesEncoder::default())
+}
+
+#[tokio::test]
+async fn items_are_routed_by_key() {
+    let client = InMemoryClient::new();
+    let mut n = 0;
+    let upload = builder(&client)
+        .part_size(ByteSize::mib(5))
+        .upload_size(ByteSize::mib(5))
+        .build_partitioned(
+            |line: &String| key(line),
+            move |key: &String| {
+                n += 1;
+                ObjectUri::from(("bucket", format!("{key}/{n}")))
+            },
+        )
+        .unwrap();
+
+    // About 8 MiB for each of three keys, so each has more than one upload.
+    let lines: Vec<String> = (0..24 * 1024)
+        .map(|i| format!("k{}:{i:01017}", i % 3))
+        .collect();
+    let completed: Vec<_> = stream::iter(lines.clone())
+        .into_partitioned_upload(upload)
+        .collect()
+        .await;
+    assert!(completed.len() > 3);
+
+    let mut uploaded = Vec::new();
+    for out in completed {
+        let uri = out.unwrap().uri;
+        let obj = String::from_utf8(client.object(&uri).unwrap().to_vec()).unwrap();
+        let prefix = format!("{}:", uri.key.split('/').next().unwrap());
+        assert!(obj.lines().all(|line| line.starts_with(&prefix)));
+        uploaded.extend(obj.lines().map(String::from));
+    }
+    uploaded.sort();
+    let mut lines = lines;
+    lines.sort();
+    assert_eq!(uploaded, lines);
+}
+
+#[tokio::test]
+async fn least_recently_used_upload_is_completed() {
+    let client = InMemoryClient::new();
+    let upload = builder(&client)
+        .build_partitioned(
+            |line: &String| key(line),
+            |key: &String| ObjectUri::from(("bucket", key.as_str())),
+        )
+        .unwrap()
+        .max_open(2);
+
+    let lines = ["a:1", "b:1", "a:2", "c:1", "a:3"].map(String::from);
+    let completed: Vec<_> = stream::iter(lines)
+        .into_partitioned_upload(upload)
+        .map(|out| out.unwrap().uri)
+        .collect()
+        .await;
+
+    // Opening `c` completes `b`, and the input ending completes the others.
+    assert_eq!(completed.len(), 3);
+    assert_eq!(completed[0], ObjectUri::from(("bucket", "b")));
+    let object = |key| client.object(&ObjectUri::from(("bucket", key))).unwrap();
+    assert_eq!(object("a"), &b"a:1\na:2\na:3\n"[..]);
+    assert_eq!(object("b"), &b"b:1\n"[..]);
+    assert_eq!(object("c"), &b"c:1\n"[..]);
+}
+
+#[tokio::test]
+async fn idle_uploads_expire() {
+    let client = InMemoryClient::new();
+    let upload = builder(&client)
+        .max_part_age(Duration::from_millis(50))
+        .build_partitioned(
+            |line: &String| key(line),
+            |key: &String| ObjectUri::from(("bucket", key.as_str())),
+        )
+        .unwrap();
+
+    let (tx, rx) = mpsc::unbounded();
+    let mut completed = rx.into_partitioned_upload(upload);
+    tx.unbounded_send("a:1".to_string()).unwrap();
+    tx.unbounded_send("b:1".to_string()).unwrap();
+
+    let mut keys = Vec::new();
+    for _ in 0..2 {
+        let next = tokio::time::timeout(Duration::from_secs(5), completed.next()).await;
+        let out = next.expect("upload did not expire").unwrap().unwrap();
+        keys.push(out.uri.key.to_string());
+    }
+    keys.sort();
+    assert_eq!(keys, ["a", "b"]);
+
+    drop(tx);
+    assert!(completed.next().await.is_none());
+}
+
+#[test]
+fn budget_is_required() {
+    let e = UploadBuilder::new(InMemoryClient::new())
+        .with_encoder(LinesEncoder::default())
+        .build_partitioned::<String, _, _>(
+            |line: &String| key(line),
+            |key: &String| ObjectUri::from(("bucket", key.as_str())),
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(e.kind(), ErrorKind::Config));
+}
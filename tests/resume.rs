@@ -0,0 +1,110 @@
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use multipart_write::MultipartWriteExt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Sends requests to an `InMemoryClient`, failing part uploads while `fail` is
+/// set.
+#[derive(Clone, Default)]
+struct FailParts {
+    inner: InMemoryClient,
+    fail: Arc<AtomicBool>,
+}
+
+impl SendRequest for FailParts {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(Error::other(ErrorKind::Sdk, "part failed"));
+        }
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+
+    async fn send_list_parts_request(&self, req: ListPartsRequest) -> Result<CompletedParts> {
+        self.inner.send_list_parts_request(req).await
+    }
+}
+
+#[tokio::test]
+async fn resume_failed_upload() {
+    let client = FailParts::default();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let first = vec![1u8; ByteSize::mib(5).as_u64() as usize];
+
+    let mut upload = UploadBuilder::new(client.clone())
+        .with_uri(uri.clone())
+        .build();
+    upload.send_part(first.clone()).await.unwrap();
+    client.fail.store(true, Ordering::SeqCst);
+    assert!(upload.send_part(b"lost".to_vec()).await.is_err());
+    drop(upload);
+
+    // The upload is left as it was when the part failed.
+    let interrupted = client.inner.in_progress().pop().unwrap();
+    assert_eq!(interrupted.uri, uri);
+
+    client.fail.store(false, Ordering::SeqCst);
+    let mut upload = UploadBuilder::new(client.clone())
+        .resume(interrupted)
+        .build();
+    upload.send_part(b"rest".to_vec()).await.unwrap();
+    let out = MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+
+    assert_eq!(out.uri, uri);
+    assert!(client.inner.in_progress().is_empty());
+    let obj = client.inner.object(&uri).unwrap();
+    assert_eq!(obj.len(), first.len() + 4);
+    assert_eq!(&obj[first.len()..], b"rest");
+}
+
+#[tokio::test]
+async fn resume_needs_list_parts() {
+    #[derive(Clone)]
+    struct NoListParts(InMemoryClient);
+
+    impl SendRequest for NoListParts {
+        async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+            self.0.send_create_upload_request(req).await
+        }
+
+        async fn send_new_part_upload_request(
+            &self,
+            req: UploadPartRequest,
+        ) -> Result<CompletedPart> {
+            self.0.send_new_part_upload_request(req).await
+        }
+
+        async fn send_complete_upload_request(
+            &self,
+            req: CompleteRequest,
+        ) -> Result<CompletedUpload> {
+            self.0.send_complete_upload_request(req).await
+        }
+
+        async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+            self.0.send_abort_upload_request(req).await
+        }
+    }
+
+    let data = UploadData::new("id", ("bucket", "key"));
+    let mut upload = UploadBuilder::new(NoListParts(InMemoryClient::new()))
+        .resume(data)
+        .build();
+    assert!(upload.send_part(b"abc".to_vec()).await.is_err());
+}
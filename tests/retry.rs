@@ -0,0 +1,110 @@
+use aws_multipart_upload::error::{Error, ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use futures::stream;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// Fails the first `fails` requests of each operation, then sends them to an
+/// `InMemoryClient`.
+#[derive(Clone, Default)]
+struct Flaky {
+    inner: InMemoryClient,
+    fails: u32,
+    create: Arc<AtomicU32>,
+    part: Arc<AtomicU32>,
+    complete: Arc<AtomicU32>,
+}
+
+impl Flaky {
+    fn new(fails: u32) -> Self {
+        Self {
+            fails,
+            ..Default::default()
+        }
+    }
+
+    fn attempt(&self, count: &AtomicU32) -> Result<()> {
+        if count.fetch_add(1, Ordering::SeqCst) < self.fails {
+            return Err(Error::other(ErrorKind::Sdk, "service unavailable"));
+        }
+        Ok(())
+    }
+}
+
+impl SendRequest for Flaky {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.attempt(&self.create)?;
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        self.attempt(&self.part)?;
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.attempt(&self.complete)?;
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+fn policy(attempts: u32) -> RetryPolicy {
+    RetryPolicy::default()
+        .max_attempts(attempts)
+        .initial_backoff(Duration::from_millis(1))
+        .retry_if(|_| true)
+}
+
+async fn upload(client: &Flaky, policy: RetryPolicy) -> Result<CompletedUpload> {
+    let upload = UploadBuilder::new(client.clone())
+        .retry_policy(policy)
+        .with_uri(("bucket", "key"))
+        .build();
+    let items = ["abc", "def"].map(|s| s.as_bytes().to_vec());
+    stream::iter(items).collect_upload(upload).await
+}
+
+#[tokio::test]
+async fn requests_are_retried() {
+    let client = Flaky::new(2);
+    upload(&client, policy(3)).await.unwrap();
+
+    assert_eq!(client.create.load(Ordering::SeqCst), 3);
+    assert_eq!(client.part.load(Ordering::SeqCst), 3);
+    assert_eq!(client.complete.load(Ordering::SeqCst), 3);
+    let obj = client.inner.object(&ObjectUri::from(("bucket", "key")));
+    assert_eq!(obj.unwrap(), &b"abcdef"[..]);
+}
+
+#[tokio::test]
+async fn retries_run_out() {
+    let client = Flaky::new(3);
+    let e = upload(&client, policy(3)).await.unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Sdk));
+    assert_eq!(client.create.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn not_retried_by_default() {
+    let client = Flaky::new(1);
+    assert!(upload(&client, RetryPolicy::default()).await.is_err());
+    assert_eq!(client.create.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn only_retryable_errors_are_retried() {
+    let client = Flaky::new(1);
+    let policy = RetryPolicy::default()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(1));
+    assert!(upload(&client, policy).await.is_err());
+    assert_eq!(client.create.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,86 @@
+use aws_multipart_upload::codec::LinesEncoder;
+use aws_multipart_upload::error::{Error, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::write::UploadStreamExt as _;
+use aws_multipart_upload::{
+    ByteSize, InMemoryClient, LocalMultipartUpload, LocalSendRequest, MultipartUpload, ObjectUri,
+    SendRequest, UploadBuilder, UploadClient,
+};
+
+use futures::stream;
+use std::rc::Rc;
+
+fn assert_send<T: Send>() {}
+fn assert_send_sync<T: Send + Sync>() {}
+
+/// A client whose request futures are not `Send`.
+struct LocalClient(Rc<InMemoryClient>);
+
+impl LocalSendRequest for LocalClient {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        SendRequest::send_create_upload_request(&*self.0, req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        SendRequest::send_new_part_upload_request(&*self.0, req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        SendRequest::send_complete_upload_request(&*self.0, req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        SendRequest::send_abort_upload_request(&*self.0, req).await
+    }
+}
+
+fn lines(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("{i:01023}")).collect()
+}
+
+#[test]
+fn client_and_upload_are_send() {
+    assert_send_sync::<UploadClient>();
+    assert_send_sync::<Error>();
+    assert_send::<SendUploadPart>();
+    assert_send::<MultipartUpload<LinesEncoder>>();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn upload_from_spawned_task() {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let upload = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .with_encoder(LinesEncoder::default())
+        .with_uri(uri.clone())
+        .build();
+
+    let items = lines(12 * 1024);
+    let task = tokio::spawn(stream::iter(items.clone()).collect_upload(upload));
+    task.await.unwrap().unwrap();
+
+    let obj = client.object(&uri).unwrap();
+    let expect: String = items.iter().map(|line| format!("{line}\n")).collect();
+    assert_eq!(obj, expect.as_bytes());
+}
+
+#[tokio::test]
+async fn upload_with_local_client() {
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let upload: LocalMultipartUpload<LinesEncoder> =
+        UploadBuilder::new_local(LocalClient(Rc::new(client.clone())))
+            .part_size(ByteSize::mib(5))
+            .with_encoder(LinesEncoder::default())
+            .with_uri(uri.clone())
+            .build();
+
+    let items = lines(6 * 1024);
+    stream::iter(items.clone())
+        .collect_upload(upload)
+        .await
+        .unwrap();
+    assert!(client.in_progress().is_empty());
+    assert_eq!(client.object(&uri).unwrap().len(), 6 * 1024 * 1024);
+}
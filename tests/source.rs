@@ -0,0 +1,124 @@
+use aws_multipart_upload::error::ErrorKind;
+use aws_multipart_upload::request::ChecksumAlgorithm;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, UploadBuilder};
+
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+const MIB: usize = 1024 * 1024;
+
+fn bytes(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+/// A directory for the files of one test that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Reads `n` bytes of ones and then fails.
+struct FailAfter(usize);
+
+impl AsyncRead for FailAfter {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.0 == 0 {
+            return Poll::Ready(Err(io::Error::other("device error")));
+        }
+        let n = self.0.min(buf.remaining());
+        buf.put_slice(&vec![1; n]);
+        self.0 -= n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn upload_file() {
+    let dir = TempDir::new("aws-multipart-upload-file");
+    let client = InMemoryClient::new();
+
+    for size in [0, 10, 5 * MIB, 23 * MIB + 7] {
+        let path = dir.0.join(size.to_string());
+        let expect = bytes(size);
+        std::fs::write(&path, &expect).unwrap();
+
+        let uri = ObjectUri::from(("bucket", size.to_string()));
+        UploadBuilder::new(client.clone())
+            .part_size(ByteSize::mib(5))
+            .max_active_tasks(3)
+            .checksum(ChecksumAlgorithm::Crc32c)
+            .upload_file(&path, uri.clone())
+            .await
+            .unwrap();
+        assert_eq!(client.object(&uri).unwrap(), expect);
+    }
+}
+
+#[tokio::test]
+async fn missing_file() {
+    let dir = TempDir::new("aws-multipart-upload-missing");
+    let client = InMemoryClient::new();
+
+    let e = UploadBuilder::new(client.clone())
+        .upload_file(dir.0.join("missing"), ("bucket", "key"))
+        .await
+        .unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Read));
+    assert!(client.in_progress().is_empty());
+}
+
+#[tokio::test]
+async fn upload_reader() {
+    let client = InMemoryClient::new();
+
+    for size in [0, 10, 10 * MIB, 12 * MIB + 3] {
+        let expect = bytes(size);
+        let uri = ObjectUri::from(("bucket", size.to_string()));
+        UploadBuilder::new(client.clone())
+            .part_size(ByteSize::mib(5))
+            .upload_reader(&expect[..], uri.clone())
+            .await
+            .unwrap();
+        assert_eq!(client.object(&uri).unwrap(), expect);
+    }
+}
+
+#[tokio::test]
+async fn read_error_fails_upload() {
+    let client = InMemoryClient::new();
+
+    let e = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .upload_reader(FailAfter(6 * MIB), ("bucket", "key"))
+        .await
+        .unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Read));
+    assert_eq!(client.in_progress().len(), 1);
+
+    let e = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .abort_on_error(true)
+        .upload_reader(FailAfter(6 * MIB), ("bucket", "key"))
+        .await
+        .unwrap_err();
+    assert_eq!(e.aborted(), Some(true));
+    assert_eq!(client.in_progress().len(), 1);
+}
@@ -0,0 +1,138 @@
+use aws_multipart_upload::error::{ErrorKind, Result};
+use aws_multipart_upload::request::*;
+use aws_multipart_upload::{ByteSize, InMemoryClient, ObjectUri, SendRequest, UploadBuilder};
+
+use multipart_write::MultipartWriteExt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt as _;
+
+const MIB: usize = 1024 * 1024;
+
+fn bytes(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+/// A directory to stage the parts of one test in that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    fn files(&self) -> usize {
+        std::fs::read_dir(&self.0).unwrap().count()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Sends requests to an `InMemoryClient`, recording for each part body whether
+/// it is a file that exists when the part is uploaded.
+#[derive(Clone, Default)]
+struct StagedParts {
+    inner: InMemoryClient,
+    staged: Arc<Mutex<Vec<bool>>>,
+}
+
+impl SendRequest for StagedParts {
+    async fn send_create_upload_request(&self, req: CreateRequest) -> Result<UploadData> {
+        self.inner.send_create_upload_request(req).await
+    }
+
+    async fn send_new_part_upload_request(&self, req: UploadPartRequest) -> Result<CompletedPart> {
+        let staged = match req.body() {
+            RequestBody::File(file) => file.path().exists(),
+            RequestBody::Bytes(_) => false,
+        };
+        self.staged.lock().unwrap().push(staged);
+        self.inner.send_new_part_upload_request(req).await
+    }
+
+    async fn send_complete_upload_request(&self, req: CompleteRequest) -> Result<CompletedUpload> {
+        self.inner.send_complete_upload_request(req).await
+    }
+
+    async fn send_abort_upload_request(&self, req: AbortRequest) -> Result<()> {
+        self.inner.send_abort_upload_request(req).await
+    }
+}
+
+#[tokio::test]
+async fn parts_are_uploaded_from_files() {
+    let dir = TempDir::new("aws-multipart-upload-stage-files");
+    let client = StagedParts::default();
+    let expect = bytes(11 * MIB);
+
+    let out = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .checksum(ChecksumAlgorithm::Crc32c)
+        .stage_parts_in(&dir.0)
+        .upload_reader(&expect[..], ("bucket", "key"))
+        .await
+        .unwrap();
+
+    assert_eq!(*client.staged.lock().unwrap(), [true; 3]);
+    assert_eq!(client.inner.object(&out.uri).unwrap(), expect);
+    assert_eq!(dir.files(), 0);
+}
+
+#[tokio::test]
+async fn stage_parts_of_writer() {
+    let dir = TempDir::new("aws-multipart-upload-stage-writer");
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+    let expect = bytes(23 * MIB);
+
+    let mut writer = UploadBuilder::new(client.clone())
+        .part_size(ByteSize::mib(5))
+        .stage_parts_in(&dir.0)
+        .with_uri(uri.clone())
+        .build_writer();
+    writer.write_all(&expect).await.unwrap();
+    writer.shutdown().await.unwrap();
+
+    assert_eq!(client.object(&uri).unwrap(), expect);
+    assert_eq!(dir.files(), 0);
+}
+
+#[tokio::test]
+async fn stage_parts_of_upload() {
+    let dir = TempDir::new("aws-multipart-upload-stage-upload");
+    let client = InMemoryClient::new();
+    let uri = ObjectUri::from(("bucket", "key"));
+
+    let mut upload = UploadBuilder::new(client.clone())
+        .stage_parts_in(&dir.0)
+        .with_uri(uri.clone())
+        .build();
+    upload.send_part(bytes(5 * MIB)).await.unwrap();
+    upload.send_part(b"end".to_vec()).await.unwrap();
+    MultipartWriteExt::<Vec<u8>>::complete(&mut upload)
+        .await
+        .unwrap();
+
+    let obj = client.object(&uri).unwrap();
+    assert_eq!(obj[..5 * MIB], bytes(5 * MIB));
+    assert_eq!(&obj[5 * MIB..], b"end");
+    assert_eq!(dir.files(), 0);
+}
+
+#[tokio::test]
+async fn missing_stage_dir() {
+    let dir = TempDir::new("aws-multipart-upload-stage-missing");
+    let e = UploadBuilder::new(InMemoryClient::new())
+        .stage_parts_in(dir.0.join("missing"))
+        .upload_reader(&b"abc"[..], ("bucket", "key"))
+        .await
+        .unwrap_err();
+    assert!(matches!(e.kind(), ErrorKind::Disk));
+}